@@ -1,15 +1,1539 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{
+    ConnectionError, KuzzleRequest, QueryOptions, RetryPolicy, Right, SdkError, User,
+};
+use serde_json::{from_value, Value};
+use std::error::Error;
+use std::thread;
 
 pub struct AuthController<'a>(pub &'a Kuzzle);
 
 impl<'a> AuthController<'a> {
     fn kuzzle(&self) -> &'a Kuzzle {
-        &self.0
+        self.0
     }
 
-    pub fn login(&self, options: QueryOptions) {
-        let req: KuzzleRequest = KuzzleRequest::new("auth", "login");
-        self.kuzzle().query(req, options).is_ok();
+    /// Authenticates a user with the given strategy and credentials.
+    /// On success, the JWT is stored on the Kuzzle client and returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let jwt = kuzzle.auth().login(
+    ///     "local",
+    ///     json!({ "username": "ferris", "password": "crab" }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn login(
+        &self,
+        strategy: &str,
+        credentials: Value,
+        options: QueryOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        if strategy.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "AuthController::login",
+                "strategy argument must not be empty.",
+            )));
+        }
+
+        if !credentials.is_object() {
+            return Err(Box::new(SdkError::new(
+                "AuthController::login",
+                "credentials argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("auth", "login",
+            query: { "strategy" => strategy }
+        );
+        for (key, value) in credentials.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => {
+                let jwt = res.result()["jwt"].as_str().unwrap().to_string();
+                self.kuzzle().set_jwt(jwt.clone());
+                Ok(jwt)
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Authenticates a user like `login`, retrying on connection errors
+    /// as configured by `policy`, e.g. to survive a transient network
+    /// failure at startup. Errors reported by Kuzzle, such as wrong
+    /// credentials, are returned right away.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, RetryPolicy};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let jwt = kuzzle.auth().login_with_retry(
+    ///     "local",
+    ///     json!({ "username": "ferris", "password": "crab" }),
+    ///     RetryPolicy::new().set_max_retries(5)
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn login_with_retry(
+        &self,
+        strategy: &str,
+        credentials: Value,
+        policy: RetryPolicy,
+    ) -> Result<String, Box<dyn Error>> {
+        let mut retries = 0;
+
+        loop {
+            match self.login(strategy, credentials.clone(), QueryOptions::new()) {
+                Err(ref err) if retries < policy.max_retries() && is_connection_error(&**err) => {
+                    retries += 1;
+                    thread::sleep(policy.delay());
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Checks a JWT validity, without authenticating with it.
+    /// Returns whether the token is valid and, if so, its expiration date
+    /// as an epoch-millis timestamp. An expired or malformed token is not
+    /// an error: it is reported as invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().check_token("some-jwt");
+    ///
+    /// ```
+    ///
+    pub fn check_token(&self, token: &str) -> Result<(bool, Option<u64>), Box<dyn Error>> {
+        if token.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "AuthController::check_token",
+                "token argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("auth", "checkToken",
+            body: { "token" => token }
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok((
+                res.result()["valid"].as_bool().unwrap(),
+                res.result()["expiresAt"].as_u64(),
+            )),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns the user authenticated by the current JWT.
+    /// Fails with an `SdkError`, without calling Kuzzle, if no JWT is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().get_current_user();
+    ///
+    /// ```
+    ///
+    pub fn get_current_user(&self) -> Result<User, Box<dyn Error>> {
+        self.check_authenticated("AuthController::get_current_user")?;
+
+        let req: KuzzleRequest = kuzzle_request!("auth", "getCurrentUser");
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(User::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns the names of the authentication strategies available
+    /// on Kuzzle. No JWT is required.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().get_strategies();
+    ///
+    /// ```
+    ///
+    pub fn get_strategies(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let req: KuzzleRequest = kuzzle_request!("auth", "getStrategies");
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result_as()?),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns the rights of the current user, or the anonymous user's ones
+    /// if no JWT is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().get_my_rights();
+    ///
+    /// ```
+    ///
+    pub fn get_my_rights(&self) -> Result<Vec<Right>, Box<dyn Error>> {
+        let req: KuzzleRequest = kuzzle_request!("auth", "getMyRights");
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(from_value(res.result()["hits"].clone())?),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Updates the current user's content with the given fields and
+    /// returns the updated user. Fields left out are kept unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().update_self(json!({ "age": 42 }));
+    ///
+    /// ```
+    ///
+    pub fn update_self(&self, content: Value) -> Result<User, Box<dyn Error>> {
+        if !content.is_object() {
+            return Err(Box::new(SdkError::new(
+                "AuthController::update_self",
+                "content argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("auth", "updateSelf");
+        for (key, value) in content.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(User::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Creates credentials of the current user for the given strategy.
+    /// Returns the created credentials, without any secret.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().create_my_credentials("local", json!({ "username": "ferris", "password": "crab" }));
+    ///
+    /// ```
+    ///
+    pub fn create_my_credentials(
+        &self,
+        strategy: &str,
+        credentials: Value,
+    ) -> Result<Value, Box<dyn Error>> {
+        self.credentials_query(
+            "AuthController::create_my_credentials",
+            "createMyCredentials",
+            strategy,
+            Some(credentials),
+        )
+    }
+
+    /// Updates credentials of the current user for the given strategy.
+    /// Returns the updated credentials, without any secret.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().update_my_credentials("local", json!({ "password": "lobster" }));
+    ///
+    /// ```
+    ///
+    pub fn update_my_credentials(
+        &self,
+        strategy: &str,
+        credentials: Value,
+    ) -> Result<Value, Box<dyn Error>> {
+        self.credentials_query(
+            "AuthController::update_my_credentials",
+            "updateMyCredentials",
+            strategy,
+            Some(credentials),
+        )
+    }
+
+    /// Checks that the given credentials would be accepted for the given
+    /// strategy, without storing them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().validate_my_credentials("local", json!({ "username": "ferris", "password": "crab" }));
+    ///
+    /// ```
+    ///
+    pub fn validate_my_credentials(
+        &self,
+        strategy: &str,
+        credentials: Value,
+    ) -> Result<bool, Box<dyn Error>> {
+        let result = self.credentials_query(
+            "AuthController::validate_my_credentials",
+            "validateMyCredentials",
+            strategy,
+            Some(credentials),
+        )?;
+        Ok(result.as_bool().unwrap())
+    }
+
+    /// Deletes credentials of the current user for the given strategy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().delete_my_credentials("local");
+    ///
+    /// ```
+    ///
+    pub fn delete_my_credentials(&self, strategy: &str) -> Result<bool, Box<dyn Error>> {
+        let result = self.credentials_query(
+            "AuthController::delete_my_credentials",
+            "deleteMyCredentials",
+            strategy,
+            None,
+        )?;
+        Ok(result["acknowledged"].as_bool().unwrap())
+    }
+
+    /// Returns credentials of the current user for the given strategy,
+    /// without any secret.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().get_my_credentials("local");
+    ///
+    /// ```
+    ///
+    pub fn get_my_credentials(&self, strategy: &str) -> Result<Value, Box<dyn Error>> {
+        self.credentials_query(
+            "AuthController::get_my_credentials",
+            "getMyCredentials",
+            strategy,
+            None,
+        )
+    }
+
+    /// Checks whether the current user has credentials for the given strategy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().credentials_exist("local");
+    ///
+    /// ```
+    ///
+    pub fn credentials_exist(&self, strategy: &str) -> Result<bool, Box<dyn Error>> {
+        let result = self.credentials_query(
+            "AuthController::credentials_exist",
+            "credentialsExist",
+            strategy,
+            None,
+        )?;
+        Ok(result.as_bool().unwrap())
+    }
+
+    /// Renews the current session without sending the credentials again:
+    /// the current JWT is exchanged for a new one, which is stored on the
+    /// Kuzzle client and returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().refresh_token();
+    ///
+    /// ```
+    ///
+    pub fn refresh_token(&self) -> Result<String, Box<dyn Error>> {
+        self.check_authenticated("AuthController::refresh_token")?;
+
+        let req: KuzzleRequest = kuzzle_request!("auth", "refreshToken");
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => {
+                let jwt = res.result()["jwt"].as_str().unwrap().to_string();
+                self.kuzzle().set_jwt(jwt.clone());
+                Ok(jwt)
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Revokes the current JWT and clears it from the Kuzzle client, so
+    /// subsequent requests are anonymous. Does nothing if no JWT is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.auth().logout();
+    ///
+    /// ```
+    ///
+    pub fn logout(&self) -> Result<(), Box<dyn Error>> {
+        if self.kuzzle().jwt().is_empty() {
+            return Ok(());
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("auth", "logout");
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => {
+                self.kuzzle().set_jwt(String::new());
+                Ok(())
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Fails with an `SdkError` if no JWT is set.
+    fn check_authenticated(&self, cause: &str) -> Result<(), Box<dyn Error>> {
+        if self.kuzzle().jwt().is_empty() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "not authenticated: login first.",
+            )));
+        }
+        Ok(())
+    }
+
+    /// Sends an `auth/<action>` request on the current user's credentials
+    /// for `strategy` and returns its result.
+    fn credentials_query(
+        &self,
+        cause: &str,
+        action: &str,
+        strategy: &str,
+        credentials: Option<Value>,
+    ) -> Result<Value, Box<dyn Error>> {
+        self.check_authenticated(cause)?;
+
+        if strategy.is_empty() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "strategy argument must not be empty.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("auth", action,
+            query: { "strategy" => strategy }
+        );
+        if let Some(credentials) = credentials {
+            if !credentials.is_object() {
+                return Err(Box::new(SdkError::new(
+                    cause,
+                    "credentials argument must be a JSON object.",
+                )));
+            }
+            for (key, value) in credentials.as_object().unwrap() {
+                req = req.add_to_body(key.to_string(), value.clone());
+            }
+        }
+
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().clone()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+}
+
+/// Whether the request failed to reach Kuzzle, as opposed to being
+/// rejected by it.
+fn is_connection_error(err: &(dyn Error + 'static)) -> bool {
+    if err.is::<ConnectionError>() {
+        return true;
+    }
+
+    match err.downcast_ref::<reqwest::Error>() {
+        Some(http_err) => http_err.is_http() || http_err.is_timeout(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::{Http, Protocol};
+    use crate::types::{
+        ConnectionState, KuzzleError, KuzzleOptions, KuzzleResponse, QueueStats, RightValue,
+    };
+    use mockito::{self, Matcher};
+    use serde_json::json;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn login_ok() {
+        let _m = mockito::mock("POST", "/_login/local")
+            .match_body(Matcher::Json(
+                json!({ "username": "ferris", "password": "crab" }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "login",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "jwt": "ferris-jwt",
+                        "expiresAt": 1321085955000,
+                        "ttl": 360000
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().login(
+            "local",
+            json!({ "username": "ferris", "password": "crab" }),
+            QueryOptions::new(),
+        );
+
+        assert_eq!(res.unwrap(), "ferris-jwt");
+        assert_eq!(k.jwt(), "ferris-jwt");
+        _m.assert();
+    }
+
+    #[test]
+    fn login_fail_bad_credentials() {
+        let _m = mockito::mock("POST", "/_login/local")
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 401,
+                    "error": {
+                        "message": "wrong username or password",
+                        "status": 401,
+                        "stack": "UnauthorizedError: wrong username or password\n"
+                    },
+                    "controller": "auth",
+                    "action": "login",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().login(
+            "local",
+            json!({ "username": "ferris", "password": "shrimp" }),
+            QueryOptions::new(),
+        );
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(401));
+        assert_eq!(k.jwt(), "");
+    }
+
+    /// A protocol failing to reach Kuzzle a given number of times,
+    /// then accepting the login.
+    struct FlakyProtocol {
+        failures: usize,
+        attempts: Rc<Cell<usize>>,
+    }
+
+    impl Protocol for FlakyProtocol {
+        fn once(&self) {}
+        fn listener_count(&self) {}
+        fn connect(&self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+        fn send(
+            &self,
+            _req: KuzzleRequest,
+            _options: QueryOptions,
+        ) -> Result<KuzzleResponse, Box<dyn Error>> {
+            self.attempts.set(self.attempts.get() + 1);
+            if self.attempts.get() <= self.failures {
+                return Err(Box::new(ConnectionError::new("getaddrinfo EAI_AGAIN")));
+            }
+            Ok(from_value(json!({
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "controller": "auth",
+                "action": "login",
+                "result": { "_id": "ferris", "jwt": "ferris-jwt" }
+            }))?)
+        }
+        fn cancel_request(&self, _request_id: &str) -> bool {
+            false
+        }
+        fn close(&self) {}
+        fn state(&self) -> ConnectionState {
+            ConnectionState::Connected
+        }
+        fn request_history(&self) {}
+        fn start_queuing(&self) {}
+        fn stop_queuing(&self) {}
+        fn clear_queue(&self) {}
+        fn queue_stats(&self) -> QueueStats {
+            QueueStats::default()
+        }
+    }
+
+    fn flaky_kuzzle(failures: usize) -> (Kuzzle, Rc<Cell<usize>>) {
+        let attempts = Rc::new(Cell::new(0));
+        let protocol = FlakyProtocol {
+            failures,
+            attempts: attempts.clone(),
+        };
+        (Kuzzle::new(protocol), attempts)
+    }
+
+    #[test]
+    fn login_with_retry_ok_after_transient_failures() {
+        let (k, attempts) = flaky_kuzzle(2);
+        let res = k.auth().login_with_retry(
+            "local",
+            json!({ "username": "ferris", "password": "crab" }),
+            RetryPolicy::new().set_max_retries(3).set_delay(0),
+        );
+
+        assert_eq!(res.unwrap(), "ferris-jwt");
+        assert_eq!(k.jwt(), "ferris-jwt");
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn login_with_retry_fail_retries_exhausted() {
+        let (k, attempts) = flaky_kuzzle(usize::MAX);
+        let res = k.auth().login_with_retry(
+            "local",
+            json!({ "username": "ferris", "password": "crab" }),
+            RetryPolicy::new().set_max_retries(2).set_delay(0),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<ConnectionError>().is_some());
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(k.jwt(), "");
+    }
+
+    #[test]
+    fn login_with_retry_fail_bad_credentials() {
+        let _m = mockito::mock("POST", "/_login/local")
+            .match_body(Matcher::Json(
+                json!({ "username": "ferris", "password": "lobster" }),
+            ))
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 401,
+                    "error": {
+                        "message": "wrong username or password",
+                        "status": 401
+                    },
+                    "controller": "auth",
+                    "action": "login",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().login_with_retry(
+            "local",
+            json!({ "username": "ferris", "password": "lobster" }),
+            RetryPolicy::new().set_max_retries(3).set_delay(0),
+        );
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(401));
+        _m.assert();
+    }
+
+    #[test]
+    fn check_token_ok_valid() {
+        let _m = mockito::mock("POST", "/_checkToken")
+            .match_header("authorization", Matcher::Missing)
+            .match_body(Matcher::Json(json!({ "token": "ferris-jwt" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "checkToken",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "valid": true,
+                        "expiresAt": 1321085955000
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("own-jwt".to_string());
+        let res = k.auth().check_token("ferris-jwt");
+
+        assert_eq!(res.unwrap(), (true, Some(1321085955000)));
+        _m.assert();
+    }
+
+    #[test]
+    fn check_token_ok_invalid() {
+        let _m = mockito::mock("POST", "/_checkToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "checkToken",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "valid": false,
+                        "state": "Json Web Token Error"
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().check_token("not-a-jwt");
+
+        assert_eq!(res.unwrap(), (false, None));
+    }
+
+    #[test]
+    fn check_token_fail_error() {
+        let _m = mockito::mock("POST", "/_checkToken")
+            .with_status(500)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 500,
+                    "error": {
+                        "message": "Internal error",
+                        "status": 500,
+                        "stack": "InternalError: Internal error\n"
+                    },
+                    "controller": "auth",
+                    "action": "checkToken",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().check_token("ferris-jwt");
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn get_current_user_ok() {
+        let _m = mockito::mock("GET", "/users/_me")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "getCurrentUser",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_source": {
+                            "name": "Ferris the crab",
+                            "profileIds": ["default", "rustacean"]
+                        },
+                        "_meta": {
+                            "author": "-1",
+                            "createdAt": 1321085955000
+                        },
+                        "strategies": ["local"]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let user = k.auth().get_current_user().unwrap();
+
+        assert_eq!(user.id(), "ferris");
+        assert_eq!(user.content()["name"], "Ferris the crab");
+        assert_eq!(
+            user.profile_ids(),
+            &vec!["default".to_string(), "rustacean".to_string()]
+        );
+        _m.assert();
+    }
+
+    #[test]
+    fn get_current_user_fail_anonymous() {
+        let _m = mockito::mock("GET", "/users/_me").expect(0).create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().get_current_user();
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+        _m.assert();
+    }
+
+    #[test]
+    fn get_strategies_ok() {
+        let _m = mockito::mock("GET", "/strategies")
+            .match_header("authorization", Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "getStrategies",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": ["local", "oauth"]
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let strategies = k.auth().get_strategies().unwrap();
+
+        assert_eq!(strategies, vec!["local".to_string(), "oauth".to_string()]);
+        _m.assert();
+    }
+
+    #[test]
+    fn get_strategies_ok_local_only() {
+        let _m = mockito::mock("GET", "/strategies")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "getStrategies",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": ["local"]
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let strategies = k.auth().get_strategies().unwrap();
+
+        assert_eq!(strategies, vec!["local".to_string()]);
+        _m.assert();
+    }
+
+    #[test]
+    fn get_my_rights_ok() {
+        let _m = mockito::mock("GET", "/users/_me/_rights")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "getMyRights",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "hits": [
+                            { "controller": "document", "action": "create", "index": "ferris_index", "collection": "*", "value": "allowed" },
+                            { "controller": "document", "action": "delete", "index": "*", "collection": "*", "value": "denied" },
+                            { "controller": "document", "action": "update", "index": "ferris_index", "collection": "crabs", "value": "conditional" }
+                        ],
+                        "total": 3
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let rights = k.auth().get_my_rights().unwrap();
+
+        assert_eq!(rights.len(), 3);
+        assert_eq!(rights[0].action(), "create");
+        assert_eq!(rights[0].index(), "ferris_index");
+        assert_eq!(rights[0].value(), RightValue::Allowed);
+        assert_eq!(rights[1].value(), RightValue::Denied);
+        assert_eq!(rights[2].collection(), "crabs");
+        assert_eq!(rights[2].value(), RightValue::Conditional);
+        _m.assert();
+    }
+
+    #[test]
+    fn get_my_rights_fail_forbidden_anonymous() {
+        let _m = mockito::mock("GET", "/users/_me/_rights")
+            .match_header("authorization", Matcher::Missing)
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 401,
+                    "error": {
+                        "message": "Unauthorized action [auth/getMyRights] for anonymous user",
+                        "status": 401
+                    },
+                    "controller": "auth",
+                    "action": "getMyRights",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().get_my_rights();
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(401));
+    }
+
+    #[test]
+    fn update_self_ok() {
+        let _m = mockito::mock("PUT", "/_updateSelf")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .match_body(Matcher::Json(json!({ "age": 42 })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "updateSelf",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_source": {
+                            "name": "Ferris the crab",
+                            "age": 42,
+                            "profileIds": ["default"]
+                        }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let user = k.auth().update_self(json!({ "age": 42 })).unwrap();
+
+        assert_eq!(user.id(), "ferris");
+        assert_eq!(user.content()["age"], 42);
+        assert_eq!(user.content()["name"], "Ferris the crab");
+        assert_eq!(user.profile_ids(), &vec!["default".to_string()]);
+        _m.assert();
+    }
+
+    #[test]
+    fn update_self_fail_anonymous() {
+        let _m = mockito::mock("PUT", "/_updateSelf")
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 401,
+                    "error": {
+                        "message": "User cannot update itself: not authenticated",
+                        "status": 401
+                    },
+                    "controller": "auth",
+                    "action": "updateSelf",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().update_self(json!({ "age": 42 }));
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(401));
+    }
+
+    #[test]
+    fn update_self_fail_content_not_an_object() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().update_self(json!(["age", 42]));
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    fn credentials_response(action: &str, result: &str) -> String {
+        format!(
+            r#"{{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "auth",
+                "action": "{}",
+                "collection": null,
+                "index": null,
+                "volatile": null,
+                "result": {}
+            }}"#,
+            action, result
+        )
+    }
+
+    #[test]
+    fn create_my_credentials_ok() {
+        let _m = mockito::mock("POST", "/credentials/local/_me/_create")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .match_body(Matcher::Json(
+                json!({ "username": "ferris", "password": "crab" }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response(
+                "createMyCredentials",
+                r#"{ "username": "ferris", "kuid": "ferris" }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k
+            .auth()
+            .create_my_credentials("local", json!({ "username": "ferris", "password": "crab" }));
+
+        assert_eq!(res.unwrap()["username"], "ferris");
+        _m.assert();
+    }
+
+    #[test]
+    fn update_my_credentials_ok() {
+        let _m = mockito::mock("PUT", "/credentials/local/_me/_update")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .match_body(Matcher::Json(json!({ "password": "lobster" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response(
+                "updateMyCredentials",
+                r#"{ "username": "ferris", "kuid": "ferris" }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k
+            .auth()
+            .update_my_credentials("local", json!({ "password": "lobster" }));
+
+        assert_eq!(res.unwrap()["kuid"], "ferris");
+        _m.assert();
+    }
+
+    #[test]
+    fn validate_my_credentials_ok() {
+        let _m = mockito::mock("POST", "/credentials/local/_me/_validate")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .match_body(Matcher::Json(
+                json!({ "username": "ferris", "password": "crab" }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response("validateMyCredentials", r#"true"#))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k
+            .auth()
+            .validate_my_credentials("local", json!({ "username": "ferris", "password": "crab" }));
+
+        assert!(res.unwrap());
+        _m.assert();
+    }
+
+    #[test]
+    fn delete_my_credentials_ok() {
+        let _m = mockito::mock("DELETE", "/credentials/local/_me")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response(
+                "deleteMyCredentials",
+                r#"{ "acknowledged": true }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k.auth().delete_my_credentials("local");
+
+        assert!(res.unwrap());
+        _m.assert();
+    }
+
+    #[test]
+    fn get_my_credentials_ok() {
+        let _m = mockito::mock("GET", "/credentials/local/_me")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response(
+                "getMyCredentials",
+                r#"{ "username": "ferris", "kuid": "ferris" }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k.auth().get_my_credentials("local");
+
+        assert_eq!(
+            res.unwrap(),
+            json!({ "username": "ferris", "kuid": "ferris" })
+        );
+        _m.assert();
+    }
+
+    #[test]
+    fn credentials_exist_ok() {
+        let _m = mockito::mock("GET", "/credentials/local/_me/_exists")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response("credentialsExist", r#"false"#))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k.auth().credentials_exist("local");
+
+        assert!(!res.unwrap());
+        _m.assert();
+    }
+
+    #[test]
+    fn validate_my_credentials_fail_error() {
+        let _m = mockito::mock("POST", "/credentials/local/_me/_validate")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 400,
+                    "error": {
+                        "message": "Password must be at least 8 characters long",
+                        "status": 400
+                    },
+                    "controller": "auth",
+                    "action": "validateMyCredentials",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k
+            .auth()
+            .validate_my_credentials("local", json!({ "password": "crab" }));
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(400));
+    }
+
+    #[test]
+    fn credentials_fail_anonymous() {
+        let _m = mockito::mock("GET", "/credentials/local/_me")
+            .expect(0)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().get_my_credentials("local");
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+        _m.assert();
+    }
+
+    #[test]
+    fn create_my_credentials_fail_empty_strategy() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k.auth().create_my_credentials("", json!({}));
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn refresh_token_ok() {
+        let _m = mockito::mock("POST", "/_refreshToken")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "refreshToken",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "jwt": "ferris-jwt-renewed",
+                        "expiresAt": 1321085955000,
+                        "ttl": 360000
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let jwt = k.auth().refresh_token().unwrap();
+
+        assert_ne!(jwt, "ferris-jwt");
+        assert_eq!(jwt, "ferris-jwt-renewed");
+        assert_eq!(k.jwt(), "ferris-jwt-renewed");
+        _m.assert();
+    }
+
+    #[test]
+    fn refresh_token_fail_anonymous() {
+        let _m = mockito::mock("POST", "/_refreshToken").expect(0).create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().refresh_token();
+
+        let err = res.unwrap_err();
+        let sdk_err = err.downcast_ref::<SdkError>().unwrap();
+        assert_eq!(sdk_err.cause(), "AuthController::refresh_token");
+        _m.assert();
+    }
+
+    #[test]
+    fn logout_ok() {
+        let _m = mockito::mock("POST", "/_logout")
+            .match_header("authorization", "Bearer ferris-jwt")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "logout",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {}
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k.auth().logout();
+
+        assert!(res.is_ok());
+        assert_eq!(k.jwt(), "");
+        _m.assert();
+    }
+
+    #[test]
+    fn logout_ok_without_jwt() {
+        let _m = mockito::mock("POST", "/_logout").expect(0).create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().logout();
+
+        assert!(res.is_ok());
+        _m.assert();
+    }
+
+    #[test]
+    fn logout_fail_error() {
+        let _m = mockito::mock("POST", "/_logout")
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 401,
+                    "error": {
+                        "message": "Invalid token.",
+                        "status": 401,
+                        "stack": "UnauthorizedError: Invalid token.\n"
+                    },
+                    "controller": "auth",
+                    "action": "logout",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_jwt("ferris-jwt".to_string());
+        let res = k.auth().logout();
+
+        assert!(res.is_err());
+        assert_eq!(k.jwt(), "ferris-jwt");
+    }
+
+    #[test]
+    fn login_fail_empty_strategy() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().login("", json!({}), QueryOptions::new());
+
+        assert!(res.is_err());
     }
 }
@@ -1,15 +1,391 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{KuzzleRequest, QueryOptions, SdkError};
+use serde_json::{from_str, Value};
+use std::error::Error;
 
 pub struct BulkController<'a>(pub &'a Kuzzle);
 
 impl<'a> BulkController<'a> {
     pub fn import(&self, options: QueryOptions) {
         let req: KuzzleRequest = KuzzleRequest::new("bulk", "import");
-        self.kuzzle().query(req, options).is_ok();
+        let _ = self.kuzzle().query(req, options);
+    }
+
+    /// Imports NDJSON formatted bulk data (an action line, followed by a
+    /// document line unless the action is a `delete`) into a collection,
+    /// sending `chunk_size` operations per `bulk/import` request, or fewer
+    /// if the server `documentsWriteCount` limit is lower (see
+    /// `Kuzzle::load_server_limits`).
+    ///
+    /// After each chunk, `progress` is called with the number of operations
+    /// processed so far, the total number of operations and the number
+    /// of failed operations so far.
+    /// Returns the number of processed and failed operations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let ndjson = r#"{ "create": { "_id": "ferris" } }
+    /// { "name": "Ferris" }
+    /// { "delete": { "_id": "nemo" } }"#;
+    ///
+    /// let mut render = |processed, total, errors| {
+    ///     println!("{}/{} ({} errors)", processed, total, errors);
+    /// };
+    /// let res = kuzzle.bulk().import_ndjson(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     ndjson,
+    ///     1000,
+    ///     Some(&mut render),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn import_ndjson(
+        &self,
+        index: &str,
+        collection: &str,
+        ndjson: &str,
+        chunk_size: usize,
+        mut progress: Option<&mut dyn FnMut(usize, usize, usize)>,
+        options: QueryOptions,
+    ) -> Result<(usize, usize), Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "BulkController::import_ndjson",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if chunk_size == 0 {
+            return Err(Box::new(SdkError::new(
+                "BulkController::import_ndjson",
+                "chunk_size argument must be greater than 0.",
+            )));
+        }
+
+        let max_chunk_size = self.kuzzle().server_limits().documents_write_count() as usize;
+        let chunk_size = chunk_size.min(max_chunk_size.max(1));
+
+        let operations = parse_operations(ndjson)?;
+        let total = operations.len();
+        let mut processed = 0;
+        let mut errors = 0;
+
+        for chunk in operations.chunks(chunk_size) {
+            let bulk_data: Vec<Value> = chunk.iter().flatten().cloned().collect();
+            let req: KuzzleRequest = kuzzle_request!("bulk", "import",
+                index: index,
+                collection: collection,
+                body: { "bulkData" => bulk_data }
+            );
+            let res = self.kuzzle().query(req, options.clone())?;
+            if let Some(k_err) = res.error() {
+                return Err(Box::new(k_err.clone()));
+            }
+
+            processed += chunk.len();
+            errors += count_failed_items(res.result());
+            if let Some(callback) = progress.as_mut() {
+                callback(processed, total, errors);
+            }
+        }
+
+        Ok((processed, errors))
     }
 
     fn kuzzle(&self) -> &'a Kuzzle {
-        &self.0
+        self.0
+    }
+}
+
+/// Splits NDJSON bulk data into operations: an action line, along with
+/// its document line unless the action is a `delete`.
+fn parse_operations(ndjson: &str) -> Result<Vec<Vec<Value>>, Box<dyn Error>> {
+    let mut lines = ndjson.lines().filter(|line| !line.trim().is_empty());
+    let mut operations = Vec::new();
+
+    while let Some(line) = lines.next() {
+        let action: Value = from_str(line)?;
+        if action.get("delete").is_some() {
+            operations.push(vec![action]);
+            continue;
+        }
+
+        match lines.next() {
+            Some(document) => operations.push(vec![action, from_str(document)?]),
+            None => {
+                return Err(Box::new(SdkError::new(
+                    "BulkController::import_ndjson",
+                    "bulk data ends with an action missing its document line.",
+                )))
+            }
+        }
+    }
+
+    Ok(operations)
+}
+
+/// Counts the items of a bulk import result with an error status.
+fn count_failed_items(result: &Value) -> usize {
+    match result["items"].as_array() {
+        Some(items) => items
+            .iter()
+            .filter_map(|item| item.as_object())
+            .filter_map(|item| item.values().next())
+            .filter(|item| item["status"].as_u64().unwrap_or(0) >= 400)
+            .count(),
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::{Document, KuzzleOptions};
+    use mockito::{self, Matcher};
+    use serde_json::{from_value, json};
+
+    fn import_response(items: &str) -> String {
+        format!(
+            r#"{{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "bulk",
+                "action": "import",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {{
+                    "items": {}
+                }}
+            }}"#,
+            items
+        )
+    }
+
+    #[test]
+    fn import_ndjson_ok_progress_per_chunk() {
+        let _first = mockito::mock("POST", "/ferris_index/ferris_collection/_bulk")
+            .match_body(Matcher::Json(json!({
+                "bulkData": [
+                    { "create": { "_id": "ferris" } },
+                    { "name": "Ferris" },
+                    { "create": { "_id": "crab" } },
+                    { "name": "Crab" }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(import_response(
+                r#"[
+                    { "create": { "_id": "ferris", "status": 201 } },
+                    { "create": { "_id": "crab", "status": 409, "error": { "reason": "conflict" } } }
+                ]"#,
+            ))
+            .expect(1)
+            .create();
+        let _second = mockito::mock("POST", "/ferris_index/ferris_collection/_bulk")
+            .match_body(Matcher::Json(json!({
+                "bulkData": [
+                    { "delete": { "_id": "nemo" } },
+                    { "create": { "_id": "shrimp" } },
+                    { "name": "Shrimp" }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(import_response(
+                r#"[
+                    { "delete": { "_id": "nemo", "status": 404 } },
+                    { "create": { "_id": "shrimp", "status": 201 } }
+                ]"#,
+            ))
+            .expect(1)
+            .create();
+        let _third = mockito::mock("POST", "/ferris_index/ferris_collection/_bulk")
+            .match_body(Matcher::Json(json!({
+                "bulkData": [
+                    { "create": { "_id": "lobster" } },
+                    { "name": "Lobster" }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(import_response(
+                r#"[{ "create": { "_id": "lobster", "status": 201 } }]"#,
+            ))
+            .expect(1)
+            .create();
+
+        let ndjson = r#"{ "create": { "_id": "ferris" } }
+{ "name": "Ferris" }
+{ "create": { "_id": "crab" } }
+{ "name": "Crab" }
+{ "delete": { "_id": "nemo" } }
+{ "create": { "_id": "shrimp" } }
+{ "name": "Shrimp" }
+
+{ "create": { "_id": "lobster" } }
+{ "name": "Lobster" }
+"#;
+
+        let mut calls = Vec::new();
+        let mut record = |processed, total, errors| calls.push((processed, total, errors));
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.bulk().import_ndjson(
+            "ferris_index",
+            "ferris_collection",
+            ndjson,
+            2,
+            Some(&mut record),
+            QueryOptions::new(),
+        );
+
+        assert_eq!(res.unwrap(), (5, 2));
+        assert_eq!(calls, vec![(2, 5, 1), (4, 5, 2), (5, 5, 2)]);
+        _first.assert();
+        _second.assert();
+        _third.assert();
+    }
+
+    #[test]
+    fn import_ndjson_ok_invalidates_cached_documents() {
+        let _m = mockito::mock("POST", "/ferris_index/cached_collection/_bulk")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(import_response(
+                r#"[{ "index": { "_id": "ferris", "status": 200 } }]"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_document_cache(true);
+        let ferris: Document =
+            from_value(json!({ "_id": "ferris", "_version": 1, "_source": {} })).unwrap();
+        k.cache_document("ferris_index", "cached_collection", ferris.clone());
+        k.cache_document("ferris_index", "crabs", ferris);
+
+        k.bulk()
+            .import_ndjson(
+                "ferris_index",
+                "cached_collection",
+                "{\"index\":{\"_id\":\"ferris\"}}\n{\"legs\":12}\n",
+                10,
+                None,
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            k.cached_document("ferris_index", "cached_collection", "ferris"),
+            None
+        );
+        assert!(k
+            .cached_document("ferris_index", "crabs", "ferris")
+            .is_some());
+        _m.assert();
+    }
+
+    #[test]
+    fn import_ndjson_ok_split_by_server_limits() {
+        let _config = mockito::mock("GET", "/_getConfig")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "getConfig",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "limits": { "documentsFetchCount": 1000, "documentsWriteCount": 2 }
+                    }
+                }"#,
+            )
+            .create();
+        let _import = mockito::mock("POST", "/ferris_index/ferris_collection/_bulk")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(import_response("[]"))
+            .expect(2)
+            .create();
+
+        let ndjson = r#"{ "create": { "_id": "ferris" } }
+{ "name": "Ferris" }
+{ "create": { "_id": "crab" } }
+{ "name": "Crab" }
+{ "delete": { "_id": "nemo" } }
+"#;
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let limits = k.load_server_limits().unwrap();
+        assert_eq!(limits.documents_write_count(), 2);
+        assert_eq!(k.server_limits().documents_fetch_count(), 1000);
+
+        let mut calls = Vec::new();
+        let mut record = |processed, total, errors| calls.push((processed, total, errors));
+        let res = k.bulk().import_ndjson(
+            "ferris_index",
+            "ferris_collection",
+            ndjson,
+            1000,
+            Some(&mut record),
+            QueryOptions::new(),
+        );
+
+        assert_eq!(res.unwrap(), (3, 0));
+        assert_eq!(calls, vec![(2, 3, 0), (3, 3, 0)]);
+        _import.assert();
+    }
+
+    #[test]
+    fn import_ndjson_fail_missing_document_line() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.bulk().import_ndjson(
+            "ferris_index",
+            "ferris_collection",
+            r#"{ "create": { "_id": "ferris" } }"#,
+            2,
+            None,
+            QueryOptions::new(),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn import_ndjson_fail_zero_chunk_size() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.bulk().import_ndjson(
+            "ferris_index",
+            "ferris_collection",
+            "",
+            0,
+            None,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
     }
 }
@@ -1,16 +1,1480 @@
+use crate::controllers::readiness::{wait_until_ready, POLL_INTERVAL};
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{KuzzleRequest, QueryOptions, SdkError, SearchResult};
+use serde_json::{json, to_value, Value};
+use std::error::Error;
+use std::time::Duration;
 
 pub struct CollectionController<'a>(pub &'a Kuzzle);
 
 impl<'a> CollectionController<'a> {
-    pub fn create(&self, options: QueryOptions) {
-        &self
-            .kuzzle()
-            .query(KuzzleRequest::new("collection", "create"), options);
+    /// Create a new collection in the provided index, with the given
+    /// mapping `properties` if any (see `update_mapping`).
+    ///
+    /// If `wait_for_active_shards` is set (e.g. `"all"` or `"2"`), Kuzzle
+    /// only answers once that many shard copies are active, so the collection
+    /// is usable on every node of a cluster as soon as the call returns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().create(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     Some(json!({ "name": { "type": "keyword" } })),
+    ///     Some("all"),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn create(
+        &self,
+        index: &str,
+        collection: &str,
+        mapping: Option<Value>,
+        wait_for_active_shards: Option<&str>,
+        options: QueryOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        if index.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::create",
+                "index argument must not be empty.",
+            )));
+        }
+
+        if collection.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::create",
+                "collection argument must not be empty.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("collection", "create",
+            index: index,
+            collection: collection
+        );
+
+        if let Some(mapping) = mapping {
+            if !mapping.is_object() {
+                return Err(Box::new(SdkError::new(
+                    "CollectionController::create",
+                    "mapping argument must be a JSON object.",
+                )));
+            }
+
+            req = req.add_to_body("properties".to_string(), mapping);
+        }
+
+        if let Some(shards) = wait_for_active_shards {
+            req = req.add_to_query_strings("wait_for_active_shards".to_string(), to_value(shards)?);
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Deletes the validation specifications of a collection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().delete_specifications("ferris_index", "ferris_collection");
+    ///
+    /// ```
+    ///
+    pub fn delete_specifications(
+        &self,
+        index: &str,
+        collection: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::delete_specifications",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("collection", "deleteSpecifications",
+            index: index,
+            collection: collection
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Creates a collection with the given mapping `properties` if it does
+    /// not exist yet, or updates its mapping otherwise, so provisioning
+    /// scripts can be run again safely. Returns whether the collection
+    /// was created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().ensure(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "name": { "type": "keyword" } })
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn ensure(
+        &self,
+        index: &str,
+        collection: &str,
+        mapping: Value,
+    ) -> Result<bool, Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::ensure",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if self.exists(index, collection, QueryOptions::new())? {
+            self.update_mapping(index, collection, mapping)?;
+            return Ok(false);
+        }
+
+        self.create(index, collection, Some(mapping), None, QueryOptions::new())?;
+        Ok(true)
+    }
+
+    /// Checks whether a collection exists in the provided index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().exists("ferris_index", "ferris_collection", QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn exists(
+        &self,
+        index: &str,
+        collection: &str,
+        options: QueryOptions,
+    ) -> Result<bool, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::exists",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("collection", "exists",
+            index: index,
+            collection: collection
+        );
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result().as_bool().unwrap()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets the mapping of a collection, i.e. its `properties`.
+    /// When the mapping cache is enabled on the Kuzzle client, a cached
+    /// mapping is returned without querying Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().get_mapping("ferris_index", "ferris_collection");
+    ///
+    /// ```
+    ///
+    pub fn get_mapping(&self, index: &str, collection: &str) -> Result<Value, Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::get_mapping",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if let Some(mapping) = self.kuzzle().cached_mapping(index, collection) {
+            return Ok(mapping);
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("collection", "getMapping",
+            index: index,
+            collection: collection
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => {
+                let mapping = res.result()[index]["mappings"][collection]["properties"].clone();
+                self.kuzzle()
+                    .cache_mapping(index, collection, mapping.clone());
+                Ok(mapping)
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets the validation specifications of a collection, e.g.
+    /// `{ "strict": true, "fields": { "legs": { "type": "integer" } } }`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().get_specifications("ferris_index", "ferris_collection");
+    ///
+    /// ```
+    ///
+    pub fn get_specifications(
+        &self,
+        index: &str,
+        collection: &str,
+    ) -> Result<Value, Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::get_specifications",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("collection", "getSpecifications",
+            index: index,
+            collection: collection
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result()["validation"].clone()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Lists the names of the collections of an index. `collection_type`
+    /// filters them: `stored`, `realtime` or `all` (the default).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().list("ferris_index", Some("stored"));
+    ///
+    /// ```
+    ///
+    pub fn list(
+        &self,
+        index: &str,
+        collection_type: Option<&str>,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        if index.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::list",
+                "index argument must not be empty.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("collection", "list", index: index);
+        if let Some(collection_type) = collection_type {
+            if !["stored", "realtime", "all"].contains(&collection_type) {
+                return Err(Box::new(SdkError::new(
+                    "CollectionController::list",
+                    "collection_type argument must be one of stored, realtime or all.",
+                )));
+            }
+
+            req = req.add_to_query_strings("type".to_string(), Value::from(collection_type));
+        }
+
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result()["collections"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|collection| collection["name"].as_str().unwrap().to_string())
+                .collect()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Searches the validation specifications of every collection, using
+    /// Elasticsearch Query DSL. Each hit holds the `index`, `collection`
+    /// and `validation` of a collection. Pagination (`from`, `size` and
+    /// `scroll`) is set through `QueryOptions`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().search_specifications(
+    ///     json!({ "query": { "match": { "index": "ferris_index" } } }),
+    ///     QueryOptions::new().set_size(10),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn search_specifications(
+        &self,
+        query: Value,
+        options: QueryOptions,
+    ) -> Result<SearchResult, Box<dyn Error>> {
+        if !query.is_object() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::search_specifications",
+                "query argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("collection", "searchSpecifications");
+        for (key, value) in query.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result_as()?),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Removes all the documents of a collection, keeping its mapping.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().truncate("ferris_index", "ferris_collection");
+    ///
+    /// ```
+    ///
+    pub fn truncate(&self, index: &str, collection: &str) -> Result<(), Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::truncate",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("collection", "truncate",
+            index: index,
+            collection: collection
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Updates the mapping of a collection with the given `properties`.
+    /// New fields are added, existing ones cannot change their type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().update_mapping(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "name": { "type": "keyword" }, "legs": { "type": "integer" } })
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_mapping(
+        &self,
+        index: &str,
+        collection: &str,
+        mapping: Value,
+    ) -> Result<(), Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::update_mapping",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if !mapping.is_object() {
+            return Err(Box::new(SdkError::new(
+                "CollectionController::update_mapping",
+                "mapping argument must be a JSON object.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("collection", "updateMapping",
+            index: index,
+            collection: collection,
+            body: { "properties" => mapping }
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Replaces the validation specifications of a collection, e.g.
+    /// `{ "strict": true, "fields": { "legs": { "type": "integer" } } }`,
+    /// returning them as stored by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().update_specifications(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "strict": true, "fields": { "legs": { "type": "integer" } } })
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_specifications(
+        &self,
+        index: &str,
+        collection: &str,
+        specifications: Value,
+    ) -> Result<Value, Box<dyn Error>> {
+        let req = specifications_request(
+            "CollectionController::update_specifications",
+            "updateSpecifications",
+            index,
+            collection,
+            specifications,
+        )?;
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result()[index][collection].clone()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Checks validation specifications without storing them. Returns
+    /// whether they are valid, along with the description of each error:
+    /// invalid specifications are not reported as an `Err`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().validate_specifications(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "strict": true, "fields": { "legs": { "type": "integer" } } })
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn validate_specifications(
+        &self,
+        index: &str,
+        collection: &str,
+        specifications: Value,
+    ) -> Result<(bool, Vec<String>), Box<dyn Error>> {
+        let req = specifications_request(
+            "CollectionController::validate_specifications",
+            "validateSpecifications",
+            index,
+            collection,
+            specifications,
+        )?;
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => {
+                let details = match res.result()["details"].as_array() {
+                    Some(details) => details
+                        .iter()
+                        .map(|detail| match detail.as_str() {
+                            Some(detail) => detail.to_string(),
+                            None => detail.to_string(),
+                        })
+                        .collect(),
+                    None => Vec::new(),
+                };
+                Ok((res.result()["valid"].as_bool().unwrap(), details))
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Waits until the collection exists, polling Kuzzle, so writes can
+    /// follow its creation safely. Fails with an `SdkError` once `timeout`
+    /// elapsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use std::time::Duration;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.collection().wait_until_ready(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     Duration::from_secs(5)
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn wait_until_ready(
+        &self,
+        index: &str,
+        collection: &str,
+        timeout: Duration,
+    ) -> Result<(), Box<dyn Error>> {
+        wait_until_ready(
+            "CollectionController::wait_until_ready",
+            &format!("{}/{}", index, collection),
+            timeout,
+            POLL_INTERVAL,
+            || self.exists(index, collection, QueryOptions::new()),
+        )
     }
 
     fn kuzzle(&self) -> &'a Kuzzle {
-        &self.0
+        self.0
+    }
+}
+
+/// Builds a `collection/*Specifications` request, whose body holds the
+/// specifications under their index and collection names.
+fn specifications_request(
+    cause: &str,
+    action: &str,
+    index: &str,
+    collection: &str,
+    specifications: Value,
+) -> Result<KuzzleRequest, Box<dyn Error>> {
+    if index.is_empty() || collection.is_empty() {
+        return Err(Box::new(SdkError::new(
+            cause,
+            "index and collection arguments must not be empty.",
+        )));
+    }
+
+    if !specifications.is_object() {
+        return Err(Box::new(SdkError::new(
+            cause,
+            "specifications argument must be a JSON object.",
+        )));
+    }
+
+    Ok(kuzzle_request!("collection", action,
+        body: { index => json!({ collection: specifications }) }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::{KuzzleError, KuzzleOptions};
+    use mockito::{self, Matcher};
+
+    const CREATE_RESPONSE: &str = r#"{
+        "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+        "status": 200,
+        "error": null,
+        "controller": "collection",
+        "action": "create",
+        "collection": "ferris_collection",
+        "index": "ferris_index",
+        "volatile": null,
+        "result": {
+            "acknowledged": true
+        }
+    }"#;
+
+    #[test]
+    fn create_ok() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(CREATE_RESPONSE)
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().create(
+            "ferris_index",
+            "ferris_collection",
+            None,
+            None,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        _m.assert();
+    }
+
+    #[test]
+    fn create_ok_with_mapping() {
+        let _m = mockito::mock("PUT", "/ferris_index/crab_collection")
+            .match_body(Matcher::Json(json!({
+                "properties": {
+                    "name": { "type": "keyword" },
+                    "legs": { "type": "integer" }
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(CREATE_RESPONSE)
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().create(
+            "ferris_index",
+            "crab_collection",
+            Some(json!({
+                "name": { "type": "keyword" },
+                "legs": { "type": "integer" }
+            })),
+            None,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        _m.assert();
+    }
+
+    #[test]
+    fn create_ok_wait_for_active_shards() {
+        let _m = mockito::mock(
+            "PUT",
+            "/ferris_index/ferris_collection?wait_for_active_shards=all",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(CREATE_RESPONSE)
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().create(
+            "ferris_index",
+            "ferris_collection",
+            None,
+            Some("all"),
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        _m.assert();
+    }
+
+    #[test]
+    fn create_fail_index_not_found() {
+        let _m = mockito::mock("PUT", "/nemo_index/ferris_collection")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "The index \"nemo_index\" does not exist.",
+                        "status": 404
+                    },
+                    "controller": "collection",
+                    "action": "create",
+                    "collection": "ferris_collection",
+                    "index": "nemo_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().create(
+            "nemo_index",
+            "ferris_collection",
+            None,
+            None,
+            QueryOptions::new(),
+        );
+
+        let err = res.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<KuzzleError>().unwrap().status(),
+            Some(404)
+        );
+    }
+
+    const EXISTS_RESPONSE: &str = r#"{
+        "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+        "status": 200,
+        "error": null,
+        "controller": "collection",
+        "action": "exists",
+        "collection": "ferris_collection",
+        "index": "ferris_index",
+        "volatile": null,
+        "result": true
+    }"#;
+
+    fn exists_response(exists: bool) -> String {
+        EXISTS_RESPONSE
+            .replace("ferris_collection", "ensured_collection")
+            .replace(r#""result": true"#, &format!(r#""result": {}"#, exists))
+    }
+
+    #[test]
+    fn ensure_ok_creates_missing_collection() {
+        let _exists = mockito::mock("GET", "/ferris_index/ensured_collection/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(exists_response(false))
+            .expect(1)
+            .create();
+        let _create = mockito::mock("PUT", "/ferris_index/ensured_collection")
+            .match_body(Matcher::Json(json!({
+                "properties": { "name": { "type": "keyword" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(CREATE_RESPONSE)
+            .expect(1)
+            .create();
+        let _update = mockito::mock("PUT", "/ferris_index/ensured_collection/_mapping")
+            .expect(0)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().ensure(
+            "ferris_index",
+            "ensured_collection",
+            json!({ "name": { "type": "keyword" } }),
+        );
+
+        assert!(res.unwrap());
+        _exists.assert();
+        _create.assert();
+        _update.assert();
+    }
+
+    #[test]
+    fn ensure_ok_updates_existing_collection() {
+        let _exists = mockito::mock("GET", "/ferris_index/ensured_collection/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(exists_response(true))
+            .expect(1)
+            .create();
+        let _create = mockito::mock("PUT", "/ferris_index/ensured_collection")
+            .expect(0)
+            .create();
+        let _update = mockito::mock("PUT", "/ferris_index/ensured_collection/_mapping")
+            .match_body(Matcher::Json(json!({
+                "properties": { "name": { "type": "keyword" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "collection",
+                    "action": "updateMapping",
+                    "collection": "ensured_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "acknowledged": true }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().ensure(
+            "ferris_index",
+            "ensured_collection",
+            json!({ "name": { "type": "keyword" } }),
+        );
+
+        assert!(!res.unwrap());
+        _exists.assert();
+        _create.assert();
+        _update.assert();
+    }
+
+    #[test]
+    fn exists_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(EXISTS_RESPONSE)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .collection()
+            .exists("ferris_index", "ferris_collection", QueryOptions::new());
+
+        assert!(res.unwrap());
+    }
+
+    const GET_MAPPING_RESPONSE: &str = r#"{
+        "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+        "status": 200,
+        "error": null,
+        "controller": "collection",
+        "action": "getMapping",
+        "collection": "ferris_collection",
+        "index": "ferris_index",
+        "volatile": null,
+        "result": {
+            "ferris_index": {
+                "mappings": {
+                    "ferris_collection": {
+                        "properties": {
+                            "name": { "type": "keyword" },
+                            "legs": { "type": "integer" }
+                        }
+                    }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn get_mapping_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(GET_MAPPING_RESPONSE)
+            .expect(2)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        for _ in 0..2 {
+            let mapping = k
+                .collection()
+                .get_mapping("ferris_index", "ferris_collection")
+                .unwrap();
+            assert_eq!(mapping["legs"], json!({ "type": "integer" }));
+        }
+
+        _m.assert();
+    }
+
+    #[test]
+    fn get_mapping_ok_cache_hit() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(GET_MAPPING_RESPONSE)
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_mapping_cache(true);
+        let first = k
+            .collection()
+            .get_mapping("ferris_index", "ferris_collection")
+            .unwrap();
+        let second = k
+            .collection()
+            .get_mapping("ferris_index", "ferris_collection")
+            .unwrap();
+
+        assert_eq!(first, second);
+        _m.assert();
+    }
+
+    #[test]
+    fn update_mapping_ok_busts_cache() {
+        let _get = mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(GET_MAPPING_RESPONSE)
+            .expect(2)
+            .create();
+        let _update = mockito::mock("PUT", "/ferris_index/ferris_collection/_mapping")
+            .match_body(Matcher::Json(json!({
+                "properties": { "age": { "type": "integer" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "collection",
+                    "action": "updateMapping",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "acknowledged": true }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_mapping_cache(true);
+        k.collection()
+            .get_mapping("ferris_index", "ferris_collection")
+            .unwrap();
+        k.collection()
+            .update_mapping(
+                "ferris_index",
+                "ferris_collection",
+                json!({ "age": { "type": "integer" } }),
+            )
+            .unwrap();
+        k.collection()
+            .get_mapping("ferris_index", "ferris_collection")
+            .unwrap();
+
+        _get.assert();
+        _update.assert();
+    }
+
+    #[test]
+    fn update_mapping_fail_conflicting_type() {
+        let _m = mockito::mock("PUT", "/ferris_index/crab_collection/_mapping")
+            .match_body(Matcher::Json(json!({
+                "properties": { "legs": { "type": "keyword" } }
+            })))
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 400,
+                    "error": {
+                        "message": "mapper [legs] of different type, current_type [integer], merged_type [keyword]",
+                        "status": 400
+                    },
+                    "controller": "collection",
+                    "action": "updateMapping",
+                    "collection": "crab_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().update_mapping(
+            "ferris_index",
+            "crab_collection",
+            json!({ "legs": { "type": "keyword" } }),
+        );
+
+        _m.assert();
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(400));
+        assert!(k_err.message().contains("of different type"));
+    }
+
+    #[test]
+    fn clear_mapping_cache_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(GET_MAPPING_RESPONSE)
+            .expect(2)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_mapping_cache(true);
+        k.collection()
+            .get_mapping("ferris_index", "ferris_collection")
+            .unwrap();
+        k.clear_mapping_cache();
+        k.collection()
+            .get_mapping("ferris_index", "ferris_collection")
+            .unwrap();
+
+        _m.assert();
+    }
+
+    fn list_response(collections: &str) -> String {
+        format!(
+            r#"{{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "collection",
+                "action": "list",
+                "collection": null,
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {{ "collections": [{}], "type": "all" }}
+            }}"#,
+            collections
+        )
+    }
+
+    #[test]
+    fn list_ok_several_collections() {
+        let _m = mockito::mock("GET", "/ferris_index/_list?type=all")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(list_response(
+                r#"{ "name": "crabs", "type": "stored" },
+                   { "name": "lobsters", "type": "stored" },
+                   { "name": "chat", "type": "realtime" }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().list("ferris_index", Some("all"));
+
+        _m.assert();
+        assert_eq!(res.unwrap(), vec!["crabs", "lobsters", "chat"]);
+    }
+
+    #[test]
+    fn list_ok_empty_index() {
+        let _m = mockito::mock("GET", "/empty_index/_list")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(list_response("").replace("ferris_index", "empty_index"))
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().list("empty_index", None);
+
+        assert!(res.unwrap().is_empty());
+    }
+
+    #[test]
+    fn list_fail_unknown_type() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().list("ferris_index", Some("archived"));
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    fn specifications_response(action: &str, result: &str) -> String {
+        format!(
+            r#"{{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "collection",
+                "action": "{}",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {}
+            }}"#,
+            action, result
+        )
+    }
+
+    #[test]
+    fn delete_specifications_ok() {
+        let _m = mockito::mock("DELETE", "/ferris_index/ferris_collection/_specifications")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(specifications_response(
+                "deleteSpecifications",
+                r#"{ "acknowledged": true }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .collection()
+            .delete_specifications("ferris_index", "ferris_collection");
+
+        _m.assert();
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn get_specifications_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_specifications")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(specifications_response(
+                "getSpecifications",
+                r#"{
+                    "index": "ferris_index",
+                    "collection": "ferris_collection",
+                    "validation": { "strict": true, "fields": { "legs": { "type": "integer" } } }
+                }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .collection()
+            .get_specifications("ferris_index", "ferris_collection");
+
+        _m.assert();
+        assert_eq!(
+            res.unwrap(),
+            json!({ "strict": true, "fields": { "legs": { "type": "integer" } } })
+        );
+    }
+
+    #[test]
+    fn search_specifications_ok() {
+        let _m = mockito::mock("POST", "/validations/_search?size=10")
+            .match_body(Matcher::Json(json!({
+                "query": { "match": { "index": "ferris_index" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(specifications_response(
+                "searchSpecifications",
+                r#"{
+                    "total": 1,
+                    "hits": [{
+                        "_id": "ferris_index#ferris_collection",
+                        "_source": {
+                            "index": "ferris_index",
+                            "collection": "ferris_collection",
+                            "validation": { "strict": true, "fields": {} }
+                        }
+                    }]
+                }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().search_specifications(
+            json!({ "query": { "match": { "index": "ferris_index" } } }),
+            QueryOptions::new().set_size(10),
+        );
+
+        _m.assert();
+        let result = res.unwrap();
+        assert_eq!(result.total(), 1);
+        assert_eq!(result.hits()[0].source()["collection"], "ferris_collection");
+    }
+
+    #[test]
+    fn update_specifications_ok() {
+        let _m = mockito::mock("PUT", "/_specifications")
+            .match_body(Matcher::Json(json!({
+                "ferris_index": {
+                    "ferris_collection": { "strict": true, "fields": { "legs": { "type": "integer" } } }
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(specifications_response(
+                "updateSpecifications",
+                r#"{
+                    "ferris_index": {
+                        "ferris_collection": { "strict": true, "fields": { "legs": { "type": "integer" } } }
+                    }
+                }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().update_specifications(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "strict": true, "fields": { "legs": { "type": "integer" } } }),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap()["fields"]["legs"]["type"], "integer");
+    }
+
+    #[test]
+    fn validate_specifications_ok_valid() {
+        let _m = mockito::mock("POST", "/_validateSpecifications")
+            .match_body(Matcher::Json(json!({
+                "ferris_index": { "ferris_collection": { "strict": true, "fields": {} } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(specifications_response(
+                "validateSpecifications",
+                r#"{ "valid": true }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().validate_specifications(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "strict": true, "fields": {} }),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap(), (true, Vec::new()));
+    }
+
+    #[test]
+    fn validate_specifications_ok_invalid() {
+        let _m = mockito::mock("POST", "/_validateSpecifications")
+            .match_body(Matcher::Json(json!({
+                "ferris_index": {
+                    "ferris_collection": { "strict": true, "fields": { "legs": { "type": "crab" } } }
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(specifications_response(
+                "validateSpecifications",
+                r#"{
+                    "valid": false,
+                    "details": ["In ferris_index.ferris_collection.legs: crab is not a recognized type."],
+                    "description": "Some errors with provided specifications."
+                }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().validate_specifications(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "strict": true, "fields": { "legs": { "type": "crab" } } }),
+        );
+
+        _m.assert();
+        let (valid, details) = res.unwrap();
+        assert!(!valid);
+        assert_eq!(
+            details,
+            vec!["In ferris_index.ferris_collection.legs: crab is not a recognized type."]
+        );
+    }
+
+    #[test]
+    fn validate_specifications_fail_not_an_object() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().validate_specifications(
+            "ferris_index",
+            "ferris_collection",
+            json!(["strict"]),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn truncate_ok_busts_cache() {
+        let _get = mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(GET_MAPPING_RESPONSE)
+            .expect(2)
+            .create();
+        let _truncate = mockito::mock("DELETE", "/ferris_index/ferris_collection/_truncate")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "collection",
+                    "action": "truncate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "acknowledged": true }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_mapping_cache(true);
+        k.collection()
+            .get_mapping("ferris_index", "ferris_collection")
+            .unwrap();
+        k.collection()
+            .truncate("ferris_index", "ferris_collection")
+            .unwrap();
+        k.collection()
+            .get_mapping("ferris_index", "ferris_collection")
+            .unwrap();
+
+        _get.assert();
+        _truncate.assert();
+    }
+
+    #[test]
+    fn wait_until_ready_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(EXISTS_RESPONSE)
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.collection().wait_until_ready(
+            "ferris_index",
+            "ferris_collection",
+            Duration::from_secs(1),
+        );
+
+        assert!(res.is_ok());
+        _m.assert();
+    }
+
+    #[test]
+    fn create_fail_empty_index_name() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .collection()
+            .create("", "ferris_collection", None, None, QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn create_fail_empty_collection_name() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .collection()
+            .create("ferris_index", "", None, None, QueryOptions::new());
+
+        assert!(res.is_err());
     }
 }
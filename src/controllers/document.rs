@@ -1,15 +1,4265 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{
+    Document, KuzzleError, KuzzleRequest, KuzzleResponse, QueryOptions, SdkError, SearchResult,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{from_value, json, to_value, to_writer, Value};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::io::Write;
+use std::vec::IntoIter;
 
 pub struct DocumentController<'a>(pub &'a Kuzzle);
 
 impl<'a> DocumentController<'a> {
-    pub fn create(&self, options: QueryOptions) {
-        let req: KuzzleRequest = KuzzleRequest::new("document", "create");
-        self.kuzzle().query(req, options).is_ok();
+    /// Counts the documents of a collection, or only those matching
+    /// `query` if given. `query` is the search body, e.g.
+    /// `{ "query": { "match": { "species": "crab" } } }`, or a Koncorde
+    /// filter with `options.set_lang("koncorde")`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().count(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     Some(json!({ "query": { "match": { "species": "crab" } } })),
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn count(
+        &self,
+        index: &str,
+        collection: &str,
+        query: Option<Value>,
+        options: QueryOptions,
+    ) -> Result<u64, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::count",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("document", "count",
+            index: index,
+            collection: collection
+        );
+        if let Some(query) = query {
+            if !query.is_object() {
+                return Err(Box::new(SdkError::new(
+                    "DocumentController::count",
+                    "query argument must be a JSON object.",
+                )));
+            }
+
+            for (key, value) in query.as_object().unwrap() {
+                req = req.add_to_body(key.to_string(), value.clone());
+            }
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => match res.result()["count"].as_u64() {
+                Some(count) => Ok(count),
+                None => Err(Box::new(SdkError::new(
+                    "DocumentController::count",
+                    "response is missing the count field.",
+                ))),
+            },
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Creates a new document in the provided collection.
+    /// If no `id` is given, Kuzzle generates one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().create(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     Some("ferris"),
+    ///     json!({ "name": "Ferris", "legs": 10 }),
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn create(
+        &self,
+        index: &str,
+        collection: &str,
+        id: Option<&str>,
+        body: Value,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::create",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if !body.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::create",
+                "body argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("document", "create",
+            index: index,
+            collection: collection
+        );
+        if let Some(id) = id {
+            req = req.set_id(id);
+        }
+        for (key, value) in body.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Creates a new document in the provided collection, or replaces it
+    /// if it already exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().create_or_replace(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     "ferris",
+    ///     json!({ "name": "Ferris", "legs": 10 }),
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn create_or_replace(
+        &self,
+        index: &str,
+        collection: &str,
+        id: &str,
+        document: Value,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty() || id.is_empty())
+            && !options.skip_validation()
+        {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::create_or_replace",
+                "index, collection and id arguments must not be empty.",
+            )));
+        }
+
+        if !document.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::create_or_replace",
+                "document argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("document", "createOrReplace",
+            index: index,
+            collection: collection,
+            id: id
+        );
+        for (key, value) in document.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result().clone()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Deletes a document, given its id.
+    /// Returns the id of the deleted document.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().delete(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     "ferris",
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn delete(
+        &self,
+        index: &str,
+        collection: &str,
+        id: &str,
+        options: QueryOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty() || id.is_empty())
+            && !options.skip_validation()
+        {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::delete",
+                "index, collection and id arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("document", "delete",
+            index: index,
+            collection: collection,
+            id: id
+        );
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result()["_id"].as_str().unwrap().to_string()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Deletes the documents matching the given search query, e.g.
+    /// `{ "query": { "term": { "species": "crab" } } }`.
+    /// Returns the ids of the deleted documents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().delete_by_query(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "query": { "term": { "species": "crab" } } }),
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn delete_by_query(
+        &self,
+        index: &str,
+        collection: &str,
+        query: Value,
+        options: QueryOptions,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::delete_by_query",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if !query.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::delete_by_query",
+                "query argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("document", "deleteByQuery",
+            index: index,
+            collection: collection
+        );
+        for (key, value) in query.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => {
+                let deleted: Vec<String> = from_value(res.result()["ids"].clone())?;
+                Ok(deleted)
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Checks whether a document exists, given its id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().exists("ferris_index", "ferris_collection", "ferris");
+    ///
+    /// ```
+    ///
+    pub fn exists(&self, index: &str, collection: &str, id: &str) -> Result<bool, Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() || id.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::exists",
+                "index, collection and id arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("document", "exists",
+            index: index,
+            collection: collection,
+            id: id
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_bool().unwrap()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Exports the documents of a collection matching `query` (all of them
+    /// if `{}`) as NDJSON, one `{ "_id": ..., "_source": ... }` line per
+    /// document, e.g. for backups. Documents are scrolled through a page
+    /// at a time and written as they come, rather than buffered.
+    /// Returns the number of exported documents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// // Any `std::io::Write`, e.g. a `File`.
+    /// let mut backup: Vec<u8> = Vec::new();
+    /// let res = kuzzle.document().export_ndjson(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({}),
+    ///     &mut backup,
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn export_ndjson<W: Write>(
+        &self,
+        index: &str,
+        collection: &str,
+        query: Value,
+        writer: &mut W,
+    ) -> Result<u64, Box<dyn Error>> {
+        let page = self.search(
+            index,
+            collection,
+            query,
+            QueryOptions::new()
+                .set_size(EXPORT_PAGE_SIZE)
+                .set_scroll(EXPORT_SCROLL),
+        )?;
+
+        let mut exported = 0;
+        for hit in page.hits() {
+            write_ndjson_line(writer, &Document::from_hit(hit))?;
+            exported += 1;
+        }
+
+        if let Some(scroll_id) = page.scroll_id() {
+            for document in self.scroll(scroll_id, EXPORT_SCROLL)? {
+                write_ndjson_line(writer, &document?)?;
+                exported += 1;
+            }
+        }
+
+        writer.flush()?;
+        Ok(exported)
+    }
+
+    /// Gets a document, given its id.
+    /// When the document cache is enabled on the Kuzzle client, a cached
+    /// document is returned without querying Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().get("ferris_index", "ferris_collection", "ferris");
+    ///
+    /// ```
+    ///
+    pub fn get(&self, index: &str, collection: &str, id: &str) -> Result<Document, Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() || id.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::get",
+                "index, collection and id arguments must not be empty.",
+            )));
+        }
+
+        if let Some(document) = self.kuzzle().cached_document(index, collection, id) {
+            return Ok(document);
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("document", "get",
+            index: index,
+            collection: collection,
+            id: id
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => {
+                let document: Document = res.result_as()?;
+                self.kuzzle()
+                    .cache_document(index, collection, document.clone());
+                Ok(document)
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets a document and deserializes its source into a `T`. A source
+    /// which does not fit `T` fails with an `SdkError`, while errors
+    /// returned by Kuzzle are `KuzzleError`s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Crab {
+    ///     name: String,
+    ///     legs: u8,
+    /// }
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().get_as::<Crab>(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     "ferris",
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn get_as<T>(&self, index: &str, collection: &str, id: &str) -> Result<T, Box<dyn Error>>
+    where
+        T: DeserializeOwned,
+    {
+        let document = self.get(index, collection, id)?;
+        Ok(document.source_as()?)
+    }
+
+    /// Creates multiple documents at once. Each document is given as an
+    /// optional id, Kuzzle generating one if missing, and its content.
+    /// Returns a tuple containing the created documents and the list of
+    /// per-document failures reported by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().m_create(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     vec![
+    ///         (Some("crab".to_string()), json!({ "legs": 10 })),
+    ///         (None, json!({ "legs": 8 })),
+    ///     ],
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_create(
+        &self,
+        index: &str,
+        collection: &str,
+        documents: Vec<(Option<String>, Value)>,
+        options: QueryOptions,
+    ) -> Result<(Vec<Document>, Vec<Value>), Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::m_create",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if documents.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::m_create",
+                "documents argument must not be empty.",
+            )));
+        }
+
+        let documents: Vec<Value> = documents
+            .into_iter()
+            .map(|(id, body)| match id {
+                Some(id) => json!({ "_id": id, "body": body }),
+                None => json!({ "body": body }),
+            })
+            .collect();
+        let req: KuzzleRequest = kuzzle_request!("document", "mCreate",
+            index: index,
+            collection: collection,
+            body: { "documents" => documents }
+        );
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => {
+                let created: Vec<Document> = from_value(res.result()["successes"].clone())?;
+                let errors = res.result()["errors"].as_array().unwrap().clone();
+                Ok((created, errors))
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Creates or replaces multiple documents at once.
+    /// Returns a tuple containing the written documents and the
+    /// per-document failures reported by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().m_create_or_replace(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     vec![
+    ///         ("crab".to_string(), json!({ "legs": 10 })),
+    ///         ("lobster".to_string(), json!({ "legs": 10 })),
+    ///     ],
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_create_or_replace(
+        &self,
+        index: &str,
+        collection: &str,
+        documents: Vec<(String, Value)>,
+        options: QueryOptions,
+    ) -> Result<(Vec<Document>, Vec<KuzzleError>), Box<dyn Error>> {
+        self.m_write(
+            "DocumentController::m_create_or_replace",
+            "mCreateOrReplace",
+            index,
+            collection,
+            documents,
+            options,
+        )
+    }
+
+    /// Deletes multiple documents at once.
+    /// Returns a tuple containing the ids of the deleted documents
+    /// and the list of per-document failures reported by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions, RefreshMode};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().m_delete(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     vec!["crab".to_string(), "lobster".to_string()],
+    ///     QueryOptions::new().set_refresh(RefreshMode::WaitFor),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_delete(
+        &self,
+        index: &str,
+        collection: &str,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<(Vec<String>, Vec<Value>), Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::m_delete",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if ids.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::m_delete",
+                "ids argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("document", "mDelete")
+            .set_index(index)
+            .set_collection(collection)
+            .add_to_body("ids".to_string(), to_value(ids).unwrap());
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => {
+                let deleted: Vec<String> = from_value(res.result()["successes"].clone())?;
+                let errors: Vec<Value> = from_value(res.result()["errors"].clone())?;
+                Ok((deleted, errors))
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets multiple documents at once.
+    /// Returns a tuple containing the found documents and the ids of
+    /// the missing ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().m_get(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     vec!["crab".to_string(), "lobster".to_string()],
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_get(
+        &self,
+        index: &str,
+        collection: &str,
+        ids: Vec<String>,
+    ) -> Result<(Vec<Document>, Vec<String>), Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::m_get",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if ids.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::m_get",
+                "ids argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("document", "mGet",
+            index: index,
+            collection: collection,
+            body: { "ids" => ids }
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => {
+                let found: Vec<Document> = from_value(res.result()["successes"].clone())?;
+                let missing: Vec<String> = from_value(res.result()["errors"].clone())?;
+                Ok((found, missing))
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Replaces multiple existing documents at once.
+    /// Returns a tuple containing the replaced documents and the
+    /// per-document failures reported by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().m_replace(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     vec![
+    ///         ("crab".to_string(), json!({ "legs": 10 })),
+    ///         ("lobster".to_string(), json!({ "legs": 10 })),
+    ///     ],
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_replace(
+        &self,
+        index: &str,
+        collection: &str,
+        documents: Vec<(String, Value)>,
+        options: QueryOptions,
+    ) -> Result<(Vec<Document>, Vec<KuzzleError>), Box<dyn Error>> {
+        self.m_write(
+            "DocumentController::m_replace",
+            "mReplace",
+            index,
+            collection,
+            documents,
+            options,
+        )
+    }
+
+    /// Updates multiple documents at once, merging each body into the
+    /// document content.
+    /// Returns a tuple containing the updated documents and the
+    /// per-document failures reported by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().m_update(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     vec![
+    ///         ("crab".to_string(), json!({ "legs": 10 })),
+    ///         ("lobster".to_string(), json!({ "legs": 10 })),
+    ///     ],
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_update(
+        &self,
+        index: &str,
+        collection: &str,
+        documents: Vec<(String, Value)>,
+        options: QueryOptions,
+    ) -> Result<(Vec<Document>, Vec<KuzzleError>), Box<dyn Error>> {
+        self.m_write(
+            "DocumentController::m_update",
+            "mUpdate",
+            index,
+            collection,
+            documents,
+            options,
+        )
+    }
+
+    /// Searches documents across several `(index, collection)` targets,
+    /// e.g. for a federated search, and merges the hits by descending score.
+    /// Each hit is tagged with the index and collection it comes from (see
+    /// `Hit::index` and `Hit::collection`), `total` sums the matches of
+    /// every target.
+    ///
+    /// The searches are sent at once with `Kuzzle::batch`, so that servers
+    /// without multi-target search are supported, the query being given to
+    /// each of them: its `size` applies per target.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().multi_search(
+    ///     vec![
+    ///         ("ferris_index".to_string(), "crabs".to_string()),
+    ///         ("ferris_index".to_string(), "lobsters".to_string()),
+    ///     ],
+    ///     json!({ "query": { "match": { "color": "red" } } }),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn multi_search(
+        &self,
+        targets: Vec<(String, String)>,
+        query: Value,
+    ) -> Result<SearchResult, Box<dyn Error>> {
+        if targets.is_empty()
+            || targets
+                .iter()
+                .any(|(index, collection)| index.is_empty() || collection.is_empty())
+        {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::multi_search",
+                "targets argument must hold non-empty indexes and collections.",
+            )));
+        }
+
+        if !query.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::multi_search",
+                "query argument must be a JSON object.",
+            )));
+        }
+
+        let requests = targets
+            .iter()
+            .map(|(index, collection)| {
+                let mut req: KuzzleRequest = KuzzleRequest::new("document", "search")
+                    .set_index(index)
+                    .set_collection(collection);
+                for (key, value) in query.as_object().unwrap() {
+                    req = req.add_to_body(key.to_string(), value.clone());
+                }
+                req
+            })
+            .collect();
+        let responses = self.kuzzle().batch(requests, QueryOptions::new())?;
+
+        let mut total = 0;
+        let mut hits = Vec::new();
+        for ((index, collection), res) in targets.iter().zip(responses) {
+            if let Some(k_err) = res.error() {
+                return Err(Box::new(k_err.clone()));
+            }
+
+            let result: SearchResult = res.result_as()?;
+            total += result.total();
+            for hit in result.hits() {
+                let mut raw = hit.raw().clone();
+                raw["index"] = Value::from(index.as_str());
+                raw["collection"] = Value::from(collection.as_str());
+                hits.push(raw);
+            }
+        }
+        // Stable sort: hits without score keep their target order, last.
+        hits.sort_by(|a, b| {
+            let (a, b) = (a["_score"].as_f64(), b["_score"].as_f64());
+            b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+        });
+
+        Ok(from_value(json!({ "total": total, "hits": hits }))?)
+    }
+
+    /// Replaces the content of an existing document.
+    /// Unlike an update, the whole document is overwritten with the provided
+    /// body instead of being merged with it. Fails if the document does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().replace(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     "ferris",
+    ///     json!({ "name": "Ferris", "legs": 10 }),
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn replace(
+        &self,
+        index: &str,
+        collection: &str,
+        id: &str,
+        document: Value,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty() || id.is_empty())
+            && !options.skip_validation()
+        {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::replace",
+                "index, collection and id arguments must not be empty.",
+            )));
+        }
+
+        if !document.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::replace",
+                "document argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = KuzzleRequest::new("document", "replace")
+            .set_index(index)
+            .set_collection(collection)
+            .set_id(id);
+        for (key, value) in document.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result().clone()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns an iterator over the documents of a scrolled search, given
+    /// the `scroll_id` of its first page (see `QueryOptions::set_scroll`).
+    /// The next pages are fetched with `document/scroll` as the iteration
+    /// goes, each one keeping the cursor alive for `ttl` (e.g. `30s`),
+    /// until an empty page is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// if let Ok(page) = kuzzle.document().search(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({}),
+    ///     QueryOptions::new().set_size(100).set_scroll("30s"),
+    /// ) {
+    ///     if let Some(scroll_id) = page.scroll_id() {
+    ///         for document in kuzzle.document().scroll(scroll_id, "30s").unwrap() {
+    ///             println!("{:?}", document);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// ```
+    ///
+    pub fn scroll(&self, scroll_id: &str, ttl: &str) -> Result<ScrollIterator<'a>, Box<dyn Error>> {
+        if scroll_id.is_empty() || ttl.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::scroll",
+                "scroll_id and ttl arguments must not be empty.",
+            )));
+        }
+
+        Ok(ScrollIterator {
+            kuzzle: self.kuzzle(),
+            scroll_id: scroll_id.to_string(),
+            ttl: ttl.to_string(),
+            buffer: Vec::new().into_iter(),
+            done: false,
+        })
+    }
+
+    /// Searches documents, using Elasticsearch Query DSL.
+    /// `query` is the search body, which can hold `query`, `sort` and
+    /// `aggregations` entries. Pagination (`from`, `size`, `scroll`
+    /// and `search_after`) is set through `QueryOptions`: `from` and `size`
+    /// must not reach past the server `documentsFetchCount` limit (see
+    /// `Kuzzle::load_server_limits`), as deeper pages need a scroll.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().search(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({
+    ///         "query": { "match": { "species": "crab" } },
+    ///         "sort": [{ "age": "asc" }, { "_id": "asc" }]
+    ///     }),
+    ///     QueryOptions::new().set_size(10),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn search(
+        &self,
+        index: &str,
+        collection: &str,
+        query: Value,
+        options: QueryOptions,
+    ) -> Result<SearchResult, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::search",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if !query.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::search",
+                "query argument must be a JSON object.",
+            )));
+        }
+
+        let fetch_count = options.from().unwrap_or(0) + options.size().unwrap_or(0);
+        let max_fetch_count = self.kuzzle().server_limits().documents_fetch_count();
+        if fetch_count > max_fetch_count && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::search",
+                &format!(
+                    "from + size ({}) exceeds the server documentsFetchCount limit ({}), use a scroll instead.",
+                    fetch_count, max_fetch_count
+                ),
+            )));
+        }
+
+        let mut req: KuzzleRequest = KuzzleRequest::new("document", "search")
+            .set_index(index)
+            .set_collection(collection);
+        for (key, value) in query.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+        if let Some(search_after) = options.search_after() {
+            req = req.add_to_body("search_after".to_string(), to_value(search_after).unwrap());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result_as()?),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Searches for a single document, for uniqueness lookups.
+    /// Only the first hit is fetched: it is returned, or `None` if nothing
+    /// matched. If more than one document matches the query, an `SdkError`
+    /// reporting the number of matches is returned instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().search_one(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "query": { "term": { "email": "ferris@rust-lang.org" } } }),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn search_one(
+        &self,
+        index: &str,
+        collection: &str,
+        query: Value,
+    ) -> Result<Option<Value>, Box<dyn Error>> {
+        let result = self.search(index, collection, query, QueryOptions::new().set_size(1))?;
+
+        if result.total() > 1 {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::search_one",
+                &format!("expected at most one document, {} matched.", result.total()),
+            )));
+        }
+
+        Ok(result.hits().first().map(|hit| hit.raw().clone()))
+    }
+
+    /// Updates a document, merging `partial_body` into its content.
+    /// When set, `retry_on_conflict` is the number of times Kuzzle retries
+    /// the update on a version conflict before giving up.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().update(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     "ferris",
+    ///     json!({ "legs": 12 }),
+    ///     Some(3),
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update(
+        &self,
+        index: &str,
+        collection: &str,
+        id: &str,
+        partial_body: Value,
+        retry_on_conflict: Option<u32>,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty() || id.is_empty())
+            && !options.skip_validation()
+        {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::update",
+                "index, collection and id arguments must not be empty.",
+            )));
+        }
+
+        if !partial_body.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::update",
+                "partial_body argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = KuzzleRequest::new("document", "update")
+            .set_index(index)
+            .set_collection(collection)
+            .set_id(id);
+        for (key, value) in partial_body.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+        if let Some(retries) = retry_on_conflict {
+            req = req.add_to_query_strings("retryOnConflict".to_string(), Value::from(retries));
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result().clone()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Applies `changes` to the documents matching the given search query,
+    /// e.g. `{ "query": { "term": { "species": "crab" } } }`, merging them
+    /// into the documents content.
+    /// Returns the number of updated documents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().update_by_query(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "query": { "term": { "species": "crab" } } }),
+    ///     json!({ "legs": 10 }),
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_by_query(
+        &self,
+        index: &str,
+        collection: &str,
+        query: Value,
+        changes: Value,
+        options: QueryOptions,
+    ) -> Result<u64, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::update_by_query",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if !query.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::update_by_query",
+                "query argument must be a JSON object.",
+            )));
+        }
+
+        if !changes.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::update_by_query",
+                "changes argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("document", "updateByQuery",
+            index: index,
+            collection: collection,
+            body: { "changes" => changes }
+        );
+        for (key, value) in query.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => match res.result()["successes"].as_array() {
+                Some(successes) => Ok(successes.len() as u64),
+                None => Err(Box::new(SdkError::new(
+                    "DocumentController::update_by_query",
+                    "response is missing the successes field.",
+                ))),
+            },
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Checks a document against the collection specifications, without
+    /// storing it. A document violating them is reported as `Ok(false)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().validate(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "name": "Ferris", "legs": 10 }),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn validate(
+        &self,
+        index: &str,
+        collection: &str,
+        body: Value,
+    ) -> Result<bool, Box<dyn Error>> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::validate",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if !body.is_object() {
+            return Err(Box::new(SdkError::new(
+                "DocumentController::validate",
+                "body argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("document", "validate",
+            index: index,
+            collection: collection
+        );
+        for (key, value) in body.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => match res.result()["valid"].as_bool() {
+                Some(valid) => Ok(valid),
+                None => Err(Box::new(SdkError::new(
+                    "DocumentController::validate",
+                    "response is missing the valid field.",
+                ))),
+            },
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sends a `document/m*` write action taking `{ _id, body }` documents,
+    /// and splits its result into successes and failures.
+    fn m_write(
+        &self,
+        cause: &str,
+        action: &str,
+        index: &str,
+        collection: &str,
+        documents: Vec<(String, Value)>,
+        options: QueryOptions,
+    ) -> Result<(Vec<Document>, Vec<KuzzleError>), Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if documents.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "documents argument must not be empty.",
+            )));
+        }
+
+        let documents: Vec<Value> = documents
+            .into_iter()
+            .map(|(id, body)| json!({ "_id": id, "body": body }))
+            .collect();
+        let req: KuzzleRequest = kuzzle_request!("document", action,
+            index: index,
+            collection: collection,
+            body: { "documents" => documents }
+        );
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => {
+                let successes: Vec<Document> = from_value(res.result()["successes"].clone())?;
+                let errors = res.result()["errors"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|error| {
+                        let status = error["status"].as_u64().map(|status| status as u16);
+                        KuzzleError::new(status, error["reason"].as_str().unwrap_or_default())
+                    })
+                    .collect();
+                Ok((successes, errors))
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
     }
 
     fn kuzzle(&self) -> &'a Kuzzle {
-        &self.0
+        self.0
+    }
+}
+
+/// Number of documents fetched per page by `DocumentController::export_ndjson`.
+const EXPORT_PAGE_SIZE: u64 = 1000;
+
+/// Time to live of the scroll cursor of `DocumentController::export_ndjson`.
+const EXPORT_SCROLL: &str = "1m";
+
+/// Writes a document as a `{ "_id": ..., "_source": ... }` NDJSON line.
+fn write_ndjson_line<W: Write>(writer: &mut W, document: &Document) -> Result<(), Box<dyn Error>> {
+    to_writer(
+        &mut *writer,
+        &json!({ "_id": document.id(), "_source": document.source() }),
+    )?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// An iterator over the documents of a scrolled search, returned by
+/// `DocumentController::scroll`.
+pub struct ScrollIterator<'a> {
+    kuzzle: &'a Kuzzle,
+    scroll_id: String,
+    ttl: String,
+    buffer: IntoIter<Document>,
+    done: bool,
+}
+
+impl<'a> ScrollIterator<'a> {
+    /// The scroll id to pass to fetch the next page, as last returned by Kuzzle.
+    pub fn scroll_id(&self) -> &String {
+        &self.scroll_id
+    }
+
+    /// Fetches the next page of documents into the buffer.
+    fn fetch(&mut self) -> Result<(), Box<dyn Error>> {
+        let req: KuzzleRequest = kuzzle_request!("document", "scroll",
+            query: { "scrollId" => self.scroll_id }
+        );
+        let res = self
+            .kuzzle
+            .query(req, QueryOptions::new().set_scroll(&self.ttl))?;
+        match &res.error() {
+            None => {
+                let page: SearchResult = res.result_as()?;
+                if let Some(scroll_id) = page.scroll_id() {
+                    self.scroll_id = scroll_id.clone();
+                }
+                let documents: Vec<Document> = page.hits().iter().map(Document::from_hit).collect();
+                self.buffer = documents.into_iter();
+                Ok(())
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+}
+
+impl<'a> Iterator for ScrollIterator<'a> {
+    type Item = Result<Document, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(document) = self.buffer.next() {
+            return Some(Ok(document));
+        }
+
+        if self.done {
+            return None;
+        }
+
+        if let Err(err) = self.fetch() {
+            self.done = true;
+            return Some(Err(err));
+        }
+
+        match self.buffer.next() {
+            Some(document) => Some(Ok(document)),
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::{KuzzleError, KuzzleOptions, RefreshMode};
+    use mockito::{self, Matcher};
+    use serde_json::json;
+
+    #[test]
+    fn count_ok_with_query() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_count")
+            .match_body(Matcher::Json(json!({
+                "query": { "match": { "species": "crab" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "count",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "count": 42 }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().count(
+            "ferris_index",
+            "ferris_collection",
+            Some(json!({ "query": { "match": { "species": "crab" } } })),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap(), 42);
+    }
+
+    #[test]
+    fn count_ok_koncorde_lang() {
+        let _m = mockito::mock(
+            "POST",
+            "/ferris_index/ferris_collection/_count?lang=koncorde",
+        )
+        .match_body(Matcher::Json(json!({
+            "query": { "equals": { "species": "crab" } }
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "count",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "count": 7 }
+                }"#,
+        )
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().count(
+            "ferris_index",
+            "ferris_collection",
+            Some(json!({ "query": { "equals": { "species": "crab" } } })),
+            QueryOptions::new().set_lang("koncorde"),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap(), 7);
+    }
+
+    #[test]
+    fn count_ok_empty_collection() {
+        let _m = mockito::mock("POST", "/ferris_index/empty_collection/_count")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "count",
+                    "collection": "empty_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "count": 0 }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().count(
+            "ferris_index",
+            "empty_collection",
+            None,
+            QueryOptions::new(),
+        );
+
+        assert_eq!(res.unwrap(), 0);
+    }
+
+    #[test]
+    fn count_fail_missing_count() {
+        let _m = mockito::mock("POST", "/ferris_index/odd_collection/_count")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "count",
+                    "collection": "odd_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {}
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .document()
+            .count("ferris_index", "odd_collection", None, QueryOptions::new());
+
+        let err = res.unwrap_err();
+        let sdk_err = err.downcast_ref::<SdkError>().unwrap();
+        assert_eq!(sdk_err.message(), "response is missing the count field.");
+    }
+
+    #[test]
+    fn count_fail_empty_index() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .document()
+            .count("", "ferris_collection", None, QueryOptions::new());
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn count_skip_validation_empty_index() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().count(
+            "",
+            "ferris_collection",
+            None,
+            QueryOptions::new().set_skip_validation(true),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_none());
+    }
+
+    #[test]
+    fn create_ok_with_id() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_create?_id=ferris")
+            .match_body(Matcher::Json(json!({ "name": "Ferris", "legs": 10 })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "create",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_version": 1,
+                        "_source": { "name": "Ferris", "legs": 10 }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().create(
+            "ferris_index",
+            "ferris_collection",
+            Some("ferris"),
+            json!({ "name": "Ferris", "legs": 10 }),
+            QueryOptions::new(),
+        );
+
+        assert_eq!(res.unwrap().result()["_id"], "ferris");
+        _m.assert();
+    }
+
+    #[test]
+    fn create_ok_without_id() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_create")
+            .match_body(Matcher::Json(json!({ "name": "Ferris" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "create",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "_id": "AWxHzUJ4wXgLgoMjxZ3S",
+                        "_version": 1,
+                        "_source": { "name": "Ferris" }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().create(
+            "ferris_index",
+            "ferris_collection",
+            None,
+            json!({ "name": "Ferris" }),
+            QueryOptions::new(),
+        );
+
+        assert_eq!(res.unwrap().result()["_id"], "AWxHzUJ4wXgLgoMjxZ3S");
+        _m.assert();
+    }
+
+    #[test]
+    fn create_fail_already_exists() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_create?_id=ferris")
+            .with_status(409)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 409,
+                    "error": {
+                        "message": "Document already exists",
+                        "status": 409
+                    },
+                    "controller": "document",
+                    "action": "create",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().create(
+            "ferris_index",
+            "ferris_collection",
+            Some("ferris"),
+            json!({ "name": "Ferris" }),
+            QueryOptions::new(),
+        );
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(409));
+    }
+
+    #[test]
+    fn create_fail_empty_collection() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().create(
+            "ferris_index",
+            "",
+            None,
+            json!({ "name": "Ferris" }),
+            QueryOptions::new(),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn create_or_replace_ok_creates_missing() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/nemo")
+            .match_body(Matcher::Json(json!({ "name": "Nemo" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "createOrReplace",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "_id": "nemo",
+                        "_version": 1,
+                        "created": true,
+                        "_source": { "name": "Nemo" }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().create_or_replace(
+            "ferris_index",
+            "ferris_collection",
+            "nemo",
+            json!({ "name": "Nemo" }),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        let document = res.unwrap();
+        assert_eq!(document["_id"], "nemo");
+        assert_eq!(document["_version"], 1);
+    }
+
+    #[test]
+    fn create_or_replace_ok_replaces_existing() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/ferris")
+            .match_body(Matcher::Json(json!({ "name": "Ferris", "legs": 10 })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "createOrReplace",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_version": 2,
+                        "created": false,
+                        "_source": { "name": "Ferris", "legs": 10 }
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().create_or_replace(
+            "ferris_index",
+            "ferris_collection",
+            "ferris",
+            json!({ "name": "Ferris", "legs": 10 }),
+            QueryOptions::new(),
+        );
+
+        let document = res.unwrap();
+        assert_eq!(document["_id"], "ferris");
+        assert_eq!(document["_version"], 2);
+    }
+
+    #[test]
+    fn create_or_replace_fail_empty_id() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().create_or_replace(
+            "ferris_index",
+            "ferris_collection",
+            "",
+            json!({ "name": "Ferris" }),
+            QueryOptions::new(),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn delete_ok() {
+        let _m = mockito::mock("DELETE", "/ferris_index/ferris_collection/ferris")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "delete",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "_id": "ferris" }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().delete(
+            "ferris_index",
+            "ferris_collection",
+            "ferris",
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap(), "ferris");
+    }
+
+    #[test]
+    fn delete_ok_without_notifications() {
+        let _m = mockito::mock(
+            "DELETE",
+            "/ferris_index/ferris_collection/ferris?notify=false",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "delete",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "_id": "ferris" }
+                }"#,
+        )
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().delete(
+            "ferris_index",
+            "ferris_collection",
+            "ferris",
+            QueryOptions::new().set_notify(false),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap(), "ferris");
+    }
+
+    #[test]
+    fn delete_fail_not_found() {
+        let _m = mockito::mock("DELETE", "/ferris_index/ferris_collection/nemo")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "Document nemo not found in \"ferris_index\":\"ferris_collection\".",
+                        "status": 404
+                    },
+                    "controller": "document",
+                    "action": "delete",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().delete(
+            "ferris_index",
+            "ferris_collection",
+            "nemo",
+            QueryOptions::new(),
+        );
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(404));
+    }
+
+    #[test]
+    fn delete_fail_empty_id() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .document()
+            .delete("ferris_index", "ferris_collection", "", QueryOptions::new());
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn delete_by_query_ok_many_matches() {
+        let _m = mockito::mock("DELETE", "/ferris_index/ferris_collection/_query")
+            .match_body(Matcher::Json(json!({
+                "query": { "term": { "species": "crab" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "deleteByQuery",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "ids": ["ferris", "crabby", "pinchy"] }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().delete_by_query(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "query": { "term": { "species": "crab" } } }),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap(), vec!["ferris", "crabby", "pinchy"]);
+    }
+
+    #[test]
+    fn delete_by_query_ok_no_match() {
+        let _m = mockito::mock("DELETE", "/ferris_index/empty_collection/_query")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "deleteByQuery",
+                    "collection": "empty_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "ids": [] }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().delete_by_query(
+            "ferris_index",
+            "empty_collection",
+            json!({ "query": { "term": { "species": "unicorn" } } }),
+            QueryOptions::new(),
+        );
+
+        assert!(res.unwrap().is_empty());
+    }
+
+    #[test]
+    fn delete_by_query_fail_query_not_an_object() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().delete_by_query(
+            "ferris_index",
+            "ferris_collection",
+            json!("crab"),
+            QueryOptions::new(),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn exists_ok_true() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/ferris/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "exists",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": true
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .document()
+            .exists("ferris_index", "ferris_collection", "ferris");
+
+        assert!(res.unwrap());
+    }
+
+    #[test]
+    fn exists_ok_false() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/nemo/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "exists",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": false
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .document()
+            .exists("ferris_index", "ferris_collection", "nemo");
+
+        assert!(!res.unwrap());
+    }
+
+    #[test]
+    fn export_ndjson_ok_two_pages() {
+        let response = |action: &str, scroll_id: &str, hits: &str| {
+            format!(
+                r#"{{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "{}",
+                    "collection": "backup_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {{ "total": 3, "scrollId": "{}", "hits": [{}] }}
+                }}"#,
+                action, scroll_id, hits
+            )
+        };
+        let _search = mockito::mock("GET", "/ferris_index/backup_collection?scroll=1m&size=1000")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response(
+                "search",
+                "backup-scroll-1",
+                r#"{ "_id": "ferris", "_source": { "name": "Ferris" } },
+               { "_id": "crab", "_source": { "name": "Crab" } }"#,
+            ))
+            .expect(1)
+            .create();
+        let _scroll = mockito::mock("GET", "/_scroll/backup-scroll-1?scroll=1m")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response(
+                "scroll",
+                "backup-scroll-2",
+                r#"{ "_id": "lobster", "_source": { "name": "Lobster" } }"#,
+            ))
+            .expect(1)
+            .create();
+        let _last = mockito::mock("GET", "/_scroll/backup-scroll-2?scroll=1m")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response("scroll", "backup-scroll-2", ""))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut buffer: Vec<u8> = Vec::new();
+        let res =
+            k.document()
+                .export_ndjson("ferris_index", "backup_collection", json!({}), &mut buffer);
+
+        _search.assert();
+        _scroll.assert();
+        _last.assert();
+        assert_eq!(res.unwrap(), 3);
+        let ndjson = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = ndjson.lines().collect();
+        assert_eq!(lines.len(), 3);
+        let last: Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(
+            last,
+            json!({ "_id": "lobster", "_source": { "name": "Lobster" } })
+        );
+    }
+
+    #[test]
+    fn get_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/ferris")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "get",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_index": "ferris_index",
+                        "_type": "ferris_collection",
+                        "_version": 3,
+                        "found": true,
+                        "_source": { "name": "Ferris", "legs": 10 }
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let document = k
+            .document()
+            .get("ferris_index", "ferris_collection", "ferris")
+            .unwrap();
+
+        assert_eq!(document.id(), "ferris");
+        assert_eq!(document.version(), 3);
+        assert_eq!(document.source()["legs"], 10);
+    }
+
+    const GET_RESPONSE: &str = r#"{
+        "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+        "status": 200,
+        "error": null,
+        "controller": "document",
+        "action": "get",
+        "collection": "ferris_collection",
+        "index": "ferris_index",
+        "volatile": null,
+        "result": {
+            "_id": "cached_ferris",
+            "_version": 3,
+            "_source": { "name": "Ferris", "legs": 10 }
+        }
+    }"#;
+
+    #[test]
+    fn get_ok_cache_hit() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/cached_ferris")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(GET_RESPONSE)
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_document_cache(true);
+        let first = k
+            .document()
+            .get("ferris_index", "ferris_collection", "cached_ferris")
+            .unwrap();
+        let second = k
+            .document()
+            .get("ferris_index", "ferris_collection", "cached_ferris")
+            .unwrap();
+
+        assert_eq!(first, second);
+        _m.assert();
+    }
+
+    #[test]
+    fn update_ok_invalidates_cached_document() {
+        let _get = mockito::mock("GET", "/ferris_index/ferris_collection/cached_ferris")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(GET_RESPONSE)
+            .expect(2)
+            .create();
+        let _update = mockito::mock(
+            "PUT",
+            "/ferris_index/ferris_collection/cached_ferris/_update",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "update",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": { "_id": "cached_ferris", "_version": 4 }
+            }"#,
+        )
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_document_cache(true);
+        k.document()
+            .get("ferris_index", "ferris_collection", "cached_ferris")
+            .unwrap();
+        k.document()
+            .update(
+                "ferris_index",
+                "ferris_collection",
+                "cached_ferris",
+                json!({ "legs": 12 }),
+                None,
+                QueryOptions::new(),
+            )
+            .unwrap();
+        k.document()
+            .get("ferris_index", "ferris_collection", "cached_ferris")
+            .unwrap();
+
+        _get.assert();
+        _update.assert();
+    }
+
+    #[test]
+    fn get_fail_not_found() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/nemo")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "Document nemo not found in \"ferris_index\":\"ferris_collection\".",
+                        "status": 404
+                    },
+                    "controller": "document",
+                    "action": "get",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .document()
+            .get("ferris_index", "ferris_collection", "nemo");
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(404));
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Crab {
+        name: String,
+        legs: u8,
+    }
+
+    #[test]
+    fn get_as_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/typed_ferris")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(GET_RESPONSE.replace("cached_ferris", "typed_ferris"))
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let crab: Crab = k
+            .document()
+            .get_as("ferris_index", "ferris_collection", "typed_ferris")
+            .unwrap();
+
+        assert_eq!(
+            crab,
+            Crab {
+                name: "Ferris".to_string(),
+                legs: 10
+            }
+        );
+    }
+
+    #[test]
+    fn get_as_fail_source_mismatch() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/lobster")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                GET_RESPONSE
+                    .replace("cached_ferris", "lobster")
+                    .replace(r#""legs": 10"#, r#""legs": "ten""#),
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .document()
+            .get_as::<Crab>("ferris_index", "ferris_collection", "lobster")
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<KuzzleError>().is_none());
+        let sdk_err = err.downcast_ref::<SdkError>().unwrap();
+        assert!(sdk_err
+            .message()
+            .starts_with("cannot deserialize the source of `lobster` at `legs`: invalid type"));
+    }
+
+    #[test]
+    fn get_as_fail_not_found() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/nemo_as")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "Document nemo_as not found in \"ferris_index\":\"ferris_collection\".",
+                        "status": 404
+                    },
+                    "controller": "document",
+                    "action": "get",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .document()
+            .get_as::<Crab>("ferris_index", "ferris_collection", "nemo_as")
+            .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<KuzzleError>().unwrap().status(),
+            Some(404)
+        );
+    }
+
+    #[test]
+    fn m_create_ok() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_mCreate")
+            .match_body(Matcher::Json(json!({
+                "documents": [
+                    { "_id": "crab", "body": { "legs": 10 } },
+                    { "body": { "legs": 8 } }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mCreate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "crab", "_version": 1, "_source": { "legs": 10 }, "created": true },
+                            { "_id": "AWjvYh1P", "_version": 1, "_source": { "legs": 8 }, "created": true }
+                        ],
+                        "errors": []
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_create(
+            "ferris_index",
+            "ferris_collection",
+            vec![
+                (Some("crab".to_string()), json!({ "legs": 10 })),
+                (None, json!({ "legs": 8 })),
+            ],
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        let (created, errors) = res.unwrap();
+        assert_eq!(created.len(), 2);
+        assert_eq!(created[0].id(), "crab");
+        assert_eq!(created[1].source()["legs"], 8);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn m_create_ok_partial_failures() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_mCreate")
+            .with_status(206)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 206,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mCreate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "crab", "_version": 1, "_source": { "legs": 10 }, "created": true }
+                        ],
+                        "errors": [
+                            {
+                                "document": { "_id": "lobster", "body": { "legs": 10 } },
+                                "reason": "document already exists",
+                                "status": 409
+                            }
+                        ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_create(
+            "ferris_index",
+            "ferris_collection",
+            vec![
+                (Some("crab".to_string()), json!({ "legs": 10 })),
+                (Some("lobster".to_string()), json!({ "legs": 10 })),
+            ],
+            QueryOptions::new(),
+        );
+
+        let (created, errors) = res.unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].id(), "crab");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["document"]["_id"], "lobster");
+        assert_eq!(errors[0]["status"], 409);
+    }
+
+    #[test]
+    fn m_create_fail_empty_documents() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_create(
+            "ferris_index",
+            "ferris_collection",
+            vec![],
+            QueryOptions::new(),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn m_create_or_replace_ok() {
+        let _m = mockito::mock(
+            "PUT",
+            "/ferris_index/ferris_collection/_mCreateOrReplace",
+        )
+        .match_body(Matcher::Json(json!({
+            "documents": [
+                { "_id": "crab", "body": { "legs": 10 } },
+                { "_id": "lobster", "body": { "legs": 10 } }
+            ]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "mCreateOrReplace",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {
+                    "successes": [
+                        { "_id": "crab", "_version": 3, "_source": { "legs": 10 }, "created": false },
+                        { "_id": "lobster", "_version": 1, "_source": { "legs": 10 }, "created": true }
+                    ],
+                    "errors": []
+                }
+            }"#,
+        )
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_create_or_replace(
+            "ferris_index",
+            "ferris_collection",
+            vec![
+                ("crab".to_string(), json!({ "legs": 10 })),
+                ("lobster".to_string(), json!({ "legs": 10 })),
+            ],
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        let (written, errors) = res.unwrap();
+        assert_eq!(written[0].version(), 3);
+        assert_eq!(written[1].id(), "lobster");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn m_delete_ok() {
+        let _m = mockito::mock(
+            "DELETE",
+            "/ferris_index/ferris_collection/_mDelete?refresh=wait_for",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mDelete",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": ["crab", "lobster"],
+                        "errors": []
+                    }
+                }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_delete(
+            "ferris_index",
+            "ferris_collection",
+            vec!["crab".to_string(), "lobster".to_string()],
+            QueryOptions::new().set_refresh(RefreshMode::WaitFor),
+        );
+
+        assert!(res.is_ok());
+        let (deleted, errors) = res.unwrap();
+        assert_eq!(deleted, vec!["crab".to_string(), "lobster".to_string()]);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn m_delete_ok_partial_failures() {
+        let _m = mockito::mock("DELETE", "/ferris_index/ferris_collection/_mDelete")
+            .with_status(206)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 206,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mDelete",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": ["crab"],
+                        "errors": [
+                            { "id": "lobster", "reason": "Document not found" },
+                            { "id": "shrimp", "reason": "Forbidden" }
+                        ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_delete(
+            "ferris_index",
+            "ferris_collection",
+            vec![
+                "crab".to_string(),
+                "lobster".to_string(),
+                "shrimp".to_string(),
+            ],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        let (deleted, errors) = res.unwrap();
+        assert_eq!(deleted, vec!["crab".to_string()]);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].get("id").unwrap(), "lobster");
+        assert_eq!(errors[1].get("reason").unwrap(), "Forbidden");
+    }
+
+    #[test]
+    fn m_delete_fail_error() {
+        let _m = mockito::mock("DELETE", "/ferris_index/ferris_collection/_mDelete")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "Index \"ferris_index\" does not exist",
+                        "status": 404,
+                        "stack": "NotFoundError: Index \"ferris_index\" does not exist\n"
+                    },
+                    "controller": "document",
+                    "action": "mDelete",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_delete(
+            "ferris_index",
+            "ferris_collection",
+            vec!["crab".to_string()],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn m_delete_fail_malformed_result() {
+        let _m = mockito::mock("DELETE", "/ferris_index/ferris_collection/_mDelete")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mDelete",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [42]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_delete(
+            "ferris_index",
+            "ferris_collection",
+            vec!["crab".to_string()],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn m_delete_fail_empty_ids_array() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_delete(
+            "ferris_index",
+            "ferris_collection",
+            vec![],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn search_ok_many_hits() {
+        let _m = mockito::mock(
+            "GET",
+            "/ferris_index/ferris_collection?from=0&scroll=30s&size=2",
+        )
+        .match_body(Matcher::Json(json!({
+            "query": { "match": { "species": "crab" } }
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "search",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {
+                    "total": 5,
+                    "scrollId": "ferris-scroll",
+                    "hits": [
+                        { "_id": "ferris", "_score": 1.3, "_source": { "species": "crab" } },
+                        { "_id": "crabby", "_score": 0.9, "_source": { "species": "crab" } }
+                    ]
+                }
+            }"#,
+        )
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let result = k
+            .document()
+            .search(
+                "ferris_index",
+                "ferris_collection",
+                json!({ "query": { "match": { "species": "crab" } } }),
+                QueryOptions::new()
+                    .set_from(0)
+                    .set_size(2)
+                    .set_scroll("30s"),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(result.total(), 5);
+        let ids: Vec<&String> = result.hits().iter().map(|hit| hit.id()).collect();
+        assert_eq!(ids, vec!["ferris", "crabby"]);
+        assert_eq!(result.scroll_id(), &Some("ferris-scroll".to_string()));
+        assert_eq!(result.aggregations(), &None);
+    }
+
+    #[test]
+    fn search_ok_no_hit() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "search",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "total": 0,
+                        "hits": []
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let result = k
+            .document()
+            .search(
+                "ferris_index",
+                "ferris_collection",
+                json!({ "query": { "match": { "species": "unicorn" } } }),
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        assert_eq!(result.total(), 0);
+        assert!(result.hits().is_empty());
+        assert_eq!(result.scroll_id(), &None);
+    }
+
+    #[test]
+    fn search_ok_koncorde_lang() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection?lang=koncorde")
+            .match_body(Matcher::Json(json!({
+                "query": { "range": { "legs": { "gte": 8 } } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "search",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "total": 1,
+                        "hits": [{ "_id": "ferris", "_source": { "name": "Ferris", "legs": 10 } }]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let result = k
+            .document()
+            .search(
+                "ferris_index",
+                "ferris_collection",
+                json!({ "query": { "range": { "legs": { "gte": 8 } } } }),
+                QueryOptions::new().set_lang("koncorde"),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(result.hits()[0].id(), "ferris");
+    }
+
+    #[test]
+    fn search_ok_aggregations() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection?size=0")
+            .match_body(Matcher::Json(json!({
+                "aggregations": { "by_species": { "terms": { "field": "species" } } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "search",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "total": 12,
+                        "hits": [],
+                        "aggregations": {
+                            "by_species": {
+                                "buckets": [
+                                    { "key": "crab", "doc_count": 9 },
+                                    { "key": "lobster", "doc_count": 3 }
+                                ]
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let result = k
+            .document()
+            .search(
+                "ferris_index",
+                "ferris_collection",
+                json!({ "aggregations": { "by_species": { "terms": { "field": "species" } } } }),
+                QueryOptions::new().set_size(0),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(result.total(), 12);
+        let aggregations = result.aggregations().as_ref().unwrap();
+        assert_eq!(aggregations["by_species"]["buckets"][0]["doc_count"], 9);
+    }
+
+    #[test]
+    fn search_ok_search_after_pages() {
+        let _first = mockito::mock("GET", "/ferris_index/ferris_collection?size=2")
+            .match_body(Matcher::Json(json!({
+                "sort": [{ "age": "asc" }, { "_id": "asc" }]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "search",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "hits": [
+                            { "_id": "crab", "_source": { "age": 2 }, "sort": [2, "crab"] },
+                            { "_id": "lobster", "_source": { "age": 5 }, "sort": [5, "lobster"] }
+                        ]
+                    }
+                }"#,
+            )
+            .create();
+        let _second = mockito::mock("GET", "/ferris_index/ferris_collection?size=2")
+            .match_body(Matcher::Json(json!({
+                "sort": [{ "age": "asc" }, { "_id": "asc" }],
+                "search_after": [5, "lobster"]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "search",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "hits": [
+                            { "_id": "shrimp", "_source": { "age": 7 }, "sort": [7, "shrimp"] }
+                        ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let query = json!({ "sort": [{ "age": "asc" }, { "_id": "asc" }] });
+
+        let first_page = k
+            .document()
+            .search(
+                "ferris_index",
+                "ferris_collection",
+                query.clone(),
+                QueryOptions::new().set_size(2),
+            )
+            .unwrap();
+        assert_eq!(first_page.total(), 3);
+        assert_eq!(first_page.hits().len(), 2);
+        let last_sort = first_page.last_sort().unwrap();
+        assert_eq!(last_sort, vec![json!(5), json!("lobster")]);
+
+        let second_page = k
+            .document()
+            .search(
+                "ferris_index",
+                "ferris_collection",
+                query,
+                QueryOptions::new().set_size(2).set_search_after(last_sort),
+            )
+            .unwrap();
+        assert_eq!(second_page.hits().len(), 1);
+        assert_eq!(second_page.hits()[0].id(), "shrimp");
+        assert_eq!(
+            second_page.last_sort(),
+            Some(vec![json!(7), json!("shrimp")])
+        );
+    }
+
+    #[test]
+    fn search_ok_unsorted_has_no_last_sort() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "search",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "total": 1,
+                        "hits": [
+                            { "_id": "crab", "_source": { "age": 2 } }
+                        ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().search(
+            "ferris_index",
+            "ferris_collection",
+            json!({}),
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap().last_sort(), None);
+    }
+
+    #[test]
+    fn search_fail_query_not_an_object() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().search(
+            "ferris_index",
+            "ferris_collection",
+            json!(["crab"]),
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn search_fail_past_documents_fetch_count() {
+        let _m = mockito::mock(
+            "POST",
+            Matcher::Regex(r"^/ferris_index/ferris_collection/_search".to_string()),
+        )
+        .expect(0)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().search(
+            "ferris_index",
+            "ferris_collection",
+            json!({}),
+            QueryOptions::new().set_from(9_990).set_size(20),
+        );
+
+        _m.assert();
+        assert_eq!(
+            res.unwrap_err().downcast_ref::<SdkError>().unwrap().message(),
+            "from + size (10010) exceeds the server documentsFetchCount limit (10000), use a scroll instead."
+        );
+    }
+
+    fn search_one_response(total: u64, hits: &str) -> String {
+        format!(
+            r#"{{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "search",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {{
+                    "total": {},
+                    "hits": {}
+                }}
+            }}"#,
+            total, hits
+        )
+    }
+
+    #[test]
+    fn search_one_ok_one_hit() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection?size=1")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_one_response(
+                1,
+                r#"[{ "_id": "crab", "_source": { "name": "ferris" } }]"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().search_one(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "query": { "term": { "name": "ferris" } } }),
+        );
+
+        assert_eq!(
+            res.unwrap(),
+            Some(json!({ "_id": "crab", "_source": { "name": "ferris" } }))
+        );
+        _m.assert();
+    }
+
+    #[test]
+    fn search_one_ok_no_hit() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection?size=1")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_one_response(0, "[]"))
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().search_one(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "query": { "term": { "name": "nobody" } } }),
+        );
+
+        assert_eq!(res.unwrap(), None);
+    }
+
+    #[test]
+    fn search_one_fail_many_hits() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection?size=1")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(search_one_response(
+                3,
+                r#"[{ "_id": "crab", "_source": { "species": "crab" } }]"#,
+            ))
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().search_one(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "query": { "term": { "species": "crab" } } }),
+        );
+
+        let err = res.unwrap_err();
+        let sdk_err = err.downcast_ref::<SdkError>().unwrap();
+        assert!(sdk_err.message().contains("3 matched"));
+    }
+
+    #[test]
+    fn m_get_ok_partial_misses() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_mGet")
+            .match_body(Matcher::Json(json!({ "ids": ["crab", "nemo"] })))
+            .with_status(206)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 206,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mGet",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "crab", "_version": 4, "_source": { "legs": 10 } }
+                        ],
+                        "errors": ["nemo"]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_get(
+            "ferris_index",
+            "ferris_collection",
+            vec!["crab".to_string(), "nemo".to_string()],
+        );
+
+        _m.assert();
+        let (found, missing) = res.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id(), "crab");
+        assert_eq!(found[0].version(), 4);
+        assert_eq!(missing, vec!["nemo".to_string()]);
+    }
+
+    #[test]
+    fn m_get_fail_empty_ids() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .document()
+            .m_get("ferris_index", "ferris_collection", vec![]);
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn m_replace_ok_partial_failures() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/_mReplace")
+            .with_status(206)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 206,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mReplace",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "crab", "_version": 2, "_source": { "legs": 10 } },
+                            { "_id": "lobster", "_version": 5, "_source": { "legs": 10 } }
+                        ],
+                        "errors": [
+                            {
+                                "document": { "_id": "shrimp", "body": { "legs": "many" } },
+                                "reason": "Field legs must be an integer",
+                                "status": 400
+                            },
+                            {
+                                "document": { "_id": "prawn", "body": { "legs": "few" } },
+                                "reason": "Field legs must be an integer",
+                                "status": 400
+                            }
+                        ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_replace(
+            "ferris_index",
+            "ferris_collection",
+            vec![
+                ("crab".to_string(), json!({ "legs": 10 })),
+                ("lobster".to_string(), json!({ "legs": 10 })),
+                ("shrimp".to_string(), json!({ "legs": "many" })),
+                ("prawn".to_string(), json!({ "legs": "few" })),
+            ],
+            QueryOptions::new(),
+        );
+
+        let (replaced, errors) = res.unwrap();
+        assert_eq!(replaced.len(), 2);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].status(), Some(400));
+        assert_eq!(errors[1].message(), "Field legs must be an integer");
+    }
+
+    #[test]
+    fn m_update_ok() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/_mUpdate")
+            .match_body(Matcher::Json(json!({
+                "documents": [{ "_id": "crab", "body": { "age": 3 } }]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mUpdate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "crab", "_version": 4, "_source": { "legs": 10, "age": 3 } }
+                        ],
+                        "errors": []
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_update(
+            "ferris_index",
+            "ferris_collection",
+            vec![("crab".to_string(), json!({ "age": 3 }))],
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        let (updated, errors) = res.unwrap();
+        assert_eq!(updated[0].source()["age"], 3);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn m_update_fail_empty_documents() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().m_update(
+            "ferris_index",
+            "ferris_collection",
+            vec![],
+            QueryOptions::new(),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn multi_search_ok_merges_hits() {
+        let _m_crabs = mockito::mock("GET", "/ferris_index/crabs")
+            .match_body(Matcher::Json(json!({
+                "query": { "match": { "color": "red" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "search",
+                    "collection": "crabs",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "hits": [
+                            { "_id": "ferris", "_score": 1.3, "_source": { "color": "red" } },
+                            { "_id": "crabby", "_score": 0.4, "_source": { "color": "red" } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let _m_lobsters = mockito::mock("GET", "/ferris_index/lobsters")
+            .match_body(Matcher::Json(json!({
+                "query": { "match": { "color": "red" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "search",
+                    "collection": "lobsters",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "total": 1,
+                        "hits": [
+                            { "_id": "lobster", "_score": 0.9, "_source": { "color": "red" } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let result = k
+            .document()
+            .multi_search(
+                vec![
+                    ("ferris_index".to_string(), "crabs".to_string()),
+                    ("ferris_index".to_string(), "lobsters".to_string()),
+                ],
+                json!({ "query": { "match": { "color": "red" } } }),
+            )
+            .unwrap();
+
+        _m_crabs.assert();
+        _m_lobsters.assert();
+        assert_eq!(result.total(), 4);
+        let hits: Vec<(&str, Option<&str>, Option<&str>)> = result
+            .hits()
+            .iter()
+            .map(|hit| (hit.id().as_str(), hit.index(), hit.collection()))
+            .collect();
+        assert_eq!(
+            hits,
+            vec![
+                ("ferris", Some("ferris_index"), Some("crabs")),
+                ("lobster", Some("ferris_index"), Some("lobsters")),
+                ("crabby", Some("ferris_index"), Some("crabs")),
+            ]
+        );
+    }
+
+    #[test]
+    fn multi_search_fail_no_target() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k.document().multi_search(vec![], json!({})).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "[DocumentController::multi_search] targets argument must hold non-empty indexes and collections."
+        );
+    }
+
+    #[test]
+    fn replace_ok() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/ferris/_replace")
+            .match_body(Matcher::Json(json!({ "name": "Ferris", "legs": 10 })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "replace",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_version": 2,
+                        "_source": { "name": "Ferris", "legs": 10 }
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().replace(
+            "ferris_index",
+            "ferris_collection",
+            "ferris",
+            json!({ "name": "Ferris", "legs": 10 }),
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        let document = res.unwrap();
+        assert_eq!(document.get("_version").unwrap(), 2);
+        assert_eq!(
+            document.get("_source").unwrap(),
+            &json!({ "name": "Ferris", "legs": 10 })
+        );
+    }
+
+    #[test]
+    fn replace_ok_version_satisfied() {
+        let _m = mockito::mock(
+            "PUT",
+            "/ferris_index/ferris_collection/ferris/_replace?version=1",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "replace",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {
+                    "_id": "ferris",
+                    "_version": 2,
+                    "_source": { "name": "Ferris" }
+                }
+            }"#,
+        )
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().replace(
+            "ferris_index",
+            "ferris_collection",
+            "ferris",
+            json!({ "name": "Ferris" }),
+            QueryOptions::new().set_version(1),
+        );
+
+        assert_eq!(res.unwrap()["_version"], 2);
+        _m.assert();
+    }
+
+    #[test]
+    fn replace_fail_version_precondition() {
+        let _m = mockito::mock(
+            "PUT",
+            "/ferris_index/ferris_collection/ferris/_replace?version=1",
+        )
+        .with_status(409)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 409,
+                "error": {
+                    "message": "version conflict, current version [2] is different than the one provided [1]",
+                    "status": 409
+                },
+                "controller": "document",
+                "action": "replace",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": null
+            }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().replace(
+            "ferris_index",
+            "ferris_collection",
+            "ferris",
+            json!({ "name": "Ferris" }),
+            QueryOptions::new().set_version(1),
+        );
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(412));
+        assert!(format!("{}", k_err).contains("PreconditionError"));
+    }
+
+    #[test]
+    fn replace_fail_not_found() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/nemo/_replace")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "Document with id \"nemo\" not found.",
+                        "status": 404,
+                        "stack": "NotFoundError: Document with id \"nemo\" not found.\n"
+                    },
+                    "controller": "document",
+                    "action": "replace",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().replace(
+            "ferris_index",
+            "ferris_collection",
+            "nemo",
+            json!({ "name": "Nemo" }),
+            QueryOptions::new(),
+        );
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(404));
+    }
+
+    #[test]
+    fn replace_fail_empty_id() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().replace(
+            "ferris_index",
+            "ferris_collection",
+            "",
+            json!({ "name": "Ferris" }),
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn update_ok() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/ferris/_update")
+            .match_body(Matcher::Json(json!({ "legs": 12 })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "update",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_version": 3
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().update(
+            "ferris_index",
+            "ferris_collection",
+            "ferris",
+            json!({ "legs": 12 }),
+            None,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap().get("_version").unwrap(), 3);
+    }
+
+    #[test]
+    fn update_ok_without_notifications() {
+        let _m = mockito::mock(
+            "PUT",
+            "/ferris_index/ferris_collection/ferris/_update?notify=false",
+        )
+        .match_body(Matcher::Json(json!({ "legs": 12 })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "update",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_version": 3
+                    }
+                }"#,
+        )
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().update(
+            "ferris_index",
+            "ferris_collection",
+            "ferris",
+            json!({ "legs": 12 }),
+            None,
+            QueryOptions::new().set_notify(false),
+        );
+
+        _m.assert();
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn update_ok_retry_on_conflict() {
+        let _m = mockito::mock(
+            "PUT",
+            "/ferris_index/ferris_collection/ferris_retried/_update?retryOnConflict=3",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "update",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {
+                    "_id": "ferris_retried",
+                    "_version": 5
+                }
+            }"#,
+        )
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().update(
+            "ferris_index",
+            "ferris_collection",
+            "ferris_retried",
+            json!({ "legs": 12 }),
+            Some(3),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap().get("_version").unwrap(), 5);
+    }
+
+    #[test]
+    fn update_fail_conflict() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/ferris_busy/_update")
+            .with_status(409)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 409,
+                    "error": {
+                        "message": "version conflict, current version [4] is different than the one provided [3]",
+                        "status": 409
+                    },
+                    "controller": "document",
+                    "action": "update",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().update(
+            "ferris_index",
+            "ferris_collection",
+            "ferris_busy",
+            json!({ "legs": 12 }),
+            None,
+            QueryOptions::new(),
+        );
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(409));
+    }
+
+    #[test]
+    fn update_by_query_ok_many_matches() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/_query")
+            .match_body(Matcher::Json(json!({
+                "query": { "term": { "species": "crab" } },
+                "changes": { "legs": 10 }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "updateByQuery",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "ferris", "_version": 2, "_source": { "legs": 10 } },
+                            { "_id": "crabby", "_version": 5, "_source": { "legs": 10 } }
+                        ],
+                        "errors": []
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().update_by_query(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "query": { "term": { "species": "crab" } } }),
+            json!({ "legs": 10 }),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap(), 2);
+    }
+
+    #[test]
+    fn update_by_query_ok_no_match() {
+        let _m = mockito::mock("PUT", "/ferris_index/empty_collection/_query")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "updateByQuery",
+                    "collection": "empty_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "successes": [], "errors": [] }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().update_by_query(
+            "ferris_index",
+            "empty_collection",
+            json!({ "query": { "term": { "species": "unicorn" } } }),
+            json!({ "legs": 10 }),
+            QueryOptions::new(),
+        );
+
+        assert_eq!(res.unwrap(), 0);
+    }
+
+    #[test]
+    fn update_by_query_fail_missing_successes() {
+        let _m = mockito::mock("PUT", "/ferris_index/odd_collection/_query")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "updateByQuery",
+                    "collection": "odd_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {}
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().update_by_query(
+            "ferris_index",
+            "odd_collection",
+            json!({ "query": { "term": { "species": "crab" } } }),
+            json!({ "legs": 10 }),
+            QueryOptions::new(),
+        );
+
+        let err = res.unwrap_err();
+        let sdk_err = err.downcast_ref::<SdkError>().unwrap();
+        assert_eq!(
+            sdk_err.message(),
+            "response is missing the successes field."
+        );
+    }
+
+    #[test]
+    fn update_by_query_fail_query_not_an_object() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().update_by_query(
+            "ferris_index",
+            "ferris_collection",
+            json!(["crab"]),
+            json!({ "legs": 10 }),
+            QueryOptions::new(),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
+    }
+
+    #[test]
+    fn validate_ok_valid() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_validate")
+            .match_body(Matcher::Json(json!({ "name": "Ferris", "legs": 10 })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "validate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "errorMessages": {}, "valid": true }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().validate(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "name": "Ferris", "legs": 10 }),
+        );
+
+        _m.assert();
+        assert!(res.unwrap());
+    }
+
+    #[test]
+    fn validate_ok_invalid() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_validate")
+            .match_body(Matcher::Json(json!({ "name": "Ferris", "legs": "ten" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "validate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "errorMessages": {
+                            "legs": { "messages": ["The field must be an integer."] }
+                        },
+                        "valid": false
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().validate(
+            "ferris_index",
+            "ferris_collection",
+            json!({ "name": "Ferris", "legs": "ten" }),
+        );
+
+        assert!(!res.unwrap());
+    }
+
+    #[test]
+    fn validate_fail_missing_valid() {
+        let _m = mockito::mock("POST", "/ferris_index/odd_collection/_validate")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "validate",
+                    "collection": "odd_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {}
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.document().validate(
+            "ferris_index",
+            "odd_collection",
+            json!({ "name": "Ferris", "legs": 10 }),
+        );
+
+        let err = res.unwrap_err();
+        let sdk_err = err.downcast_ref::<SdkError>().unwrap();
+        assert_eq!(sdk_err.message(), "response is missing the valid field.");
+    }
+
+    #[test]
+    fn validate_fail_collection_not_found() {
+        let _m = mockito::mock("POST", "/ferris_index/nemo_collection/_validate")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "The collection \"nemo_collection\" does not exist.",
+                        "status": 404
+                    },
+                    "controller": "document",
+                    "action": "validate",
+                    "collection": "nemo_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res =
+            k.document()
+                .validate("ferris_index", "nemo_collection", json!({ "name": "Nemo" }));
+
+        let err = res.unwrap_err();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(404));
+    }
+
+    #[test]
+    fn scroll_ok_until_empty_page() {
+        let page = |scroll_id: &str, hits: &str| {
+            format!(
+                r#"{{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "scroll",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {{ "total": 3, "scrollId": "{}", "hits": [{}] }}
+                }}"#,
+                scroll_id, hits
+            )
+        };
+        let _first = mockito::mock("GET", "/_scroll/ferris-scroll-1?scroll=30s")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(page(
+                "ferris-scroll-2",
+                r#"{ "_id": "ferris", "_version": 2, "_source": { "name": "Ferris" } },
+                   { "_id": "crab", "_source": { "name": "Crab" } }"#,
+            ))
+            .expect(1)
+            .create();
+        let _second = mockito::mock("GET", "/_scroll/ferris-scroll-2?scroll=30s")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(page(
+                "ferris-scroll-3",
+                r#"{ "_id": "lobster", "_source": { "name": "Lobster" } }"#,
+            ))
+            .expect(1)
+            .create();
+        let _last = mockito::mock("GET", "/_scroll/ferris-scroll-3?scroll=30s")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(page("ferris-scroll-3", ""))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut scroll = k.document().scroll("ferris-scroll-1", "30s").unwrap();
+        let documents: Vec<Document> = scroll.by_ref().map(|document| document.unwrap()).collect();
+
+        _first.assert();
+        _second.assert();
+        _last.assert();
+        let ids: Vec<&String> = documents.iter().map(|document| document.id()).collect();
+        assert_eq!(ids, vec!["ferris", "crab", "lobster"]);
+        assert_eq!(documents[0].version(), 2);
+        assert_eq!(documents[2].source()["name"], "Lobster");
+        assert_eq!(scroll.scroll_id(), "ferris-scroll-3");
+        assert!(scroll.next().is_none());
+    }
+
+    #[test]
+    fn scroll_fail_expired() {
+        let _m = mockito::mock("GET", "/_scroll/nemo-scroll?scroll=30s")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "Non-existing or expired scroll identifier",
+                        "status": 404
+                    },
+                    "controller": "document",
+                    "action": "scroll",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut scroll = k.document().scroll("nemo-scroll", "30s").unwrap();
+        let err = scroll.next().unwrap().unwrap_err();
+
+        _m.assert();
+        assert_eq!(
+            err.downcast_ref::<KuzzleError>().unwrap().status(),
+            Some(404)
+        );
+        assert!(scroll.next().is_none());
     }
 }
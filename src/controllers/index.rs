@@ -1,7 +1,8 @@
+use crate::controllers::readiness::{wait_until_ready, POLL_INTERVAL};
 use crate::kuzzle::Kuzzle;
 use crate::types::{KuzzleRequest, QueryOptions, SdkError};
-use serde_json::to_value;
 use std::error::Error;
+use std::time::Duration;
 
 pub struct IndexController<'a>(pub &'a Kuzzle);
 
@@ -25,16 +26,25 @@ impl<'a> IndexController<'a> {
     ///
     /// ```
     ///
-    pub fn create(&self, index: &str) -> Result<(), Box<Error>> {
-        if index.is_empty() {
+    pub fn create(&self, index: &str) -> Result<(), Box<dyn Error>> {
+        self.create_with_options(index, QueryOptions::new())
+    }
+
+    /// Like `create`, with request options.
+    pub fn create_with_options(
+        &self,
+        index: &str,
+        options: QueryOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        if index.is_empty() && !options.skip_validation() {
             return Err(Box::new(SdkError::new(
                 "IndexController::create",
                 "index argument must not be empty.",
             )));
         }
 
-        let req: KuzzleRequest = KuzzleRequest::new("index", "create").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        let req: KuzzleRequest = kuzzle_request!("index", "create", index: index);
+        let res = self.kuzzle().query(req, options)?;
         match &res.error() {
             None => Ok(()),
             Some(k_err) => Err(Box::new(k_err.clone())),
@@ -60,22 +70,67 @@ impl<'a> IndexController<'a> {
     ///
     /// ```
     ///
-    pub fn delete(&self, index: &str) -> Result<(), Box<Error>> {
-        if index.is_empty() {
+    pub fn delete(&self, index: &str) -> Result<(), Box<dyn Error>> {
+        self.delete_with_options(index, QueryOptions::new())
+    }
+
+    /// Like `delete`, with request options.
+    pub fn delete_with_options(
+        &self,
+        index: &str,
+        options: QueryOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        if index.is_empty() && !options.skip_validation() {
             return Err(Box::new(SdkError::new(
                 "IndexController::delete",
                 "index argument must not be empty.",
             )));
         }
 
-        let req: KuzzleRequest = KuzzleRequest::new("index", "delete").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        let req: KuzzleRequest = kuzzle_request!("index", "delete", index: index);
+        let res = self.kuzzle().query(req, options)?;
         match &res.error() {
             None => Ok(()),
             Some(k_err) => Err(Box::new(k_err.clone())),
         }
     }
 
+    /// Creates the given index if it does not exist yet, so bootstrap
+    /// scripts can be run again safely. Returns whether the index was created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.index().ensure("ferris_index");
+    ///
+    /// ```
+    ///
+    pub fn ensure(&self, index: &str) -> Result<bool, Box<dyn Error>> {
+        if index.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "IndexController::ensure",
+                "index argument must not be empty.",
+            )));
+        }
+
+        if self.exists(index)? {
+            return Ok(false);
+        }
+
+        self.create(index)?;
+        Ok(true)
+    }
+
     /// Checks if the given index exists in Kuzzle.
     ///
     /// # Example
@@ -95,22 +150,62 @@ impl<'a> IndexController<'a> {
     ///
     /// ```
     ///
-    pub fn exists(&self, index: &str) -> Result<bool, Box<Error>> {
-        if index.is_empty() {
+    pub fn exists(&self, index: &str) -> Result<bool, Box<dyn Error>> {
+        self.exists_with_options(index, QueryOptions::new())
+    }
+
+    /// Like `exists`, with request options.
+    pub fn exists_with_options(
+        &self,
+        index: &str,
+        options: QueryOptions,
+    ) -> Result<bool, Box<dyn Error>> {
+        if index.is_empty() && !options.skip_validation() {
             return Err(Box::new(SdkError::new(
                 "IndexController::exists",
                 "index argument must not be empty.",
             )));
         }
 
-        let req: KuzzleRequest = KuzzleRequest::new("index", "exists").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        let req: KuzzleRequest = kuzzle_request!("index", "exists", index: index);
+        let res = self.kuzzle().query(req, options)?;
         match &res.error() {
             None => Ok(res.result().as_bool().unwrap()),
             Some(k_err) => Err(Box::new(k_err.clone())),
         }
     }
 
+    /// Waits until the index exists, polling Kuzzle, so writes can follow
+    /// its creation safely. Fails with an `SdkError` once `timeout` elapsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use std::time::Duration;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.index().wait_until_ready("ferris_index", Duration::from_secs(5));
+    ///
+    /// ```
+    ///
+    pub fn wait_until_ready(&self, index: &str, timeout: Duration) -> Result<(), Box<dyn Error>> {
+        wait_until_ready(
+            "IndexController::wait_until_ready",
+            index,
+            timeout,
+            POLL_INTERVAL,
+            || self.exists(index),
+        )
+    }
+
     /// Return the current autorefresh status for the index.
     /// Each index has an autorefresh flag. When set to true, each write request trigger
     /// a refresh action on Elasticsearch. Without a refresh after a write request,
@@ -133,16 +228,25 @@ impl<'a> IndexController<'a> {
     ///
     /// ```
     ///
-    pub fn get_auto_refresh(&self, index: &str) -> Result<bool, Box<Error>> {
-        if index.is_empty() {
+    pub fn get_auto_refresh(&self, index: &str) -> Result<bool, Box<dyn Error>> {
+        self.get_auto_refresh_with_options(index, QueryOptions::new())
+    }
+
+    /// Like `get_auto_refresh`, with request options.
+    pub fn get_auto_refresh_with_options(
+        &self,
+        index: &str,
+        options: QueryOptions,
+    ) -> Result<bool, Box<dyn Error>> {
+        if index.is_empty() && !options.skip_validation() {
             return Err(Box::new(SdkError::new(
                 "IndexController::get_auto_refresh",
                 "index argument must not be empty.",
             )));
         }
 
-        let req: KuzzleRequest = KuzzleRequest::new("index", "getAutoRefresh").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        let req: KuzzleRequest = kuzzle_request!("index", "getAutoRefresh", index: index);
+        let res = self.kuzzle().query(req, options)?;
         match &res.error() {
             None => Ok(res.result().as_bool().unwrap()),
             Some(k_err) => Err(Box::new(k_err.clone())),
@@ -168,9 +272,14 @@ impl<'a> IndexController<'a> {
     ///
     /// ```
     ///
-    pub fn list(&self) -> Result<Vec<String>, Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("index", "list");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
+    pub fn list(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        self.list_with_options(QueryOptions::new())
+    }
+
+    /// Like `list`, with request options.
+    pub fn list_with_options(&self, options: QueryOptions) -> Result<Vec<String>, Box<dyn Error>> {
+        let req: KuzzleRequest = kuzzle_request!("index", "list");
+        let res = self.kuzzle().query(req, options)?;
         match &res.error() {
             None => Ok(res
                 .result()
@@ -202,21 +311,30 @@ impl<'a> IndexController<'a> {
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().list();
+    /// let res = kuzzle.index().mdelete(vec!["nyc-open-data".to_string()]);
     ///
     /// ```
     ///
-    pub fn mdelete(&self, indexes: Vec<String>) -> Result<Vec<String>, Box<Error>> {
-        if indexes.is_empty() {
+    pub fn mdelete(&self, indexes: Vec<String>) -> Result<Vec<String>, Box<dyn Error>> {
+        self.mdelete_with_options(indexes, QueryOptions::new())
+    }
+
+    /// Like `mdelete`, with request options.
+    pub fn mdelete_with_options(
+        &self,
+        indexes: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        if indexes.is_empty() && !options.skip_validation() {
             return Err(Box::new(SdkError::new(
                 "IndexController::mDelete",
                 "indexes argument must not be empty.",
             )));
         }
 
-        let req: KuzzleRequest = KuzzleRequest::new("index", "mDelete")
-            .add_to_body("indexes".to_string(), to_value(indexes).unwrap());
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        let req: KuzzleRequest =
+            kuzzle_request!("index", "mDelete", body: { "indexes" => indexes });
+        let res = self.kuzzle().query(req, options)?;
         match &res.error() {
             None => Ok(res
                 .result()
@@ -240,6 +358,8 @@ impl<'a> IndexController<'a> {
     ///
     /// Note: forcing immediate refreshes comes with performance costs,
     /// and should only performed when absolutely necessary.
+    /// To wait for a single write to be searchable, prefer passing
+    /// `RefreshMode::WaitFor` to the write method through `QueryOptions`.
     ///
     /// # Example
     ///
@@ -258,16 +378,25 @@ impl<'a> IndexController<'a> {
     ///
     /// ```
     ///
-    pub fn refresh(&self, index: &str) -> Result<(), Box<Error>> {
-        if index.is_empty() {
+    pub fn refresh(&self, index: &str) -> Result<(), Box<dyn Error>> {
+        self.refresh_with_options(index, QueryOptions::new())
+    }
+
+    /// Like `refresh`, with request options.
+    pub fn refresh_with_options(
+        &self,
+        index: &str,
+        options: QueryOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        if index.is_empty() && !options.skip_validation() {
             return Err(Box::new(SdkError::new(
                 "IndexController::refresh",
                 "index argument must not be empty.",
             )));
         }
 
-        let req: KuzzleRequest = KuzzleRequest::new("index", "refresh").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        let req: KuzzleRequest = kuzzle_request!("index", "refresh", index: index);
+        let res = self.kuzzle().query(req, options)?;
         match &res.error() {
             None => Ok(()),
             Some(k_err) => Err(Box::new(k_err.clone())),
@@ -299,9 +428,17 @@ impl<'a> IndexController<'a> {
     ///
     /// ```
     ///
-    pub fn refresh_internal(&self) -> Result<(), Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("index", "refreshInternal");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
+    pub fn refresh_internal(&self) -> Result<(), Box<dyn Error>> {
+        self.refresh_internal_with_options(QueryOptions::new())
+    }
+
+    /// Like `refresh_internal`, with request options.
+    pub fn refresh_internal_with_options(
+        &self,
+        options: QueryOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let req: KuzzleRequest = kuzzle_request!("index", "refreshInternal");
+        let res = self.kuzzle().query(req, options)?;
         match &res.error() {
             None => Ok(()),
             Some(k_err) => Err(Box::new(k_err.clone())),
@@ -334,18 +471,29 @@ impl<'a> IndexController<'a> {
     ///
     /// ```
     ///
-    pub fn set_auto_refresh(&self, index: &str, auto_refresh: bool) -> Result<(), Box<Error>> {
-        if index.is_empty() {
+    pub fn set_auto_refresh(&self, index: &str, auto_refresh: bool) -> Result<(), Box<dyn Error>> {
+        self.set_auto_refresh_with_options(index, auto_refresh, QueryOptions::new())
+    }
+
+    /// Like `set_auto_refresh`, with request options.
+    pub fn set_auto_refresh_with_options(
+        &self,
+        index: &str,
+        auto_refresh: bool,
+        options: QueryOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        if index.is_empty() && !options.skip_validation() {
             return Err(Box::new(SdkError::new(
                 "IndexController::set_auto_refresh",
                 "index argument must not be empty.",
             )));
         }
 
-        let req: KuzzleRequest = KuzzleRequest::new("index", "setAutoRefresh")
-            .set_index(index)
-            .add_to_body("autoRefresh".to_string(), to_value(auto_refresh).unwrap());
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        let req: KuzzleRequest = kuzzle_request!("index", "setAutoRefresh",
+            index: index,
+            body: { "autoRefresh" => auto_refresh }
+        );
+        let res = self.kuzzle().query(req, options)?;
         match &res.error() {
             None => Ok(()),
             Some(k_err) => Err(Box::new(k_err.clone())),
@@ -353,7 +501,7 @@ impl<'a> IndexController<'a> {
     }
 
     fn kuzzle(&self) -> &'a Kuzzle {
-        &self.0
+        self.0
     }
 }
 
@@ -361,8 +509,13 @@ impl<'a> IndexController<'a> {
 mod tests {
     use super::*;
     use crate::protocols::Http;
-    use crate::types::KuzzleOptions;
+    use crate::types::{Document, KuzzleError, KuzzleOptions};
     use mockito;
+    use serde_json::{from_value, json};
+
+    fn cached(id: &str) -> Document {
+        from_value(json!({ "_id": id, "_version": 1, "_source": {} })).unwrap()
+    }
 
     #[test]
     fn create_ok() {
@@ -430,6 +583,41 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn create_skip_validation_empty_index_name() {
+        let _m = mockito::mock("POST", "//_create")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "c6fd04c1-45d0-48ef-9eed-ef95c4a97422",
+                    "status": 400,
+                    "error": {
+                        "message": "Invalid index name",
+                        "status": 400,
+                        "stack": "BadRequestError: Invalid index name\n"
+                    },
+                    "controller": "index",
+                    "action": "create",
+                    "collection": null,
+                    "index": "",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .index()
+            .create_with_options("", QueryOptions::new().set_skip_validation(true));
+
+        _m.assert();
+        let err = res.unwrap_err();
+        assert!(err.downcast_ref::<KuzzleError>().is_some());
+    }
+
     #[test]
     fn delete_ok() {
         let _m = mockito::mock("DELETE", "/ferris_index")
@@ -456,6 +644,43 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn delete_ok_invalidates_caches() {
+        let _m = mockito::mock("DELETE", "/ferris_index")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "index",
+                    "action": "delete",
+                    "collection": null,
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "acknowledged": true }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_mapping_cache(true);
+        k.set_document_cache(true);
+        k.cache_document("ferris_index", "crabs", cached("ferris"));
+        k.cache_document("nemo_index", "fishes", cached("nemo"));
+        k.cache_mapping("ferris_index", "crabs", json!({ "properties": {} }));
+        k.cache_mapping("ferris_index", "lobsters", json!({ "properties": {} }));
+        k.cache_mapping("nemo_index", "fishes", json!({ "properties": {} }));
+        k.index().delete("ferris_index").unwrap();
+
+        assert_eq!(k.cached_mapping("ferris_index", "crabs"), None);
+        assert_eq!(k.cached_mapping("ferris_index", "lobsters"), None);
+        assert!(k.cached_mapping("nemo_index", "fishes").is_some());
+        assert_eq!(k.cached_document("ferris_index", "crabs", "ferris"), None);
+        assert!(k.cached_document("nemo_index", "fishes", "nemo").is_some());
+    }
+
     #[test]
     fn delete_fail_not_found_index() {
         let _m = mockito::mock("DELETE", "/ferris_index")
@@ -491,6 +716,78 @@ mod tests {
         assert!(res.is_err());
     }
 
+    fn ensure_exists_response(exists: bool) -> String {
+        format!(
+            r#"{{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "index",
+                "action": "exists",
+                "collection": null,
+                "index": "ensured_index",
+                "volatile": null,
+                "result": {}
+            }}"#,
+            exists
+        )
+    }
+
+    #[test]
+    fn ensure_ok_creates_missing_index() {
+        let _exists = mockito::mock("GET", "/ensured_index/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ensure_exists_response(false))
+            .expect(1)
+            .create();
+        let _create = mockito::mock("POST", "/ensured_index/_create")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "index",
+                    "action": "create",
+                    "collection": null,
+                    "index": "ensured_index",
+                    "volatile": null,
+                    "result": { "acknowledged": true, "shards_acknowledged": true }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.index().ensure("ensured_index");
+
+        assert!(res.unwrap());
+        _exists.assert();
+        _create.assert();
+    }
+
+    #[test]
+    fn ensure_ok_already_exists() {
+        let _exists = mockito::mock("GET", "/ensured_index/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ensure_exists_response(true))
+            .expect(1)
+            .create();
+        let _create = mockito::mock("POST", "/ensured_index/_create")
+            .expect(0)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.index().ensure("ensured_index");
+
+        assert!(!res.unwrap());
+        _exists.assert();
+        _create.assert();
+    }
+
     #[test]
     fn exists_ok_true() {
         let _m = mockito::mock("GET", "/ferris_index/_exists")
@@ -513,7 +810,61 @@ mod tests {
         let res = k.index().exists("ferris_index");
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), true);
+        assert!(res.unwrap());
+    }
+
+    #[test]
+    fn wait_until_ready_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                      "status": 200,
+                      "error": null,
+                      "index": "ferris_index",
+                      "controller": "index",
+                      "action": "exists",
+                      "requestId": "29d98f35-8cfd-4eeb-97fd-f135d931f0bd",
+                      "result": true
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .index()
+            .wait_until_ready("ferris_index", Duration::from_secs(1));
+
+        assert!(res.is_ok());
+        _m.assert();
+    }
+
+    #[test]
+    fn wait_until_ready_fail_timeout() {
+        let _m = mockito::mock("GET", "/ferris_index/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                      "status": 200,
+                      "error": null,
+                      "index": "ferris_index",
+                      "controller": "index",
+                      "action": "exists",
+                      "requestId": "29d98f35-8cfd-4eeb-97fd-f135d931f0bd",
+                      "result": false
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .index()
+            .wait_until_ready("ferris_index", Duration::from_millis(150));
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_some());
     }
 
     #[test]
@@ -538,7 +889,7 @@ mod tests {
         let res = k.index().exists("ferris_index");
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), false);
+        assert!(!res.unwrap());
     }
 
     #[test]
@@ -598,7 +949,7 @@ mod tests {
         let res = k.index().get_auto_refresh("ferris_index");
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), true);
+        assert!(res.unwrap());
     }
 
     #[test]
@@ -623,7 +974,7 @@ mod tests {
         let res = k.index().get_auto_refresh("ferris_index");
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), false);
+        assert!(!res.unwrap());
     }
 
     #[test]
@@ -757,6 +1108,45 @@ mod tests {
         assert_eq!(res.unwrap().len(), 2);
     }
 
+    #[test]
+    fn mdelete_ok_invalidates_caches() {
+        let _m = mockito::mock("DELETE", "/_mdelete")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "index",
+                    "action": "mDelete",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "deleted": ["ferris_index", "crab_index"] }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_mapping_cache(true);
+        k.set_document_cache(true);
+        k.cache_document("crab_index", "shells", cached("crab"));
+        k.cache_document("nemo_index", "fishes", cached("nemo"));
+        k.cache_mapping("ferris_index", "crabs", json!({ "properties": {} }));
+        k.cache_mapping("crab_index", "shells", json!({ "properties": {} }));
+        k.cache_mapping("nemo_index", "fishes", json!({ "properties": {} }));
+        k.index()
+            .mdelete(vec!["ferris_index".to_string(), "crab_index".to_string()])
+            .unwrap();
+
+        assert_eq!(k.cached_mapping("ferris_index", "crabs"), None);
+        assert_eq!(k.cached_mapping("crab_index", "shells"), None);
+        assert!(k.cached_mapping("nemo_index", "fishes").is_some());
+        assert_eq!(k.cached_document("crab_index", "shells", "crab"), None);
+        assert!(k.cached_document("nemo_index", "fishes", "nemo").is_some());
+    }
+
     #[test]
     fn mdelete_fail_error() {
         let _m = mockito::mock("DELETE", "/_mdelete")
@@ -1,9 +1,1471 @@
 use crate::kuzzle::Kuzzle;
+use crate::types::{KuzzleRequest, MsSetOptions, QueryOptions, SdkError};
+use serde_json::{json, to_value, Value};
+use std::error::Error;
 
 pub struct MemoryStorageController<'a>(pub &'a Kuzzle);
 
 impl<'a> MemoryStorageController<'a> {
-    fn _kuzzle(&self) -> &'a Kuzzle {
-        &self.0
+    /// Returns the value of a key, or `None` if the key does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().get("ferris_key");
+    ///
+    /// ```
+    ///
+    pub fn get(&self, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::get",
+                "key argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "get").set_id(key);
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_str().map(|x| x.to_string())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sets a value on a key, with an optional expiration delay and
+    /// condition on the key existence (see `MsSetOptions`).
+    /// Returns `false` if the key was not set because of the `nx` or
+    /// `xx` condition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, MsSetOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().set(
+    ///     "ferris_session",
+    ///     "crab",
+    ///     MsSetOptions::new().set_ex(3600).set_nx(true),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn set(
+        &self,
+        key: &str,
+        value: &str,
+        options: MsSetOptions,
+    ) -> Result<bool, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::set",
+                "key argument must not be empty.",
+            )));
+        }
+
+        if options.ex().is_some() && options.px().is_some() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::set",
+                "ex and px options are mutually exclusive.",
+            )));
+        }
+
+        if options.nx() && options.xx() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::set",
+                "nx and xx options are mutually exclusive.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = KuzzleRequest::new("ms", "set")
+            .set_id(key)
+            .add_to_body("value".to_string(), to_value(value).unwrap());
+        if let Some(ex) = options.ex() {
+            req = req.add_to_body("ex".to_string(), to_value(ex).unwrap());
+        }
+        if let Some(px) = options.px() {
+            req = req.add_to_body("px".to_string(), to_value(px).unwrap());
+        }
+        if options.nx() {
+            req = req.add_to_body("nx".to_string(), Value::from(true));
+        }
+        if options.xx() {
+            req = req.add_to_body("xx".to_string(), Value::from(true));
+        }
+
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(!res.result().is_null()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sets a new value for a key and returns its previous value,
+    /// or `None` if the key did not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().getset("ferris_key", "new_value");
+    ///
+    /// ```
+    ///
+    pub fn getset(&self, key: &str, value: &str) -> Result<Option<String>, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::getset",
+                "key argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "getset")
+            .set_id(key)
+            .add_to_body("value".to_string(), to_value(value).unwrap());
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_str().map(|x| x.to_string())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sets a value on a key only if it does not already exist.
+    /// Returns `true` if the key has been set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().setnx("ferris_lock", "owner_id");
+    ///
+    /// ```
+    ///
+    pub fn setnx(&self, key: &str, value: &str) -> Result<bool, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::setnx",
+                "key argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "setnx")
+            .set_id(key)
+            .add_to_body("value".to_string(), to_value(value).unwrap());
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_u64().unwrap() == 1),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sets a key with the provided value, and an expiration delay
+    /// expressed in seconds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().setex("ferris_key", 60, "value");
+    ///
+    /// ```
+    ///
+    pub fn setex(&self, key: &str, seconds: u64, value: &str) -> Result<(), Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::setex",
+                "key argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "setex")
+            .set_id(key)
+            .add_to_body("value".to_string(), to_value(value).unwrap())
+            .add_to_body("seconds".to_string(), to_value(seconds).unwrap());
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Appends a value to a key, creating it if needed.
+    /// Returns the length of the new value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().append("ferris_log", "crab walked\n");
+    ///
+    /// ```
+    ///
+    pub fn append(&self, key: &str, value: &str) -> Result<u64, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::append",
+                "key argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "append")
+            .set_id(key)
+            .add_to_body("value".to_string(), to_value(value).unwrap());
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_u64().unwrap()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns the length of the value of a key, `0` if the key does not
+    /// exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().strlen("ferris_key");
+    ///
+    /// ```
+    ///
+    pub fn strlen(&self, key: &str) -> Result<u64, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::strlen",
+                "key argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "strlen").set_id(key);
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_u64().unwrap()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns the values of several keys, in the same order, `None`
+    /// standing for the keys which do not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().mget(vec!["ferris_key".to_string(), "crab_key".to_string()]);
+    ///
+    /// ```
+    ///
+    pub fn mget(&self, keys: Vec<String>) -> Result<Vec<Option<String>>, Box<dyn Error>> {
+        if keys.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::mget",
+                "keys argument must not be empty.",
+            )));
+        }
+
+        // `ms/mget` is a GET route: keys go in the query strings, as a
+        // comma-separated list.
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "mget")
+            .add_to_query_strings("keys".to_string(), Value::from(keys.join(",")));
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res
+                .result()
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|x| x.as_str().map(|x| x.to_string()))
+                .collect()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sets several keys at once, given as `(key, value)` pairs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().mset(vec![
+    ///     ("ferris_key".to_string(), "crab".to_string()),
+    ///     ("nemo_key".to_string(), "fish".to_string()),
+    /// ]);
+    ///
+    /// ```
+    ///
+    pub fn mset(&self, entries: Vec<(String, String)>) -> Result<(), Box<dyn Error>> {
+        if entries.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::mset",
+                "entries argument must not be empty.",
+            )));
+        }
+
+        let entries: Vec<Value> = entries
+            .into_iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect();
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "mset")
+            .add_to_body("entries".to_string(), Value::from(entries));
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Adds members with their scores to a sorted set.
+    /// Returns the number of members actually added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().zadd("ferris_leaderboard", vec![(42.0, "ferris".to_string())]);
+    ///
+    /// ```
+    ///
+    pub fn zadd(&self, key: &str, elements: Vec<(f64, String)>) -> Result<u64, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::zadd",
+                "key argument must not be empty.",
+            )));
+        }
+
+        if elements.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::zadd",
+                "elements argument must not be empty.",
+            )));
+        }
+
+        let elements = elements
+            .into_iter()
+            .map(|(score, member)| json!({ "score": score, "member": member }))
+            .collect::<Vec<Value>>();
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "zadd")
+            .set_id(key)
+            .add_to_body("elements".to_string(), Value::Array(elements));
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_u64().unwrap()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns the members of a sorted set, ordered by ascending score,
+    /// between the `start` and `stop` positions (both inclusive).
+    /// Scores are zero-filled unless `with_scores` is `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().zrange("ferris_leaderboard", 0, -1, true);
+    ///
+    /// ```
+    ///
+    pub fn zrange(
+        &self,
+        key: &str,
+        start: i64,
+        stop: i64,
+        with_scores: bool,
+    ) -> Result<Vec<(String, f64)>, Box<dyn Error>> {
+        self.range("zrange", key, start, stop, with_scores)
+    }
+
+    /// Returns the members of a sorted set, ordered by descending score,
+    /// between the `start` and `stop` positions (both inclusive).
+    /// Scores are zero-filled unless `with_scores` is `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().zrevrange("ferris_leaderboard", 0, 9, true);
+    ///
+    /// ```
+    ///
+    pub fn zrevrange(
+        &self,
+        key: &str,
+        start: i64,
+        stop: i64,
+        with_scores: bool,
+    ) -> Result<Vec<(String, f64)>, Box<dyn Error>> {
+        self.range("zrevrange", key, start, stop, with_scores)
+    }
+
+    /// Returns the score of a member of a sorted set,
+    /// or `None` if the member does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().zscore("ferris_leaderboard", "ferris");
+    ///
+    /// ```
+    ///
+    pub fn zscore(&self, key: &str, member: &str) -> Result<Option<f64>, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::zscore",
+                "key argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "zscore")
+            .set_id(key)
+            .add_to_query_strings("member".to_string(), to_value(member).unwrap());
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => match res.result() {
+                Value::Null => Ok(None),
+                score => Ok(Some(parse_score(score))),
+            },
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Removes members from a sorted set.
+    /// Returns the number of members actually removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().zrem("ferris_leaderboard", vec!["ferris".to_string()]);
+    ///
+    /// ```
+    ///
+    pub fn zrem(&self, key: &str, members: Vec<String>) -> Result<u64, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::zrem",
+                "key argument must not be empty.",
+            )));
+        }
+
+        if members.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::zrem",
+                "members argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("ms", "zrem")
+            .set_id(key)
+            .add_to_body("members".to_string(), to_value(members).unwrap());
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_u64().unwrap()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns the type of the value stored at a key: `string`, `list`,
+    /// `hash`, `set`, `zset`, or `none` if the key does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().key_type("ferris_key");
+    ///
+    /// ```
+    ///
+    pub fn key_type(&self, key: &str) -> Result<String, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::key_type",
+                "key argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("ms", "type", id: key);
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_str().unwrap().to_string()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns the internal encoding of the value stored at a key
+    /// (e.g. `ziplist`, `quicklist`, `embstr`), or `None` if the key
+    /// does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.ms().object_encoding("ferris_key");
+    ///
+    /// ```
+    ///
+    pub fn object_encoding(&self, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "MemoryStorageController::object_encoding",
+                "key argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("ms", "object",
+            id: key,
+            query: { "subcommand" => "encoding" }
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(res.result().as_str().map(|x| x.to_string())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    fn range(
+        &self,
+        action: &str,
+        key: &str,
+        start: i64,
+        stop: i64,
+        with_scores: bool,
+    ) -> Result<Vec<(String, f64)>, Box<dyn Error>> {
+        if key.is_empty() {
+            return Err(Box::new(SdkError::new(
+                &format!("MemoryStorageController::{}", action),
+                "key argument must not be empty.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = KuzzleRequest::new("ms", action)
+            .set_id(key)
+            .add_to_query_strings("start".to_string(), to_value(start).unwrap())
+            .add_to_query_strings("stop".to_string(), to_value(stop).unwrap());
+        if with_scores {
+            req = req.add_to_query_strings("options".to_string(), to_value("withscores").unwrap());
+        }
+
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => {
+                let values = res.result().as_array().unwrap();
+                if with_scores {
+                    // Members and scores are returned as a flat [member, score, ...] list.
+                    Ok(values
+                        .chunks(2)
+                        .map(|pair| (pair[0].as_str().unwrap().to_string(), parse_score(&pair[1])))
+                        .collect::<Vec<(String, f64)>>())
+                } else {
+                    Ok(values
+                        .iter()
+                        .map(|member| (member.as_str().unwrap().to_string(), 0.0))
+                        .collect::<Vec<(String, f64)>>())
+                }
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    fn kuzzle(&self) -> &'a Kuzzle {
+        self.0
+    }
+}
+
+// Kuzzle returns sorted set scores as strings to keep their precision.
+fn parse_score(score: &Value) -> f64 {
+    match score {
+        Value::String(score) => score.parse::<f64>().unwrap(),
+        score => score.as_f64().unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::KuzzleOptions;
+    use mockito::{self, Matcher};
+    use serde_json::json;
+
+    #[test]
+    fn set_get_ok_round_trip() {
+        let _set = mockito::mock("POST", "/ms/_set/ferris_key")
+            .match_body(Matcher::Json(json!({ "value": "crab", "ex": 60 })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "set",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": "OK"
+                }"#,
+            )
+            .expect(1)
+            .create();
+        let _get = mockito::mock("GET", "/ms/ferris_key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "get",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": "crab"
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let set = k
+            .ms()
+            .set("ferris_key", "crab", MsSetOptions::new().set_ex(60))
+            .unwrap();
+        let value = k.ms().get("ferris_key").unwrap();
+
+        _set.assert();
+        _get.assert();
+        assert!(set);
+        assert_eq!(value, Some("crab".to_string()));
+    }
+
+    #[test]
+    fn set_ok_nx_key_exists() {
+        let _m = mockito::mock("POST", "/ms/_set/ferris_key")
+            .match_body(Matcher::Json(
+                json!({ "value": "crab", "px": 1500, "nx": true }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "set",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let set = k
+            .ms()
+            .set(
+                "ferris_key",
+                "crab",
+                MsSetOptions::new().set_px(1500).set_nx(true),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert!(!set);
+    }
+
+    #[test]
+    fn set_fail_nx_and_xx() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .ms()
+            .set(
+                "ferris_key",
+                "crab",
+                MsSetOptions::new().set_nx(true).set_xx(true),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "[MemoryStorageController::set] nx and xx options are mutually exclusive."
+        );
+    }
+
+    #[test]
+    fn get_ok_unknown_key() {
+        let _m = mockito::mock("GET", "/ms/nemo_key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "get",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let value = k.ms().get("nemo_key").unwrap();
+
+        _m.assert();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn getset_ok_swap() {
+        let _m = mockito::mock("POST", "/ms/_getset/ferris_key")
+            .match_body(Matcher::Json(json!({ "value": "new_value" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "getset",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": "old_value"
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().getset("ferris_key", "new_value");
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), Some("old_value".to_string()));
+    }
+
+    #[test]
+    fn getset_ok_unknown_key() {
+        let _m = mockito::mock("POST", "/ms/_getset/ferris_key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "getset",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().getset("ferris_key", "new_value");
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), None);
+    }
+
+    #[test]
+    fn setnx_ok_lock_acquired() {
+        let _m = mockito::mock("POST", "/ms/_setnx/ferris_lock")
+            .match_body(Matcher::Json(json!({ "value": "worker_1" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "setnx",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": 1
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().setnx("ferris_lock", "worker_1");
+
+        assert!(res.is_ok());
+        assert!(res.unwrap());
+    }
+
+    #[test]
+    fn setnx_ok_lock_already_held() {
+        let _m = mockito::mock("POST", "/ms/_setnx/ferris_lock")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "setnx",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": 0
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().setnx("ferris_lock", "worker_2");
+
+        assert!(res.is_ok());
+        assert!(!res.unwrap());
+    }
+
+    #[test]
+    fn setnx_fail_empty_key() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().setnx("", "worker_1");
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn setex_ok() {
+        let _m = mockito::mock("POST", "/ms/_setex/ferris_key")
+            .match_body(Matcher::Json(json!({ "value": "value", "seconds": 60 })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "setex",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": "OK"
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().setex("ferris_key", 60, "value");
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn append_ok() {
+        let _m = mockito::mock("POST", "/ms/_append/ferris_log")
+            .match_body(Matcher::Json(json!({ "value": " and swam" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "append",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": 23
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let len = k.ms().append("ferris_log", " and swam").unwrap();
+
+        _m.assert();
+        assert_eq!(len, 23);
+    }
+
+    #[test]
+    fn strlen_ok() {
+        let _m = mockito::mock("GET", "/ms/_strlen/ferris_key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "strlen",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": 4
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let len = k.ms().strlen("ferris_key").unwrap();
+
+        _m.assert();
+        assert_eq!(len, 4);
+    }
+
+    #[test]
+    fn mget_ok_missing_keys() {
+        let _m = mockito::mock("GET", "/ms/_mget?keys=ferris_key%2Cnemo_key%2Ccrab_key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "mget",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": ["crab", null, "lobster"]
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let values = k
+            .ms()
+            .mget(vec![
+                "ferris_key".to_string(),
+                "nemo_key".to_string(),
+                "crab_key".to_string(),
+            ])
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(
+            values,
+            vec![Some("crab".to_string()), None, Some("lobster".to_string())]
+        );
+    }
+
+    #[test]
+    fn mget_fail_no_key() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().mget(vec![]);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn mset_ok() {
+        let _m = mockito::mock("POST", "/ms/_mset")
+            .match_body(Matcher::Json(json!({
+                "entries": [
+                    { "key": "ferris_key", "value": "crab" },
+                    { "key": "nemo_key", "value": "fish" }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "mset",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": "OK"
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().mset(vec![
+            ("ferris_key".to_string(), "crab".to_string()),
+            ("nemo_key".to_string(), "fish".to_string()),
+        ]);
+
+        _m.assert();
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn zadd_ok_leaderboard() {
+        let _m = mockito::mock("POST", "/ms/_zadd/ferris_leaderboard")
+            .match_body(Matcher::Json(json!({
+                "elements": [
+                    { "score": 42.0, "member": "ferris" },
+                    { "score": 12.5, "member": "corro" },
+                    { "score": 7.0, "member": "sebastian" }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "zadd",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": 3
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().zadd(
+            "ferris_leaderboard",
+            vec![
+                (42.0, "ferris".to_string()),
+                (12.5, "corro".to_string()),
+                (7.0, "sebastian".to_string()),
+            ],
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), 3);
+    }
+
+    #[test]
+    fn zadd_fail_empty_elements() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().zadd("ferris_leaderboard", vec![]);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn zrange_ok_with_scores() {
+        let _m = mockito::mock(
+            "GET",
+            "/ms/_zrange/ferris_leaderboard?options=withscores&start=0&stop=-1",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "zrange",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": ["sebastian", "7", "corro", "12.5", "ferris", "42"]
+                }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().zrange("ferris_leaderboard", 0, -1, true);
+
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            vec![
+                ("sebastian".to_string(), 7.0),
+                ("corro".to_string(), 12.5),
+                ("ferris".to_string(), 42.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn zrevrange_ok_without_scores() {
+        let _m = mockito::mock("GET", "/ms/_zrevrange/ferris_leaderboard?start=0&stop=1")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "zrevrange",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": ["ferris", "corro"]
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().zrevrange("ferris_leaderboard", 0, 1, false);
+
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            vec![("ferris".to_string(), 0.0), ("corro".to_string(), 0.0)]
+        );
+    }
+
+    #[test]
+    fn zscore_ok() {
+        let _m = mockito::mock("GET", "/ms/_zscore/ferris_leaderboard/corro")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "zscore",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": "12.5"
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().zscore("ferris_leaderboard", "corro");
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), Some(12.5));
+    }
+
+    #[test]
+    fn zscore_ok_unknown_member() {
+        let _m = mockito::mock("GET", "/ms/_zscore/ferris_leaderboard/nobody")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "zscore",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().zscore("ferris_leaderboard", "nobody");
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), None);
+    }
+
+    #[test]
+    fn zrem_ok() {
+        let _m = mockito::mock("DELETE", "/ms/_zrem/ferris_leaderboard")
+            .match_body(Matcher::Json(json!({ "members": ["sebastian", "nobody"] })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "zrem",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": 1
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().zrem(
+            "ferris_leaderboard",
+            vec!["sebastian".to_string(), "nobody".to_string()],
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), 1);
+    }
+
+    #[test]
+    fn key_type_ok_list() {
+        let _m = mockito::mock("GET", "/ms/_type/ferris_list")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "type",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": "list"
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().key_type("ferris_list");
+
+        assert_eq!(res.unwrap(), "list");
+    }
+
+    #[test]
+    fn key_type_ok_missing_key() {
+        let _m = mockito::mock("GET", "/ms/_type/nemo")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "type",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": "none"
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().key_type("nemo");
+
+        assert_eq!(res.unwrap(), "none");
+    }
+
+    #[test]
+    fn key_type_fail_empty_key() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().key_type("");
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn object_encoding_ok() {
+        let _m = mockito::mock("GET", "/ms/_object/ferris_list?subcommand=encoding")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "object",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": "quicklist"
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().object_encoding("ferris_list");
+
+        assert_eq!(res.unwrap(), Some("quicklist".to_string()));
+        _m.assert();
+    }
+
+    #[test]
+    fn object_encoding_ok_missing_key() {
+        let _m = mockito::mock("GET", "/ms/_object/nemo?subcommand=encoding")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "object",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.ms().object_encoding("nemo");
+
+        assert_eq!(res.unwrap(), None);
     }
 }
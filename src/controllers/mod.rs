@@ -4,6 +4,7 @@ mod collection;
 mod document;
 mod index;
 mod memory_storage;
+mod readiness;
 mod realtime;
 mod security;
 mod server;
@@ -11,9 +12,9 @@ mod server;
 pub use self::auth::AuthController;
 pub use self::bulk::BulkController;
 pub use self::collection::CollectionController;
-pub use self::document::DocumentController;
+pub use self::document::{DocumentController, ScrollIterator};
 pub use self::index::IndexController;
 pub use self::memory_storage::MemoryStorageController;
 pub use self::realtime::RealtimeController;
-pub use self::security::SecurityController;
-pub use self::server::ServerController;
+pub use self::security::{SecurityController, UserSearch};
+pub use self::server::{ServerController, StatsWatcher, WatchHandle};
@@ -0,0 +1,121 @@
+use crate::types::SdkError;
+use std::error::Error;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Delay between two readiness checks.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time source of polling loops, faked in tests.
+pub(crate) trait Clock {
+    fn now(&self) -> Instant;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The system clock, sleeping the current thread.
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Calls `is_ready` every `interval` until it returns `true`, or fails with
+/// an `SdkError` naming `cause` and `resource` once `timeout` has elapsed.
+/// Errors returned by `is_ready` are propagated as is.
+pub(crate) fn wait_until_ready<F>(
+    cause: &str,
+    resource: &str,
+    timeout: Duration,
+    interval: Duration,
+    mut is_ready: F,
+) -> Result<(), Box<dyn Error>>
+where
+    F: FnMut() -> Result<bool, Box<dyn Error>>,
+{
+    let start = Instant::now();
+
+    loop {
+        if is_ready()? {
+            return Ok(());
+        }
+
+        let elapsed = start.elapsed();
+        if elapsed >= timeout {
+            return Err(Box::new(SdkError::new(
+                cause,
+                &format!(
+                    "timed out after {}ms waiting for {} to be ready.",
+                    timeout.as_millis(),
+                    resource
+                ),
+            )));
+        }
+
+        thread::sleep(interval.min(timeout - elapsed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wait_until_ready_ok_second_poll() {
+        let mut polls = 0;
+        let res = wait_until_ready(
+            "IndexController::wait_until_ready",
+            "ferris_index",
+            Duration::from_secs(1),
+            Duration::from_millis(10),
+            || {
+                polls += 1;
+                Ok(polls == 2)
+            },
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(polls, 2);
+    }
+
+    #[test]
+    fn wait_until_ready_fail_timeout() {
+        let res = wait_until_ready(
+            "IndexController::wait_until_ready",
+            "ferris_index",
+            Duration::from_millis(50),
+            Duration::from_millis(10),
+            || Ok(false),
+        );
+
+        let err = res.unwrap_err();
+        let sdk_err = err.downcast_ref::<SdkError>().unwrap();
+        assert_eq!(
+            sdk_err.message(),
+            "timed out after 50ms waiting for ferris_index to be ready."
+        );
+    }
+
+    #[test]
+    fn wait_until_ready_fail_check_error() {
+        let mut polls = 0;
+        let res = wait_until_ready(
+            "IndexController::wait_until_ready",
+            "ferris_index",
+            Duration::from_secs(1),
+            Duration::from_millis(10),
+            || {
+                polls += 1;
+                Err(Box::new(SdkError::new("test", "boom")))
+            },
+        );
+
+        assert!(res.is_err());
+        assert_eq!(polls, 1);
+    }
+}
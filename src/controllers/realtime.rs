@@ -1,15 +1,1003 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{
+    KuzzleRequest, KuzzleResponse, Notification, QueryOptions, SdkError, SubscribeOptions,
+    SubscriptionInfo,
+};
+use serde_json::{json, Value};
+use std::error::Error;
+use std::sync::mpsc::{channel, Receiver};
 
 pub struct RealtimeController<'a>(pub &'a Kuzzle);
 
 impl<'a> RealtimeController<'a> {
-    pub fn subscribe(&self, options: QueryOptions) {
-        let req: KuzzleRequest = KuzzleRequest::new("realtime", "subscribe");
-        self.kuzzle().query(req, options).is_ok();
+    /// Returns the number of connections subscribed to a room.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let count = kuzzle.realtime().count("some-room-id", QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn count(&self, room_id: &str, options: QueryOptions) -> Result<u64, Box<dyn Error>> {
+        if room_id.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "RealtimeController::count",
+                "room_id argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("realtime", "count",
+            query: { "roomId" => room_id }
+        );
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result()["count"].as_u64().unwrap_or_default()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Lists the subscriptions the current user is allowed to see, as the
+    /// number of connections of each room, by index and collection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let subscriptions = kuzzle.realtime().list(QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn list(&self, options: QueryOptions) -> Result<Value, Box<dyn Error>> {
+        let req: KuzzleRequest = kuzzle_request!("realtime", "list");
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result().clone()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sends a volatile message to the subscribers of a collection whose
+    /// filters it matches. Unlike documents, messages are not persisted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.realtime().publish(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "species": "crab", "status": "spotted" }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn publish(
+        &self,
+        index: &str,
+        collection: &str,
+        message: Value,
+        options: QueryOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "RealtimeController::publish",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if !message.is_object() {
+            return Err(Box::new(SdkError::new(
+                "RealtimeController::publish",
+                "message argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("realtime", "publish",
+            index: index,
+            collection: collection
+        );
+        for (key, value) in message.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => match res.result()["published"].as_bool() {
+                Some(true) => Ok(()),
+                _ => Err(Box::new(SdkError::new(
+                    "RealtimeController::publish",
+                    "the message was not published.",
+                ))),
+            },
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Subscribes to the documents of a collection matching the given
+    /// Koncorde filters, and returns the room id of the subscription.
+    /// `callback` is invoked with each notification received on the
+    /// subscription channel.
+    /// The subscription is registered on the Kuzzle client
+    /// (see `Kuzzle::subscriptions`).
+    ///
+    /// Notifications are pushed by Kuzzle on a persistent connection: the
+    /// protocol must support them (e.g. `Websocket`), subscribing over
+    /// `Http` fails without sending the request.
+    ///
+    /// The callbacks are invoked while the client reads from the connection,
+    /// i.e. while waiting for a response or in `Kuzzle::receive_notifications`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Websocket;
+    /// use kuzzle_sdk::types::{KuzzleOptions, SubscribeOptions};
+    /// use serde_json::json;
+    /// use std::time::Duration;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Websocket::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    /// kuzzle.connect().unwrap();
+    ///
+    /// let room_id = kuzzle.realtime().subscribe(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "equals": { "species": "crab" } }),
+    ///     Box::new(|notification| println!("{:?}", notification.result())),
+    ///     SubscribeOptions::new()
+    /// ).unwrap();
+    ///
+    /// loop {
+    ///     kuzzle.receive_notifications(Duration::from_secs(1)).unwrap();
+    /// }
+    /// ```
+    ///
+    pub fn subscribe(
+        &self,
+        index: &str,
+        collection: &str,
+        filters: Value,
+        callback: Box<dyn Fn(Notification)>,
+        options: SubscribeOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.query_options().skip_validation()
+        {
+            return Err(Box::new(SdkError::new(
+                "RealtimeController::subscribe",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if !filters.is_object() {
+            return Err(Box::new(SdkError::new(
+                "RealtimeController::subscribe",
+                "filters argument must be a JSON object.",
+            )));
+        }
+
+        let callback = if options.subscribe_to_self() {
+            callback
+        } else {
+            let instance_id = Value::from(self.kuzzle().sdk_instance_id());
+            Box::new(move |notification: Notification| {
+                if notification.volatile().get("sdkInstanceId") != Some(&instance_id) {
+                    callback(notification);
+                }
+            })
+        };
+
+        let req = subscribe_request(index, collection, &filters, &options);
+        let res = self.kuzzle().query(req, options.query_options().clone())?;
+        match &res.error() {
+            None => {
+                let (room_id, channel) = room_and_channel("RealtimeController::subscribe", &res)?;
+                self.kuzzle().add_subscription(
+                    SubscriptionInfo::new(room_id, channel, index, collection, filters),
+                    options,
+                    callback,
+                );
+                Ok(room_id.to_string())
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Same as `subscribe`, the notifications being sent to the returned
+    /// receiver, in the order they are received, rather than handed to a
+    /// callback. The receiver is disconnected once unsubscribed.
+    ///
+    /// As with `subscribe`, notifications are only received while the
+    /// client reads from the connection, e.g. in
+    /// `Kuzzle::receive_notifications`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Websocket;
+    /// use kuzzle_sdk::types::{KuzzleOptions, SubscribeOptions};
+    /// use serde_json::json;
+    /// use std::time::Duration;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Websocket::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    /// kuzzle.connect().unwrap();
+    ///
+    /// let (room_id, notifications) = kuzzle.realtime().subscribe_channel(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "equals": { "species": "crab" } }),
+    ///     SubscribeOptions::new()
+    /// ).unwrap();
+    ///
+    /// loop {
+    ///     kuzzle.receive_notifications(Duration::from_secs(1)).unwrap();
+    ///     for notification in notifications.try_iter() {
+    ///         println!("{:?}", notification.result());
+    ///     }
+    /// }
+    /// ```
+    ///
+    pub fn subscribe_channel(
+        &self,
+        index: &str,
+        collection: &str,
+        filters: Value,
+        options: SubscribeOptions,
+    ) -> Result<(String, Receiver<Notification>), Box<dyn Error>> {
+        let (sender, receiver) = channel();
+        let room_id = self.subscribe(
+            index,
+            collection,
+            filters,
+            Box::new(move |notification| {
+                // The receiver may be dropped before unsubscribing.
+                let _ = sender.send(notification);
+            }),
+            options,
+        )?;
+        Ok((room_id, receiver))
+    }
+
+    /// Removes a subscription, given its room id, and unregisters it
+    /// from the Kuzzle client. Fails if the client has no such subscription.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.realtime().unsubscribe("some-room-id", QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn unsubscribe(&self, room_id: &str, options: QueryOptions) -> Result<(), Box<dyn Error>> {
+        if room_id.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "RealtimeController::unsubscribe",
+                "room_id argument must not be empty.",
+            )));
+        }
+
+        let known = self
+            .kuzzle()
+            .subscriptions()
+            .iter()
+            .any(|subscription| subscription.room_id() == room_id);
+        if !known && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "RealtimeController::unsubscribe",
+                &format!("there is no subscription to the room `{}`.", room_id),
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("realtime", "unsubscribe",
+            body: { "roomId" => room_id }
+        );
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => {
+                self.kuzzle().remove_subscription(room_id);
+                Ok(())
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Checks whether the given filters are valid Koncorde filters, e.g.
+    /// before subscribing with them. Malformed filters are reported as
+    /// `Ok(false)`.
+    ///
+    /// Kuzzle has no HTTP route for this action: over `Http`, it fails
+    /// with an `SdkError` without sending the request.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Websocket;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Websocket::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    /// kuzzle.connect().unwrap();
+    ///
+    /// let valid = kuzzle.realtime().validate(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     json!({ "equals": { "species": "crab" } }),
+    ///     QueryOptions::new()
+    /// ).unwrap();
+    /// assert!(valid);
+    /// ```
+    ///
+    pub fn validate(
+        &self,
+        index: &str,
+        collection: &str,
+        filters: Value,
+        options: QueryOptions,
+    ) -> Result<bool, Box<dyn Error>> {
+        if (index.is_empty() || collection.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "RealtimeController::validate",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        if !filters.is_object() {
+            return Err(Box::new(SdkError::new(
+                "RealtimeController::validate",
+                "filters argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("realtime", "validate",
+            index: index,
+            collection: collection
+        );
+        for (key, value) in filters.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result()["valid"].as_bool().unwrap_or_default()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Subscribes again with the filters of a subscription, returning it
+    /// with its new room id and channel.
+    pub(crate) fn resubscribe(
+        &self,
+        subscription: &SubscriptionInfo,
+        options: &SubscribeOptions,
+    ) -> Result<SubscriptionInfo, Box<dyn Error>> {
+        let req = subscribe_request(
+            subscription.index(),
+            subscription.collection(),
+            subscription.filters(),
+            options,
+        );
+        let res = self.kuzzle().query(req, options.query_options().clone())?;
+        match &res.error() {
+            None => {
+                let (room_id, channel) = room_and_channel("RealtimeController::resubscribe", &res)?;
+                Ok(SubscriptionInfo::new(
+                    room_id,
+                    channel,
+                    subscription.index(),
+                    subscription.collection(),
+                    subscription.filters().clone(),
+                ))
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
     }
 
     fn kuzzle(&self) -> &'a Kuzzle {
-        &self.0
+        self.0
+    }
+}
+
+/// Builds the `realtime/subscribe` request, the filters being the body.
+fn subscribe_request(
+    index: &str,
+    collection: &str,
+    filters: &Value,
+    options: &SubscribeOptions,
+) -> KuzzleRequest {
+    let mut req: KuzzleRequest = kuzzle_request!("realtime", "subscribe",
+        index: index,
+        collection: collection
+    )
+    .add_to_query_strings("scope".to_string(), json!(options.scope()))
+    .add_to_query_strings("users".to_string(), json!(options.users()))
+    .add_to_query_strings("state".to_string(), json!(options.state()));
+    for (key, value) in filters.as_object().unwrap() {
+        req = req.add_to_body(key.to_string(), value.clone());
+    }
+    req
+}
+
+/// Reads the room id and channel of a `realtime/subscribe` response.
+fn room_and_channel<'r>(
+    cause: &str,
+    res: &'r KuzzleResponse,
+) -> Result<(&'r str, &'r str), SdkError> {
+    let field = |name: &str| {
+        res.result()[name].as_str().ok_or_else(|| {
+            SdkError::new(cause, &format!("response is missing the {} field.", name))
+        })
+    };
+    Ok((field("roomId")?, field("channel")?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::{Http, Protocol};
+    use crate::types::{
+        ConnectionState, KuzzleError, KuzzleOptions, QueueStats, Scope, State, Users,
+    };
+    use mockito::{self, Matcher};
+    use serde_json::from_value;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    type Listeners = Rc<RefCell<HashMap<String, Box<dyn Fn(Notification)>>>>;
+
+    /// A protocol accepting subscriptions, keeping the requests sent and
+    /// the notification callbacks.
+    struct FakeProtocol {
+        sent: Rc<RefCell<Vec<KuzzleRequest>>>,
+        listeners: Listeners,
+    }
+
+    impl Protocol for FakeProtocol {
+        fn once(&self) {}
+        fn listener_count(&self) {}
+        fn connect(&self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+        fn send(
+            &self,
+            req: KuzzleRequest,
+            _options: QueryOptions,
+        ) -> Result<KuzzleResponse, Box<dyn Error>> {
+            let result = match req.action().as_str() {
+                // Like Koncorde, only knows of the `equals` keyword.
+                "validate" if req.body().keys().all(|keyword| keyword == "equals") => {
+                    json!({ "valid": true })
+                }
+                "validate" => json!({ "valid": false, "details": "unknown keyword" }),
+                // A malformed response, without channel.
+                _ if req.collection() == &Some("lobsters".to_string()) => {
+                    json!({ "roomId": "lobster-room" })
+                }
+                _ => json!({ "roomId": "ferris-room", "channel": "ferris-channel" }),
+            };
+            let res = from_value(json!({
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "controller": req.controller(),
+                "action": req.action(),
+                "result": result
+            }))?;
+            self.sent.borrow_mut().push(req);
+            Ok(res)
+        }
+        fn supports_notifications(&self) -> bool {
+            true
+        }
+        fn listen(&self, channel: &str, callback: Box<dyn Fn(Notification)>) {
+            self.listeners
+                .borrow_mut()
+                .insert(channel.to_string(), callback);
+        }
+        fn unlisten(&self, channel: &str) {
+            self.listeners.borrow_mut().remove(channel);
+        }
+        fn cancel_request(&self, _request_id: &str) -> bool {
+            false
+        }
+        fn close(&self) {}
+        fn state(&self) -> ConnectionState {
+            ConnectionState::Connected
+        }
+        fn request_history(&self) {}
+        fn start_queuing(&self) {}
+        fn stop_queuing(&self) {}
+        fn clear_queue(&self) {}
+        fn queue_stats(&self) -> QueueStats {
+            QueueStats::default()
+        }
+    }
+
+    fn fake_kuzzle() -> (Kuzzle, Rc<RefCell<Vec<KuzzleRequest>>>, Listeners) {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let listeners: Listeners = Rc::new(RefCell::new(HashMap::new()));
+        let protocol = FakeProtocol {
+            sent: sent.clone(),
+            listeners: listeners.clone(),
+        };
+        (Kuzzle::new(protocol), sent, listeners)
+    }
+
+    #[test]
+    fn count_ok() {
+        let _m = mockito::mock("GET", "/_count/ferris-room")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "realtime",
+                    "action": "count",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "roomId": "ferris-room", "count": 3 }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.realtime().count("ferris-room", QueryOptions::new());
+
+        _m.assert();
+        assert_eq!(res.unwrap(), 3);
+    }
+
+    #[test]
+    fn count_fail_unknown_room() {
+        let _m = mockito::mock("GET", "/_count/lobster-room")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "The room Id \"lobster-room\" does not exist",
+                        "status": 404
+                    },
+                    "controller": "realtime",
+                    "action": "count",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .realtime()
+            .count("lobster-room", QueryOptions::new())
+            .unwrap_err();
+
+        _m.assert();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(404));
+    }
+
+    #[test]
+    fn list_ok_empty() {
+        let _m = mockito::mock("GET", "/_listSubscriptions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "realtime",
+                    "action": "list",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {}
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.realtime().list(QueryOptions::new());
+
+        _m.assert();
+        assert_eq!(res.unwrap(), json!({}));
+    }
+
+    #[test]
+    fn publish_ok() {
+        let _m = mockito::mock("POST", "/ferris_index/crabs/_publish")
+            .match_body(Matcher::Json(
+                json!({ "species": "crab", "status": "spotted" }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "realtime",
+                    "action": "publish",
+                    "collection": "crabs",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "published": true }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.realtime().publish(
+            "ferris_index",
+            "crabs",
+            json!({ "species": "crab", "status": "spotted" }),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn publish_fail_empty_index_or_collection() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+
+        for (index, collection) in &[("", "crabs"), ("ferris_index", "")] {
+            let err = k
+                .realtime()
+                .publish(index, collection, json!({}), QueryOptions::new())
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "[RealtimeController::publish] index and collection arguments must not be empty."
+            );
+        }
+    }
+
+    #[test]
+    fn subscribe_request_ok_filters_as_body() {
+        let filters = json!({
+            "and": [
+                { "equals": { "species": "crab" } },
+                { "range": { "legs": { "gte": 8 } } }
+            ]
+        });
+        let req = subscribe_request("ferris_index", "crabs", &filters, &SubscribeOptions::new());
+
+        assert_eq!(req.controller(), "realtime");
+        assert_eq!(req.action(), "subscribe");
+        assert_eq!(req.index(), &Some("ferris_index".to_string()));
+        assert_eq!(req.collection(), &Some("crabs".to_string()));
+        assert_eq!(json!(req.body()), filters);
+        assert_eq!(req.query_strings()["scope"], json!("all"));
+        assert_eq!(req.query_strings()["users"], json!("none"));
+        assert_eq!(req.query_strings()["state"], json!("done"));
+    }
+
+    #[test]
+    fn subscribe_request_ok_options() {
+        let options = SubscribeOptions::new()
+            .set_scope(Scope::Out)
+            .set_users(Users::All);
+        let req = subscribe_request("ferris_index", "crabs", &json!({}), &options);
+
+        assert_eq!(req.query_strings()["scope"], json!("out"));
+        assert_eq!(req.query_strings()["users"], json!("all"));
+        assert!(req.body().is_empty());
+    }
+
+    #[test]
+    fn subscribe_ok_sends_options() {
+        let (k, sent, _listeners) = fake_kuzzle();
+
+        k.realtime()
+            .subscribe(
+                "ferris_index",
+                "crabs",
+                json!({}),
+                Box::new(|_| {}),
+                SubscribeOptions::new()
+                    .set_scope(Scope::In)
+                    .set_users(Users::Out)
+                    .set_state(State::Pending),
+            )
+            .unwrap();
+
+        let req = &sent.borrow()[0];
+        assert_eq!(req.action(), "subscribe");
+        assert_eq!(req.query_strings()["scope"], json!("in"));
+        assert_eq!(req.query_strings()["users"], json!("out"));
+        assert_eq!(req.query_strings()["state"], json!("pending"));
+    }
+
+    #[test]
+    fn subscribe_ok_not_subscribed_to_self() {
+        let (k, sent, listeners) = fake_kuzzle();
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let sink = received.clone();
+
+        k.realtime()
+            .subscribe(
+                "ferris_index",
+                "crabs",
+                json!({}),
+                Box::new(move |notification| sink.borrow_mut().push(notification)),
+                SubscribeOptions::new().set_subscribe_to_self(false),
+            )
+            .unwrap();
+        assert_eq!(
+            sent.borrow()[0].volatile()["sdkInstanceId"],
+            json!(k.sdk_instance_id())
+        );
+
+        for instance_id in &[k.sdk_instance_id(), "another-client"] {
+            let notification: Notification = from_value(json!({
+                "type": "document",
+                "room": "ferris-channel",
+                "volatile": { "sdkInstanceId": instance_id },
+                "result": { "_id": "nemo" }
+            }))
+            .unwrap();
+            listeners.borrow()["ferris-channel"](notification);
+        }
+        assert_eq!(received.borrow().len(), 1);
+        assert_eq!(
+            received.borrow()[0].volatile()["sdkInstanceId"],
+            json!("another-client")
+        );
+    }
+
+    #[test]
+    fn unsubscribe_fail_unknown_room() {
+        let (k, sent, _) = fake_kuzzle();
+
+        let err = k
+            .realtime()
+            .unsubscribe("lobster-room", QueryOptions::new())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[RealtimeController::unsubscribe] there is no subscription to the room `lobster-room`."
+        );
+        assert!(sent.borrow().is_empty());
+    }
+
+    #[test]
+    fn subscribe_ok_invokes_callback() {
+        let (k, sent, listeners) = fake_kuzzle();
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let sink = received.clone();
+
+        let room_id = k
+            .realtime()
+            .subscribe(
+                "ferris_index",
+                "crabs",
+                json!({ "equals": { "species": "crab" } }),
+                Box::new(move |notification| sink.borrow_mut().push(notification)),
+                SubscribeOptions::new(),
+            )
+            .unwrap();
+        assert_eq!(room_id, "ferris-room");
+        assert_eq!(sent.borrow().len(), 1);
+
+        let notification: Notification = from_value(json!({
+            "type": "document",
+            "room": "ferris-channel",
+            "scope": "in",
+            "result": { "_id": "nemo", "_source": { "species": "crab" } }
+        }))
+        .unwrap();
+        listeners.borrow()["ferris-channel"](notification.clone());
+        assert_eq!(*received.borrow(), vec![notification]);
+
+        k.realtime()
+            .unsubscribe(&room_id, QueryOptions::new())
+            .unwrap();
+        assert!(listeners.borrow().is_empty());
+        assert_eq!(sent.borrow()[1].action(), "unsubscribe");
+        assert_eq!(sent.borrow()[1].body()["roomId"], json!("ferris-room"));
+    }
+
+    #[test]
+    fn subscribe_channel_ok_in_order() {
+        let (k, _, listeners) = fake_kuzzle();
+
+        let (room_id, notifications) = k
+            .realtime()
+            .subscribe_channel(
+                "ferris_index",
+                "crabs",
+                json!({ "equals": { "species": "crab" } }),
+                SubscribeOptions::new(),
+            )
+            .unwrap();
+        assert_eq!(room_id, "ferris-room");
+
+        for id in &["ferris", "nemo", "lobster"] {
+            let notification: Notification = from_value(json!({
+                "type": "document",
+                "room": "ferris-channel",
+                "result": { "_id": id }
+            }))
+            .unwrap();
+            listeners.borrow()["ferris-channel"](notification);
+        }
+        let ids: Vec<Value> = notifications
+            .try_iter()
+            .map(|notification| notification.result()["_id"].clone())
+            .collect();
+        assert_eq!(ids, vec![json!("ferris"), json!("nemo"), json!("lobster")]);
+
+        k.realtime()
+            .unsubscribe(&room_id, QueryOptions::new())
+            .unwrap();
+        assert!(notifications.recv().is_err());
+    }
+
+    #[test]
+    fn subscribe_fail_http_unsupported() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+
+        let err = k
+            .realtime()
+            .subscribe(
+                "ferris_index",
+                "crabs",
+                json!({ "equals": { "species": "crab" } }),
+                Box::new(|_| {}),
+                SubscribeOptions::new(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Kuzzle::query] subscriptions require the WebSocket protocol."
+        );
+        assert!(k.subscriptions().is_empty());
+    }
+
+    #[test]
+    fn subscribe_fail_missing_channel() {
+        let (k, _, listeners) = fake_kuzzle();
+
+        let err = k
+            .realtime()
+            .subscribe(
+                "ferris_index",
+                "lobsters",
+                json!({}),
+                Box::new(|_| {}),
+                SubscribeOptions::new(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SdkError>().unwrap().to_string(),
+            "[RealtimeController::subscribe] response is missing the channel field."
+        );
+        assert!(k.subscriptions().is_empty());
+        assert!(listeners.borrow().is_empty());
+    }
+
+    #[test]
+    fn validate_ok_valid_filters() {
+        let (k, sent, _) = fake_kuzzle();
+
+        let valid = k
+            .realtime()
+            .validate(
+                "ferris_index",
+                "crabs",
+                json!({ "equals": { "species": "crab" } }),
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        assert!(valid);
+        assert_eq!(sent.borrow()[0].action(), "validate");
+        assert_eq!(
+            json!(sent.borrow()[0].body()),
+            json!({ "equals": { "species": "crab" } })
+        );
+    }
+
+    #[test]
+    fn validate_ok_invalid_filters() {
+        let (k, _, _) = fake_kuzzle();
+
+        let valid = k
+            .realtime()
+            .validate(
+                "ferris_index",
+                "crabs",
+                json!({ "equalz": { "species": "crab" } }),
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        assert!(!valid);
+    }
+
+    #[test]
+    fn validate_fail_http_unsupported() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+
+        let err = k
+            .realtime()
+            .validate(
+                "ferris_index",
+                "crabs",
+                json!({ "equals": { "species": "crab" } }),
+                QueryOptions::new(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Kuzzle::query] realtime/validate requires the WebSocket protocol."
+        );
     }
 }
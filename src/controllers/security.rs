@@ -1,15 +1,3759 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{
+    KuzzleRequest, Policy, Profile, QueryOptions, Right, Role, SdkError, SearchResult, User,
+};
+use serde_json::{from_value, Value};
+use std::error::Error;
+use std::vec::IntoIter;
 
 pub struct SecurityController<'a>(pub &'a Kuzzle);
 
 impl<'a> SecurityController<'a> {
-    pub fn create_credentials(&self, options: QueryOptions) {
-        let req: KuzzleRequest = KuzzleRequest::new("security", "createCredentials");
-        self.kuzzle().query(req, options).is_ok();
+    /// Creates credentials of a user for the given strategy.
+    /// Returns the created credentials, without any secret.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.security().create_credentials(
+    ///     "local",
+    ///     "ferris",
+    ///     json!({ "username": "ferris", "password": "crab" }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn create_credentials(
+        &self,
+        strategy: &str,
+        user_id: &str,
+        credentials: Value,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        self.credentials_query(
+            "SecurityController::create_credentials",
+            "createCredentials",
+            strategy,
+            user_id,
+            Some(credentials),
+            options,
+        )
+    }
+
+    /// Creates the first administrator of a fresh Kuzzle, along with its
+    /// credentials. With `reset`, the rights of the `default` and
+    /// `anonymous` roles are revoked, so that only the administrator can
+    /// use the API. Kuzzle refuses to create it if an administrator exists.
+    /// Kuzzle generates the user id if `id` is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let admin = kuzzle.security().create_first_admin(
+    ///     Some("ferris"),
+    ///     json!({ "name": "Ferris" }),
+    ///     json!({ "local": { "username": "ferris", "password": "crab" } }),
+    ///     true
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn create_first_admin(
+        &self,
+        id: Option<&str>,
+        content: Value,
+        credentials: Value,
+        reset: bool,
+    ) -> Result<User, Box<dyn Error>> {
+        if id == Some("") {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::create_first_admin",
+                "id argument must not be empty.",
+            )));
+        }
+
+        if !content.is_object() || !credentials.is_object() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::create_first_admin",
+                "content and credentials arguments must be JSON objects.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("security", "createFirstAdmin",
+            body: {
+                "content" => content,
+                "credentials" => credentials
+            }
+        );
+        if let Some(id) = id {
+            req = req.set_id(id);
+        }
+        if reset {
+            req = req.add_to_query_strings("reset".to_string(), Value::from(true));
+        }
+
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(User::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Creates a profile out of its policies, each granting a role.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, Policy, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let profile = kuzzle.security().create_profile(
+    ///     "ferris_readers",
+    ///     vec![
+    ///         Policy::new("reader").set_restricted_to(vec![
+    ///             json!({ "index": "ferris_index", "collections": ["crabs"] }),
+    ///         ]),
+    ///     ],
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn create_profile(
+        &self,
+        id: &str,
+        policies: Vec<Policy>,
+        options: QueryOptions,
+    ) -> Result<Profile, Box<dyn Error>> {
+        self.write_profile(
+            "SecurityController::create_profile",
+            "createProfile",
+            id,
+            policies,
+            options,
+        )
+    }
+
+    /// Creates a user with the default restricted profiles, and its
+    /// credentials, e.g. for a sign-up page. Unlike `create_user`, it can be
+    /// allowed to anonymous users as `content` cannot set `profileIds`.
+    /// Kuzzle generates the user id if `id` is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let user = kuzzle.security().create_restricted_user(
+    ///     Some("ferris"),
+    ///     json!({ "name": "Ferris" }),
+    ///     json!({ "local": { "username": "ferris", "password": "crab" } }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn create_restricted_user(
+        &self,
+        id: Option<&str>,
+        content: Value,
+        credentials: Value,
+        options: QueryOptions,
+    ) -> Result<User, Box<dyn Error>> {
+        self.create_user_with(
+            "SecurityController::create_restricted_user",
+            "createRestrictedUser",
+            id,
+            content,
+            credentials,
+            options,
+        )
+    }
+
+    /// Creates a role allowing the given API actions, e.g.
+    /// `{ "document": { "actions": { "get": true } } }`.
+    /// Kuzzle rejects controllers it does not know, unless `force` is set,
+    /// e.g. to create a role for a plugin which is not installed yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let role = kuzzle.security().create_role(
+    ///     "reader",
+    ///     json!({ "document": { "actions": { "get": true, "search": true } } }),
+    ///     false,
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn create_role(
+        &self,
+        id: &str,
+        controllers: Value,
+        force: bool,
+        options: QueryOptions,
+    ) -> Result<Role, Box<dyn Error>> {
+        self.write_role(
+            "SecurityController::create_role",
+            "createRole",
+            id,
+            controllers,
+            force,
+            options,
+        )
+    }
+
+    /// Creates a user, `content` holding its `profileIds`, along with its
+    /// credentials for each authentication strategy.
+    /// Kuzzle generates the user id if `id` is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let user = kuzzle.security().create_user(
+    ///     Some("ferris"),
+    ///     json!({ "profileIds": ["default"], "name": "Ferris" }),
+    ///     json!({ "local": { "username": "ferris", "password": "crab" } }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn create_user(
+        &self,
+        id: Option<&str>,
+        content: Value,
+        credentials: Value,
+        options: QueryOptions,
+    ) -> Result<User, Box<dyn Error>> {
+        self.create_user_with(
+            "SecurityController::create_user",
+            "createUser",
+            id,
+            content,
+            credentials,
+            options,
+        )
+    }
+
+    /// Deletes credentials of a user for the given strategy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.security().delete_credentials("local", "ferris", QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn delete_credentials(
+        &self,
+        strategy: &str,
+        user_id: &str,
+        options: QueryOptions,
+    ) -> Result<bool, Box<dyn Error>> {
+        let result = self.credentials_query(
+            "SecurityController::delete_credentials",
+            "deleteCredentials",
+            strategy,
+            user_id,
+            None,
+            options,
+        )?;
+        Ok(result["acknowledged"].as_bool().unwrap())
+    }
+
+    /// Deletes a profile and returns its id. Kuzzle refuses to delete a
+    /// profile still assigned to users.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let id = kuzzle.security().delete_profile("ferris_readers", QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn delete_profile(
+        &self,
+        id: &str,
+        options: QueryOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        if id.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::delete_profile",
+                "id argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "deleteProfile", id: id);
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result()["_id"].as_str().unwrap().to_string()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Deletes a role and returns its id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let id = kuzzle.security().delete_role("reader", QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn delete_role(&self, id: &str, options: QueryOptions) -> Result<String, Box<dyn Error>> {
+        if id.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::delete_role",
+                "id argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "deleteRole", id: id);
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result()["_id"].as_str().unwrap().to_string()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Deletes a user, along with its credentials, and returns its id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let id = kuzzle.security().delete_user("ferris", QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn delete_user(&self, id: &str, options: QueryOptions) -> Result<String, Box<dyn Error>> {
+        if id.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::delete_user",
+                "id argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "deleteUser", id: id);
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result()["_id"].as_str().unwrap().to_string()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Returns credentials of a user for the given strategy, without any
+    /// secret.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.security().get_credentials("local", "ferris");
+    ///
+    /// ```
+    ///
+    pub fn get_credentials(&self, strategy: &str, user_id: &str) -> Result<Value, Box<dyn Error>> {
+        self.credentials_query(
+            "SecurityController::get_credentials",
+            "getCredentials",
+            strategy,
+            user_id,
+            None,
+            QueryOptions::new(),
+        )
+    }
+
+    /// Gets a profile, along with its policies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let profile = kuzzle.security().get_profile("ferris_readers");
+    ///
+    /// ```
+    ///
+    pub fn get_profile(&self, id: &str) -> Result<Profile, Box<dyn Error>> {
+        if id.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::get_profile",
+                "id argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "getProfile", id: id);
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(Profile::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets the rights granted to a profile, as the API actions allowed or
+    /// denied on each index and collection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let rights = kuzzle.security().get_profile_rights("ferris_readers");
+    ///
+    /// ```
+    ///
+    pub fn get_profile_rights(&self, id: &str) -> Result<Vec<Right>, Box<dyn Error>> {
+        self.get_rights(
+            "SecurityController::get_profile_rights",
+            "getProfileRights",
+            id,
+        )
+    }
+
+    /// Gets a role, along with the API actions it allows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let role = kuzzle.security().get_role("reader");
+    ///
+    /// ```
+    ///
+    pub fn get_role(&self, id: &str) -> Result<Role, Box<dyn Error>> {
+        if id.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::get_role",
+                "id argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "getRole", id: id);
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(Role::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets a user, along with the ids of its profiles.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let user = kuzzle.security().get_user("ferris");
+    ///
+    /// ```
+    ///
+    pub fn get_user(&self, id: &str) -> Result<User, Box<dyn Error>> {
+        if id.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::get_user",
+                "id argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "getUser", id: id);
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(User::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets the rights granted to a user, as the API actions allowed or
+    /// denied on each index and collection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let rights = kuzzle.security().get_user_rights("ferris");
+    ///
+    /// ```
+    ///
+    pub fn get_user_rights(&self, id: &str) -> Result<Vec<Right>, Box<dyn Error>> {
+        self.get_rights("SecurityController::get_user_rights", "getUserRights", id)
+    }
+
+    /// Checks whether a user has credentials for the given strategy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.security().has_credentials("local", "ferris");
+    ///
+    /// ```
+    ///
+    pub fn has_credentials(&self, strategy: &str, user_id: &str) -> Result<bool, Box<dyn Error>> {
+        let result = self.credentials_query(
+            "SecurityController::has_credentials",
+            "hasCredentials",
+            strategy,
+            user_id,
+            None,
+            QueryOptions::new(),
+        )?;
+        Ok(result.as_bool().unwrap())
+    }
+
+    /// Deletes multiple users at once.
+    /// Returns a tuple containing the ids of the deleted users
+    /// and the list of per-user failures reported by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.security().m_delete_users(
+    ///     vec!["crab".to_string(), "lobster".to_string()],
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_delete_users(
+        &self,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<(Vec<String>, Vec<Value>), Box<dyn Error>> {
+        if ids.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::m_delete_users",
+                "ids argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "mDeleteUsers",
+            body: { "ids" => ids }
+        );
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => {
+                let deleted: Vec<String> = from_value(res.result()["successes"].clone())?;
+                let errors: Vec<Value> = from_value(res.result()["errors"].clone())?;
+                Ok((deleted, errors))
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets multiple profiles at once. Unknown ids are skipped, so that
+    /// fewer profiles than requested may be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let profiles = kuzzle.security().m_get_profiles(
+    ///     vec!["ferris_readers".to_string(), "ferris_writers".to_string()],
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_get_profiles(&self, ids: Vec<String>) -> Result<Vec<Profile>, Box<dyn Error>> {
+        if ids.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::m_get_profiles",
+                "ids argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "mGetProfiles",
+            body: { "ids" => ids }
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(match res.result()["hits"].as_array() {
+                Some(hits) => hits.iter().map(Profile::from_result).collect(),
+                None => Vec::new(),
+            }),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets multiple roles at once. Unknown ids are skipped, so that
+    /// fewer roles than requested may be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let roles = kuzzle.security().m_get_roles(
+    ///     vec!["reader".to_string(), "writer".to_string()],
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_get_roles(&self, ids: Vec<String>) -> Result<Vec<Role>, Box<dyn Error>> {
+        if ids.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::m_get_roles",
+                "ids argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "mGetRoles",
+            body: { "ids" => ids }
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(match res.result()["hits"].as_array() {
+                Some(hits) => hits.iter().map(Role::from_result).collect(),
+                None => Vec::new(),
+            }),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Gets multiple users at once. Unknown ids are skipped, so that
+    /// fewer users than requested may be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let users = kuzzle.security().m_get_users(
+    ///     vec!["ferris".to_string(), "crab".to_string()],
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn m_get_users(&self, ids: Vec<String>) -> Result<Vec<User>, Box<dyn Error>> {
+        if ids.is_empty() {
+            return Err(Box::new(SdkError::new(
+                "SecurityController::m_get_users",
+                "ids argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", "mGetUsers",
+            body: { "ids" => ids }
+        );
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(match res.result()["hits"].as_array() {
+                Some(hits) => hits.iter().map(User::from_result).collect(),
+                None => Vec::new(),
+            }),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Replaces the whole content of a user, which must hold its
+    /// `profileIds`, and returns the user as stored by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let user = kuzzle.security().replace_user(
+    ///     "ferris",
+    ///     json!({ "name": "Ferris", "profileIds": ["default"] }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn replace_user(
+        &self,
+        id: &str,
+        content: Value,
+        options: QueryOptions,
+    ) -> Result<User, Box<dyn Error>> {
+        self.write_user(
+            "SecurityController::replace_user",
+            "replaceUser",
+            id,
+            content,
+            options,
+        )
+    }
+
+    /// Fetches the next page of a profile search started with
+    /// `search_profiles` and a `scroll` duration, from the scroll id of the
+    /// previous page, keeping the cursor alive for `ttl`. The returned page
+    /// carries the scroll id of the page after it, and holds no hit once
+    /// every profile was read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// if let Ok(page) = kuzzle.security().search_profiles(
+    ///     None,
+    ///     QueryOptions::new().set_size(100).set_scroll("30s"),
+    /// ) {
+    ///     if let Some(scroll_id) = page.scroll_id() {
+    ///         let next_page = kuzzle.security().scroll_profiles(scroll_id, "30s");
+    ///     }
+    /// }
+    ///
+    /// ```
+    ///
+    pub fn scroll_profiles(
+        &self,
+        scroll_id: &str,
+        ttl: &str,
+    ) -> Result<SearchResult, Box<dyn Error>> {
+        self.scroll_page(
+            "SecurityController::scroll_profiles",
+            "scrollProfiles",
+            scroll_id,
+            ttl,
+        )
+    }
+
+    /// Fetches the next page of a user search started with `search_users`,
+    /// from the scroll id of the previous page (see `UserSearch::scroll_id`),
+    /// keeping the cursor alive for `ttl`. It is meant for scrolling by hand,
+    /// instead of iterating over the `UserSearch`. The returned page carries
+    /// the scroll id of the page after it, and holds no hit once every user
+    /// was read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// if let Ok(users) = kuzzle.security().search_users(
+    ///     None,
+    ///     QueryOptions::new().set_size(100).set_scroll("30s"),
+    /// ) {
+    ///     if let Some(scroll_id) = users.scroll_id() {
+    ///         let next_page = kuzzle.security().scroll_users(scroll_id, "30s");
+    ///     }
+    /// }
+    ///
+    /// ```
+    ///
+    pub fn scroll_users(&self, scroll_id: &str, ttl: &str) -> Result<SearchResult, Box<dyn Error>> {
+        self.scroll_page(
+            "SecurityController::scroll_users",
+            "scrollUsers",
+            scroll_id,
+            ttl,
+        )
+    }
+
+    /// Searches profiles, all of them if `query` is `None`. Profiles can be
+    /// filtered on the roles they grant, e.g. `{ "roles": ["reader"] }`.
+    /// Pages are selected with `options.set_from` and `options.set_size`,
+    /// or scrolled through with `options.set_scroll` and `scroll_profiles`.
+    /// The hits of the returned page convert into `Profile`s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, Profile, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// if let Ok(page) = kuzzle.security().search_profiles(
+    ///     Some(json!({ "roles": ["reader"] })),
+    ///     QueryOptions::new().set_size(10),
+    /// ) {
+    ///     let profiles: Vec<Profile> = page.hits().iter().map(Profile::from).collect();
+    /// }
+    ///
+    /// ```
+    ///
+    pub fn search_profiles(
+        &self,
+        query: Option<Value>,
+        options: QueryOptions,
+    ) -> Result<SearchResult, Box<dyn Error>> {
+        let mut req: KuzzleRequest = kuzzle_request!("security", "searchProfiles");
+        if let Some(query) = query {
+            if !query.is_object() {
+                return Err(Box::new(SdkError::new(
+                    "SecurityController::search_profiles",
+                    "query argument must be a JSON object.",
+                )));
+            }
+
+            for (key, value) in query.as_object().unwrap() {
+                req = req.add_to_body(key.to_string(), value.clone());
+            }
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result_as()?),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Searches roles, all of them if `query` is `None`. Roles can be
+    /// filtered on the controllers they give access to, e.g.
+    /// `{ "controllers": ["document"] }`. Pages are selected with
+    /// `options.set_from` and `options.set_size`.
+    /// Returns a tuple containing the roles of the page and the number of
+    /// roles matched by the search.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.security().search_roles(
+    ///     Some(json!({ "controllers": ["document"] })),
+    ///     QueryOptions::new().set_size(10),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn search_roles(
+        &self,
+        query: Option<Value>,
+        options: QueryOptions,
+    ) -> Result<(Vec<Role>, u64), Box<dyn Error>> {
+        let mut req: KuzzleRequest = kuzzle_request!("security", "searchRoles");
+        if let Some(query) = query {
+            if !query.is_object() {
+                return Err(Box::new(SdkError::new(
+                    "SecurityController::search_roles",
+                    "query argument must be a JSON object.",
+                )));
+            }
+
+            for (key, value) in query.as_object().unwrap() {
+                req = req.add_to_body(key.to_string(), value.clone());
+            }
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => {
+                let roles: Vec<Role> = match res.result()["hits"].as_array() {
+                    Some(hits) => hits.iter().map(Role::from_result).collect(),
+                    None => Vec::new(),
+                };
+                let total = res.result()["total"].as_u64().unwrap_or(roles.len() as u64);
+                Ok((roles, total))
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Searches users matching the given query (all users if `None`) and
+    /// returns an iterator over every one of them. Pages are fetched with
+    /// `security/scrollUsers` as the iteration goes: their size is taken from
+    /// `options.set_size`, and the scroll cursor lives for `options.set_scroll`
+    /// (30 seconds by default) between two pages.
+    ///
+    /// If the cursor expires before all users were read, the iterator yields
+    /// an `SdkError` and stops.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// if let Ok(users) = kuzzle.security().search_users(
+    ///     Some(json!({ "query": { "term": { "status": "active" } } })),
+    ///     QueryOptions::new().set_size(100).set_scroll("1m"),
+    /// ) {
+    ///     for user in users {
+    ///         println!("{:?}", user);
+    ///     }
+    /// }
+    ///
+    /// ```
+    ///
+    pub fn search_users(
+        &self,
+        query: Option<Value>,
+        options: QueryOptions,
+    ) -> Result<UserSearch<'a>, Box<dyn Error>> {
+        let mut req: KuzzleRequest = kuzzle_request!("security", "searchUsers");
+        if let Some(query) = query {
+            if !query.is_object() {
+                return Err(Box::new(SdkError::new(
+                    "SecurityController::search_users",
+                    "query argument must be a JSON object.",
+                )));
+            }
+
+            for (key, value) in query.as_object().unwrap() {
+                req = req.add_to_body(key.to_string(), value.clone());
+            }
+        }
+
+        let scroll = options
+            .scroll()
+            .clone()
+            .unwrap_or_else(|| "30s".to_string());
+        let res = self.kuzzle().query(req, options.set_scroll(&scroll))?;
+        match &res.error() {
+            None => {
+                let mut users = UserSearch {
+                    kuzzle: self.kuzzle(),
+                    scroll,
+                    scroll_id: None,
+                    total: 0,
+                    fetched: 0,
+                    page: Vec::new().into_iter(),
+                    failed: false,
+                };
+                users.read_page(res.result());
+                Ok(users)
+            }
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Updates credentials of a user for the given strategy.
+    /// Returns the updated credentials, without any secret.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.security().update_credentials(
+    ///     "local",
+    ///     "ferris",
+    ///     json!({ "password": "lobster" }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_credentials(
+        &self,
+        strategy: &str,
+        user_id: &str,
+        credentials: Value,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        self.credentials_query(
+            "SecurityController::update_credentials",
+            "updateCredentials",
+            strategy,
+            user_id,
+            Some(credentials),
+            options,
+        )
+    }
+
+    /// Replaces the policies of a profile and returns the updated profile.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, Policy, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let profile = kuzzle.security().update_profile(
+    ///     "ferris_readers",
+    ///     vec![Policy::new("reader"), Policy::new("notifier")],
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_profile(
+        &self,
+        id: &str,
+        policies: Vec<Policy>,
+        options: QueryOptions,
+    ) -> Result<Profile, Box<dyn Error>> {
+        self.write_profile(
+            "SecurityController::update_profile",
+            "updateProfile",
+            id,
+            policies,
+            options,
+        )
+    }
+
+    /// Adds fields to the profile mapping, e.g. a custom `costCenter` tag to
+    /// search profiles on, and returns the mapping as applied by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let mapping = kuzzle.security().update_profile_mapping(
+    ///     json!({ "costCenter": { "type": "keyword" } }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_profile_mapping(
+        &self,
+        mapping: Value,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        self.update_mapping(
+            "SecurityController::update_profile_mapping",
+            "updateProfileMapping",
+            mapping,
+            options,
+        )
+    }
+
+    /// Updates the API actions allowed by a role, the controllers not given
+    /// being left as is, and returns the updated role.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let role = kuzzle.security().update_role(
+    ///     "reader",
+    ///     json!({ "collection": { "actions": { "list": true } } }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_role(
+        &self,
+        id: &str,
+        controllers: Value,
+        options: QueryOptions,
+    ) -> Result<Role, Box<dyn Error>> {
+        self.write_role(
+            "SecurityController::update_role",
+            "updateRole",
+            id,
+            controllers,
+            false,
+            options,
+        )
+    }
+
+    /// Adds fields to the role mapping, e.g. a custom `costCenter` tag to
+    /// search roles on, and returns the mapping as applied by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let mapping = kuzzle.security().update_role_mapping(
+    ///     json!({ "costCenter": { "type": "keyword" } }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_role_mapping(
+        &self,
+        mapping: Value,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        self.update_mapping(
+            "SecurityController::update_role_mapping",
+            "updateRoleMapping",
+            mapping,
+            options,
+        )
+    }
+
+    /// Adds fields to the user mapping, e.g. a custom `costCenter` tag to
+    /// search users on, and returns the mapping as applied by Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let mapping = kuzzle.security().update_user_mapping(
+    ///     json!({ "costCenter": { "type": "keyword" } }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_user_mapping(
+        &self,
+        mapping: Value,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        self.update_mapping(
+            "SecurityController::update_user_mapping",
+            "updateUserMapping",
+            mapping,
+            options,
+        )
+    }
+
+    /// Updates some fields of a user content, the others being left as is,
+    /// and returns the updated user.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let user = kuzzle.security().update_user(
+    ///     "ferris",
+    ///     json!({ "status": "active" }),
+    ///     QueryOptions::new()
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_user(
+        &self,
+        id: &str,
+        content: Value,
+        options: QueryOptions,
+    ) -> Result<User, Box<dyn Error>> {
+        self.write_user(
+            "SecurityController::update_user",
+            "updateUser",
+            id,
+            content,
+            options,
+        )
+    }
+
+    /// Checks that the given credentials would be accepted for a user and
+    /// the given strategy, without storing them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.security().validate_credentials(
+    ///     "local",
+    ///     "ferris",
+    ///     json!({ "username": "ferris", "password": "crab" }),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn validate_credentials(
+        &self,
+        strategy: &str,
+        user_id: &str,
+        credentials: Value,
+    ) -> Result<bool, Box<dyn Error>> {
+        let result = self.credentials_query(
+            "SecurityController::validate_credentials",
+            "validateCredentials",
+            strategy,
+            user_id,
+            Some(credentials),
+            QueryOptions::new(),
+        )?;
+        Ok(result.as_bool().unwrap())
+    }
+
+    /// Sends one of the `*Credentials` actions, the strategy being part of
+    /// the query and the credentials, if any, the body.
+    fn credentials_query(
+        &self,
+        cause: &str,
+        action: &str,
+        strategy: &str,
+        user_id: &str,
+        credentials: Option<Value>,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        if (strategy.is_empty() || user_id.is_empty()) && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "strategy and user_id arguments must not be empty.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("security", action,
+            id: user_id,
+            query: { "strategy" => strategy }
+        );
+        if let Some(credentials) = credentials {
+            if !credentials.is_object() {
+                return Err(Box::new(SdkError::new(
+                    cause,
+                    "credentials argument must be a JSON object.",
+                )));
+            }
+            for (key, value) in credentials.as_object().unwrap() {
+                req = req.add_to_body(key.to_string(), value.clone());
+            }
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result().clone()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sends `createUser` or `createRestrictedUser`, the body nesting the
+    /// user `content` and its `credentials`.
+    fn create_user_with(
+        &self,
+        cause: &str,
+        action: &str,
+        id: Option<&str>,
+        content: Value,
+        credentials: Value,
+        options: QueryOptions,
+    ) -> Result<User, Box<dyn Error>> {
+        if id == Some("") && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "id argument must not be empty.",
+            )));
+        }
+
+        if !content.is_object() || !credentials.is_object() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "content and credentials arguments must be JSON objects.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("security", action,
+            body: {
+                "content" => content,
+                "credentials" => credentials
+            }
+        );
+        if let Some(id) = id {
+            req = req.set_id(id);
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(User::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sends `createProfile` or `updateProfile`, the body holding the
+    /// profile `policies`.
+    fn write_profile(
+        &self,
+        cause: &str,
+        action: &str,
+        id: &str,
+        policies: Vec<Policy>,
+        options: QueryOptions,
+    ) -> Result<Profile, Box<dyn Error>> {
+        if id.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "id argument must not be empty.",
+            )));
+        }
+
+        if policies.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "policies argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", action,
+            id: id,
+            body: { "policies" => policies }
+        );
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(Profile::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sends `createRole` or `updateRole`, the body holding the role
+    /// `controllers`.
+    fn write_role(
+        &self,
+        cause: &str,
+        action: &str,
+        id: &str,
+        controllers: Value,
+        force: bool,
+        options: QueryOptions,
+    ) -> Result<Role, Box<dyn Error>> {
+        if id.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "id argument must not be empty.",
+            )));
+        }
+
+        if !controllers.is_object() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "controllers argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("security", action,
+            id: id,
+            body: { "controllers" => controllers }
+        );
+        if force {
+            req = req.add_to_query_strings("force".to_string(), Value::from(true));
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(Role::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sends `updateUser` or `replaceUser`, the body being the user content.
+    fn write_user(
+        &self,
+        cause: &str,
+        action: &str,
+        id: &str,
+        content: Value,
+        options: QueryOptions,
+    ) -> Result<User, Box<dyn Error>> {
+        if id.is_empty() && !options.skip_validation() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "id argument must not be empty.",
+            )));
+        }
+
+        if !content.is_object() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "content argument must be a JSON object.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = kuzzle_request!("security", action, id: id);
+        for (key, value) in content.as_object().unwrap() {
+            req = req.add_to_body(key.to_string(), value.clone());
+        }
+
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(User::from_result(res.result())),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sends `getUserRights` or `getProfileRights`, the rights being the
+    /// `hits` of the result.
+    fn get_rights(
+        &self,
+        cause: &str,
+        action: &str,
+        id: &str,
+    ) -> Result<Vec<Right>, Box<dyn Error>> {
+        if id.is_empty() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "id argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", action, id: id);
+        let res = self.kuzzle().query(req, QueryOptions::new())?;
+        match &res.error() {
+            None => Ok(from_value(res.result()["hits"].clone())?),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sends `scrollUsers` or `scrollProfiles`, the scroll id being part of
+    /// the query.
+    fn scroll_page(
+        &self,
+        cause: &str,
+        action: &str,
+        scroll_id: &str,
+        ttl: &str,
+    ) -> Result<SearchResult, Box<dyn Error>> {
+        if scroll_id.is_empty() || ttl.is_empty() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "scroll_id and ttl arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", action,
+            query: { "scrollId" => scroll_id }
+        );
+        let res = self
+            .kuzzle()
+            .query(req, QueryOptions::new().set_scroll(ttl))?;
+        match &res.error() {
+            None => Ok(res.result_as()?),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+
+    /// Sends one of the `update*Mapping` actions, the new fields being
+    /// the `properties` of the body.
+    fn update_mapping(
+        &self,
+        cause: &str,
+        action: &str,
+        mapping: Value,
+        options: QueryOptions,
+    ) -> Result<Value, Box<dyn Error>> {
+        if !mapping.is_object() {
+            return Err(Box::new(SdkError::new(
+                cause,
+                "mapping argument must be a JSON object.",
+            )));
+        }
+
+        let req: KuzzleRequest = kuzzle_request!("security", action,
+            body: { "properties" => mapping }
+        );
+        let res = self.kuzzle().query(req, options)?;
+        match &res.error() {
+            None => Ok(res.result().clone()),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
     }
 
     fn kuzzle(&self) -> &'a Kuzzle {
-        &self.0
+        self.0
+    }
+}
+
+/// An iterator over the users matched by `SecurityController::search_users`,
+/// scrolling through the result pages as it goes.
+pub struct UserSearch<'a> {
+    kuzzle: &'a Kuzzle,
+    scroll: String,
+    scroll_id: Option<String>,
+    total: u64,
+    fetched: u64,
+    page: IntoIter<User>,
+    failed: bool,
+}
+
+impl<'a> UserSearch<'a> {
+    /// Number of users matched by the search.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Scroll id of the next page, if any, for `SecurityController::scroll_users`.
+    /// It follows the pages fetched while iterating.
+    pub fn scroll_id(&self) -> &Option<String> {
+        &self.scroll_id
+    }
+
+    fn read_page(&mut self, result: &Value) {
+        let users: Vec<User> = match result["hits"].as_array() {
+            Some(hits) => hits.iter().map(User::from_result).collect(),
+            None => Vec::new(),
+        };
+        self.fetched += users.len() as u64;
+        self.total = result["total"].as_u64().unwrap_or(self.fetched);
+        self.scroll_id = result["scrollId"].as_str().map(String::from);
+        self.page = users.into_iter();
+    }
+
+    /// Fetches the next page of users, returning whether it holds any.
+    fn next_page(&mut self) -> Result<bool, Box<dyn Error>> {
+        let scroll_id = match &self.scroll_id {
+            Some(scroll_id) if self.fetched < self.total => scroll_id.clone(),
+            _ => return Ok(false),
+        };
+
+        let req: KuzzleRequest = kuzzle_request!("security", "scrollUsers",
+            query: { "scrollId" => scroll_id }
+        );
+        let res = self
+            .kuzzle
+            .query(req, QueryOptions::new().set_scroll(&self.scroll))?;
+        match &res.error() {
+            None => {
+                let fetched = self.fetched;
+                self.read_page(res.result());
+                Ok(self.fetched > fetched)
+            }
+            Some(k_err) if k_err.status() == Some(404) => Err(Box::new(SdkError::new(
+                "SecurityController::search_users",
+                &format!(
+                    "the scroll cursor expired after {} of {} users were read, a longer `scroll` duration is needed.",
+                    self.fetched, self.total
+                ),
+            ))),
+            Some(k_err) => Err(Box::new(k_err.clone())),
+        }
+    }
+}
+
+impl<'a> Iterator for UserSearch<'a> {
+    type Item = Result<User, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        if let Some(user) = self.page.next() {
+            return Some(Ok(user));
+        }
+
+        match self.next_page() {
+            Ok(true) => self.page.next().map(Ok),
+            Ok(false) => None,
+            Err(err) => {
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::{KuzzleError, KuzzleOptions, RightValue};
+    use mockito::{self, Matcher};
+    use serde_json::json;
+
+    const SEARCH_USERS_RESPONSE: &str = r#"{
+        "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+        "status": 200,
+        "error": null,
+        "controller": "security",
+        "action": "searchUsers",
+        "collection": null,
+        "index": null,
+        "volatile": null,
+        "result": {
+            "total": 3,
+            "scrollId": "ferris-scroll",
+            "hits": [
+                { "_id": "ferris", "_source": { "name": "Ferris", "profileIds": ["admin"] } },
+                { "_id": "crab", "_source": { "name": "Crab", "profileIds": ["default"] } }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn create_profile_ok() {
+        let _m = mockito::mock("POST", "/profiles/ferris_readers/_create")
+            .match_body(Matcher::Json(json!({
+                "policies": [
+                    {
+                        "roleId": "reader",
+                        "restrictedTo": [{ "index": "ferris_index", "collections": ["crabs"] }]
+                    },
+                    { "roleId": "notifier" }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "createProfile",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "_id": "ferris_readers",
+                    "_source": {
+                        "policies": [
+                            {
+                                "roleId": "reader",
+                                "restrictedTo": [{ "index": "ferris_index", "collections": ["crabs"] }]
+                            },
+                            { "roleId": "notifier" }
+                        ]
+                    }
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let profile = k
+            .security()
+            .create_profile(
+                "ferris_readers",
+                vec![
+                    Policy::new("reader").set_restricted_to(vec![
+                        json!({ "index": "ferris_index", "collections": ["crabs"] }),
+                    ]),
+                    Policy::new("notifier"),
+                ],
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(profile.id(), "ferris_readers");
+        assert_eq!(profile.policies().len(), 2);
+        assert_eq!(profile.policies()[0].role_id(), "reader");
+        assert_eq!(
+            profile.policies()[0].restricted_to(),
+            &Some(vec![
+                json!({ "index": "ferris_index", "collections": ["crabs"] })
+            ])
+        );
+        assert_eq!(profile.policies()[1].restricted_to(), &None);
+    }
+
+    #[test]
+    fn create_profile_fail_no_policy() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .security()
+            .create_profile("ferris_readers", vec![], QueryOptions::new())
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "[SecurityController::create_profile] policies argument must not be empty."
+        );
+    }
+
+    #[test]
+    fn search_profiles_ok_role_filter() {
+        let _m = mockito::mock("POST", "/profiles/_search")
+            .match_body(Matcher::Json(json!({ "roles": ["reader"] })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "searchProfiles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "total": 2,
+                    "hits": [
+                        { "_id": "ferris_readers", "_source": { "policies": [{ "roleId": "reader" }] } },
+                        { "_id": "admin", "_source": { "policies": [{ "roleId": "reader" }, { "roleId": "admin" }] } }
+                    ]
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let page = k
+            .security()
+            .search_profiles(Some(json!({ "roles": ["reader"] })), QueryOptions::new())
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(page.total(), 2);
+        let profiles: Vec<Profile> = page.hits().iter().map(Profile::from).collect();
+        let ids: Vec<&String> = profiles.iter().map(|profile| profile.id()).collect();
+        assert_eq!(ids, vec!["ferris_readers", "admin"]);
+        assert_eq!(profiles[1].policies()[1].role_id(), "admin");
+    }
+
+    #[test]
+    fn get_profile_ok() {
+        let _m = mockito::mock("GET", "/profiles/ferris_readers")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "getProfile",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "_id": "ferris_readers",
+                    "_source": { "policies": [{ "roleId": "reader" }] }
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let profile = k.security().get_profile("ferris_readers").unwrap();
+
+        _m.assert();
+        assert_eq!(profile.policies(), &vec![Policy::new("reader")]);
+    }
+
+    #[test]
+    fn update_profile_ok() {
+        let _m = mockito::mock("PUT", "/profiles/ferris_readers/_update")
+            .match_body(Matcher::Json(json!({
+                "policies": [{ "roleId": "reader" }, { "roleId": "notifier" }]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "updateProfile",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "_id": "ferris_readers",
+                    "_source": { "policies": [{ "roleId": "reader" }, { "roleId": "notifier" }] }
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let profile = k
+            .security()
+            .update_profile(
+                "ferris_readers",
+                vec![Policy::new("reader"), Policy::new("notifier")],
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(profile.policies().len(), 2);
+    }
+
+    #[test]
+    fn m_get_profiles_ok() {
+        let _m = mockito::mock("POST", "/profiles/_mGet")
+            .match_body(Matcher::Json(json!({ "ids": ["ferris_readers"] })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "mGetProfiles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "hits": [
+                        { "_id": "ferris_readers", "_source": { "policies": [{ "roleId": "reader" }] } }
+                    ]
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let profiles = k
+            .security()
+            .m_get_profiles(vec!["ferris_readers".to_string()])
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].id(), "ferris_readers");
+    }
+
+    #[test]
+    fn delete_profile_ok() {
+        let _m = mockito::mock("DELETE", "/profiles/ferris_readers")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "deleteProfile",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "_id": "ferris_readers" }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let id = k
+            .security()
+            .delete_profile("ferris_readers", QueryOptions::new())
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(id, "ferris_readers");
+    }
+
+    #[test]
+    fn delete_profile_fail_in_use() {
+        let _m = mockito::mock("DELETE", "/profiles/admin")
+            .with_status(412)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 412,
+                    "error": {
+                    "message": "The profile \"admin\" cannot be deleted since it is used by some users.",
+                    "status": 412,
+                    "id": "security.profile.in_use"
+                },
+                    "controller": "security",
+                    "action": "deleteProfile",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .security()
+            .delete_profile("admin", QueryOptions::new())
+            .unwrap_err();
+
+        _m.assert();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(412));
+        assert_eq!(k_err.id(), &Some("security.profile.in_use".to_string()));
+    }
+
+    #[test]
+    fn create_role_ok_forced_unknown_controller() {
+        let _m = mockito::mock("POST", "/roles/nemo_reader/_create?force=true")
+            .match_body(Matcher::Json(json!({
+                "controllers": { "nemo-plugin/fish": { "actions": { "swim": true } } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "createRole",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "_id": "nemo_reader",
+                    "_source": {
+                        "controllers": { "nemo-plugin/fish": { "actions": { "swim": true } } }
+                    }
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let role = k
+            .security()
+            .create_role(
+                "nemo_reader",
+                json!({ "nemo-plugin/fish": { "actions": { "swim": true } } }),
+                true,
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(role.id(), "nemo_reader");
+        assert_eq!(
+            role.controllers()["nemo-plugin/fish"],
+            json!({ "actions": { "swim": true } })
+        );
+    }
+
+    #[test]
+    fn create_role_fail_unknown_controller() {
+        let _m = mockito::mock("POST", "/roles/nemo_reader/_create")
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 400,
+                    "error": {
+                    "message": "Unknown controller \"nemo-plugin/fish\"",
+                    "status": 400
+                },
+                    "controller": "security",
+                    "action": "createRole",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .security()
+            .create_role(
+                "nemo_reader",
+                json!({ "nemo-plugin/fish": { "actions": { "swim": true } } }),
+                false,
+                QueryOptions::new(),
+            )
+            .unwrap_err();
+
+        _m.assert();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(400));
+    }
+
+    #[test]
+    fn get_role_ok() {
+        let _m = mockito::mock("GET", "/roles/reader")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "getRole",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "_id": "reader",
+                    "_source": {
+                        "controllers": { "document": { "actions": { "get": true } } }
+                    }
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let role = k.security().get_role("reader").unwrap();
+
+        _m.assert();
+        assert_eq!(role.id(), "reader");
+        assert_eq!(role.controllers().len(), 1);
+    }
+
+    #[test]
+    fn update_role_ok() {
+        let _m = mockito::mock("PUT", "/roles/reader/_update")
+            .match_body(Matcher::Json(json!({
+                "controllers": { "collection": { "actions": { "list": true } } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "updateRole",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "_id": "reader",
+                    "_source": {
+                        "controllers": {
+                            "document": { "actions": { "get": true } },
+                            "collection": { "actions": { "list": true } }
+                        }
+                    }
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let role = k
+            .security()
+            .update_role(
+                "reader",
+                json!({ "collection": { "actions": { "list": true } } }),
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(role.controllers().len(), 2);
+    }
+
+    #[test]
+    fn delete_role_ok() {
+        let _m = mockito::mock("DELETE", "/roles/reader")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "deleteRole",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "_id": "reader" }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let id = k
+            .security()
+            .delete_role("reader", QueryOptions::new())
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(id, "reader");
+    }
+
+    #[test]
+    fn search_roles_ok() {
+        let _m = mockito::mock("POST", "/roles/_search?from=0&size=2")
+            .match_body(Matcher::Json(json!({ "controllers": ["document"] })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "searchRoles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "total": 3,
+                    "hits": [
+                        { "_id": "reader", "_source": { "controllers": { "document": { "actions": { "get": true } } } } },
+                        { "_id": "writer", "_source": { "controllers": { "document": { "actions": { "create": true } } } } }
+                    ]
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let (roles, total) = k
+            .security()
+            .search_roles(
+                Some(json!({ "controllers": ["document"] })),
+                QueryOptions::new().set_from(0).set_size(2),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(total, 3);
+        let ids: Vec<&String> = roles.iter().map(|role| role.id()).collect();
+        assert_eq!(ids, vec!["reader", "writer"]);
+    }
+
+    #[test]
+    fn m_get_roles_ok() {
+        let _m = mockito::mock("POST", "/roles/_mGet")
+            .match_body(Matcher::Json(json!({ "ids": ["reader", "nemo"] })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "mGetRoles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "hits": [
+                        { "_id": "reader", "_source": { "controllers": { "document": { "actions": { "get": true } } } } }
+                    ]
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let roles = k
+            .security()
+            .m_get_roles(vec!["reader".to_string(), "nemo".to_string()])
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(roles.len(), 1);
+        assert_eq!(roles[0].id(), "reader");
+    }
+
+    fn credentials_response(action: &str, result: &str) -> String {
+        format!(
+            r#"{{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "security",
+                "action": "{}",
+                "collection": null,
+                "index": null,
+                "volatile": null,
+                "result": {}
+            }}"#,
+            action, result
+        )
+    }
+
+    #[test]
+    fn create_credentials_ok() {
+        let _m = mockito::mock("POST", "/credentials/local/ferris/_create")
+            .match_body(Matcher::Json(
+                json!({ "username": "ferris", "password": "crab" }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response(
+                "createCredentials",
+                r#"{ "username": "ferris", "kuid": "ferris" }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.security().create_credentials(
+            "local",
+            "ferris",
+            json!({ "username": "ferris", "password": "crab" }),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap()["kuid"], "ferris");
+    }
+
+    #[test]
+    fn update_credentials_ok() {
+        let _m = mockito::mock("PUT", "/credentials/local/ferris/_update")
+            .match_body(Matcher::Json(json!({ "password": "lobster" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response(
+                "updateCredentials",
+                r#"{ "username": "ferris", "kuid": "ferris" }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.security().update_credentials(
+            "local",
+            "ferris",
+            json!({ "password": "lobster" }),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(res.unwrap()["username"], "ferris");
+    }
+
+    #[test]
+    fn get_credentials_ok() {
+        let _m = mockito::mock("GET", "/credentials/local/ferris")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response(
+                "getCredentials",
+                r#"{ "username": "ferris", "kuid": "ferris" }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.security().get_credentials("local", "ferris");
+
+        _m.assert();
+        assert_eq!(res.unwrap()["username"], "ferris");
+    }
+
+    #[test]
+    fn has_credentials_ok() {
+        let _m = mockito::mock("GET", "/credentials/local/nemo/_exists")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response("hasCredentials", r#"false"#))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.security().has_credentials("local", "nemo");
+
+        _m.assert();
+        assert!(!res.unwrap());
+    }
+
+    #[test]
+    fn validate_credentials_ok() {
+        let _m = mockito::mock("POST", "/credentials/local/ferris/_validate")
+            .match_body(Matcher::Json(
+                json!({ "username": "ferris", "password": "crab" }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response("validateCredentials", r#"true"#))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.security().validate_credentials(
+            "local",
+            "ferris",
+            json!({ "username": "ferris", "password": "crab" }),
+        );
+
+        _m.assert();
+        assert!(res.unwrap());
+    }
+
+    #[test]
+    fn delete_credentials_ok() {
+        let _m = mockito::mock("DELETE", "/credentials/local/ferris")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(credentials_response(
+                "deleteCredentials",
+                r#"{ "acknowledged": true }"#,
+            ))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .security()
+            .delete_credentials("local", "ferris", QueryOptions::new());
+
+        _m.assert();
+        assert!(res.unwrap());
+    }
+
+    #[test]
+    fn get_credentials_fail_empty_user_id() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k.security().get_credentials("local", "").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "[SecurityController::get_credentials] strategy and user_id arguments must not be empty."
+        );
+    }
+
+    #[test]
+    fn delete_credentials_skip_validation_empty_user_id() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.security().delete_credentials(
+            "local",
+            "",
+            QueryOptions::new().set_skip_validation(true),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<SdkError>().is_none());
+    }
+
+    #[test]
+    fn get_user_rights_ok_mixed() {
+        let _m = mockito::mock("GET", "/users/ferris/_rights")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "getUserRights",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "hits": [
+                        { "controller": "document", "action": "get", "index": "ferris_index", "collection": "*", "value": "allowed" },
+                        { "controller": "document", "action": "delete", "index": "ferris_index", "collection": "crabs", "value": "denied" },
+                        { "controller": "document", "action": "update", "index": "*", "collection": "*", "value": "conditional" }
+                    ]
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let rights = k.security().get_user_rights("ferris").unwrap();
+
+        _m.assert();
+        let values: Vec<(&str, RightValue)> = rights
+            .iter()
+            .map(|right| (right.action().as_str(), right.value()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                ("get", RightValue::Allowed),
+                ("delete", RightValue::Denied),
+                ("update", RightValue::Conditional),
+            ]
+        );
+        assert_eq!(rights[1].collection(), "crabs");
+    }
+
+    #[test]
+    fn get_profile_rights_ok() {
+        let _m = mockito::mock("GET", "/profiles/ferris_readers/_rights")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "getProfileRights",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "hits": [
+                        { "controller": "document", "action": "search", "index": "ferris_index", "collection": "crabs", "value": "allowed" }
+                    ]
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let rights = k.security().get_profile_rights("ferris_readers").unwrap();
+
+        _m.assert();
+        assert_eq!(rights.len(), 1);
+        assert_eq!(rights[0].controller(), "document");
+        assert_eq!(rights[0].value(), RightValue::Allowed);
+    }
+
+    #[test]
+    fn get_user_rights_fail_empty_id() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k.security().get_user_rights("").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "[SecurityController::get_user_rights] id argument must not be empty."
+        );
+    }
+
+    #[test]
+    fn get_user_ok() {
+        let _m = mockito::mock("GET", "/users/ferris")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "getUser",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_source": { "name": "Ferris", "profileIds": ["admin"] }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let user = k.security().get_user("ferris").unwrap();
+
+        _m.assert();
+        assert_eq!(user.id(), "ferris");
+        assert_eq!(user.content()["name"], json!("Ferris"));
+        assert_eq!(user.profile_ids(), &vec!["admin".to_string()]);
+    }
+
+    #[test]
+    fn get_user_fail_not_found() {
+        let _m = mockito::mock("GET", "/users/nemo")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "User with id \"nemo\" not found",
+                        "status": 404,
+                        "id": "security.user.not_found"
+                    },
+                    "controller": "security",
+                    "action": "getUser",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k.security().get_user("nemo").unwrap_err();
+
+        _m.assert();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(404));
+        assert_eq!(k_err.id(), &Some("security.user.not_found".to_string()));
+    }
+
+    #[test]
+    fn m_get_users_ok() {
+        let _m = mockito::mock("POST", "/users/_mGet")
+            .match_body(Matcher::Json(json!({ "ids": ["ferris", "nemo", "crab"] })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "mGetUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "hits": [
+                            { "_id": "ferris", "_source": { "name": "Ferris", "profileIds": ["admin"] } },
+                            { "_id": "crab", "_source": { "name": "Crab", "profileIds": ["default"] } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let users = k
+            .security()
+            .m_get_users(vec![
+                "ferris".to_string(),
+                "nemo".to_string(),
+                "crab".to_string(),
+            ])
+            .unwrap();
+
+        _m.assert();
+        let ids: Vec<&String> = users.iter().map(|user| user.id()).collect();
+        assert_eq!(ids, vec!["ferris", "crab"]);
+        assert_eq!(users[1].profile_ids(), &vec!["default".to_string()]);
+    }
+
+    #[test]
+    fn m_get_users_fail_no_id() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k.security().m_get_users(vec![]).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "[SecurityController::m_get_users] ids argument must not be empty."
+        );
+    }
+
+    #[test]
+    fn scroll_users_ok_until_empty_page() {
+        let _page_2 = mockito::mock("GET", "/users/_scroll/ferris-scroll-1?scroll=1m")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "scrollUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "total": 3,
+                    "scrollId": "ferris-scroll-2",
+                    "hits": [
+                        { "_id": "crab", "_source": { "name": "Crab" } },
+                        { "_id": "lobster", "_source": { "name": "Lobster" } }
+                    ]
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+        let _page_3 = mockito::mock("GET", "/users/_scroll/ferris-scroll-2?scroll=1m")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "scrollUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "total": 3,
+                    "scrollId": "ferris-scroll-3",
+                    "hits": [
+                        { "_id": "nemo", "_source": { "name": "Nemo" } }
+                    ]
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+        let _end = mockito::mock("GET", "/users/_scroll/ferris-scroll-3?scroll=1m")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "scrollUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "total": 3,
+                    "scrollId": "ferris-scroll-3",
+                    "hits": []
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut scroll_id = "ferris-scroll-1".to_string();
+        let mut ids = Vec::new();
+        loop {
+            let page = k.security().scroll_users(&scroll_id, "1m").unwrap();
+            if page.hits().is_empty() {
+                break;
+            }
+            ids.extend(page.hits().iter().map(|hit| hit.id().clone()));
+            scroll_id = page.scroll_id().clone().unwrap();
+        }
+
+        _page_2.assert();
+        _page_3.assert();
+        _end.assert();
+        assert_eq!(ids, vec!["crab", "lobster", "nemo"]);
+        assert_eq!(scroll_id, "ferris-scroll-3");
+    }
+
+    #[test]
+    fn scroll_profiles_ok_from_search() {
+        let _search = mockito::mock("POST", "/profiles/_search?scroll=30s&size=1")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "searchProfiles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "scrollId": "ferris-scroll-1",
+                        "hits": [
+                            { "_id": "ferris_readers", "_source": { "policies": [{ "roleId": "reader" }] } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+        let _page_2 = mockito::mock("GET", "/profiles/_scroll/ferris-scroll-1?scroll=30s")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "scrollProfiles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "scrollId": "ferris-scroll-2",
+                        "hits": [
+                            { "_id": "crab_readers", "_source": { "policies": [{ "roleId": "reader" }] } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+        let _page_3 = mockito::mock("GET", "/profiles/_scroll/ferris-scroll-2?scroll=30s")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "scrollProfiles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "scrollId": "ferris-scroll-3",
+                        "hits": [
+                            { "_id": "lobster_readers", "_source": { "policies": [{ "roleId": "reader" }] } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+        let _end = mockito::mock("GET", "/profiles/_scroll/ferris-scroll-3?scroll=30s")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "scrollProfiles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "scrollId": "ferris-scroll-3",
+                        "hits": []
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut page = k
+            .security()
+            .search_profiles(None, QueryOptions::new().set_size(1).set_scroll("30s"))
+            .unwrap();
+        let mut ids = Vec::new();
+        while !page.hits().is_empty() {
+            ids.extend(page.hits().iter().map(|hit| hit.id().clone()));
+            let scroll_id = page.scroll_id().clone().unwrap();
+            page = k.security().scroll_profiles(&scroll_id, "30s").unwrap();
+        }
+
+        _search.assert();
+        _page_2.assert();
+        _page_3.assert();
+        _end.assert();
+        assert_eq!(
+            ids,
+            vec!["ferris_readers", "crab_readers", "lobster_readers"]
+        );
+    }
+
+    #[test]
+    fn scroll_users_ok_from_search() {
+        let _search = mockito::mock("POST", "/users/_search?scroll=1m&size=2")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "searchUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "scrollId": "ferris-scroll-1",
+                        "hits": [
+                            { "_id": "ferris", "_source": { "name": "Ferris" } },
+                            { "_id": "crab", "_source": { "name": "Crab" } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+        let _page_2 = mockito::mock("GET", "/users/_scroll/ferris-scroll-1?scroll=1m")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "scrollUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "scrollId": "ferris-scroll-2",
+                        "hits": [
+                            { "_id": "lobster", "_source": { "name": "Lobster" } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let users = k
+            .security()
+            .search_users(None, QueryOptions::new().set_size(2).set_scroll("1m"))
+            .unwrap();
+        let scroll_id = users.scroll_id().clone().unwrap();
+        let page = k.security().scroll_users(&scroll_id, "1m").unwrap();
+
+        _search.assert();
+        _page_2.assert();
+        assert_eq!(scroll_id, "ferris-scroll-1");
+        assert_eq!(page.hits()[0].id(), "lobster");
+        assert_eq!(page.scroll_id(), &Some("ferris-scroll-2".to_string()));
+    }
+
+    #[test]
+    fn scroll_users_fail_empty_ttl() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .security()
+            .scroll_users("ferris-scroll-1", "")
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "[SecurityController::scroll_users] scroll_id and ttl arguments must not be empty."
+        );
+    }
+
+    #[test]
+    fn search_users_ok_forwards_pagination() {
+        let _m = mockito::mock("POST", "/users/_search?from=10&scroll=1m&size=5")
+            .match_body(Matcher::Json(json!({ "query": { "match_all": {} } })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "searchUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "total": 11,
+                        "hits": [
+                            { "_id": "lobster", "_source": { "name": "Lobster" } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let users = k
+            .security()
+            .search_users(
+                Some(json!({ "query": { "match_all": {} } })),
+                QueryOptions::new()
+                    .set_from(10)
+                    .set_size(5)
+                    .set_scroll("1m"),
+            )
+            .unwrap();
+        assert_eq!(users.total(), 11);
+
+        let users: Vec<User> = users.map(|user| user.unwrap()).collect();
+
+        _m.assert();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id(), "lobster");
+    }
+
+    #[test]
+    fn search_users_ok_two_pages() {
+        let _search = mockito::mock("POST", "/users/_search?scroll=30s&size=2")
+            .match_body(Matcher::Json(json!({ "query": { "match_all": {} } })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(SEARCH_USERS_RESPONSE)
+            .expect(1)
+            .create();
+        let _scroll = mockito::mock("GET", "/users/_scroll/ferris-scroll?scroll=30s")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "scrollUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "total": 3,
+                        "scrollId": "ferris-scroll",
+                        "hits": [
+                            { "_id": "lobster", "_source": { "name": "Lobster" } }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let users = k
+            .security()
+            .search_users(
+                Some(json!({ "query": { "match_all": {} } })),
+                QueryOptions::new().set_size(2),
+            )
+            .unwrap();
+        assert_eq!(users.total(), 3);
+
+        let users: Vec<User> = users.map(|user| user.unwrap()).collect();
+
+        _search.assert();
+        _scroll.assert();
+        let ids: Vec<&String> = users.iter().map(|user| user.id()).collect();
+        assert_eq!(ids, vec!["ferris", "crab", "lobster"]);
+        assert_eq!(users[0].profile_ids(), &vec!["admin".to_string()]);
+        assert!(users[2].profile_ids().is_empty());
+    }
+
+    #[test]
+    fn search_users_fail_scroll_expired() {
+        let _search = mockito::mock("POST", "/users/_search?scroll=30s&size=2")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(SEARCH_USERS_RESPONSE)
+            .create();
+        let _scroll = mockito::mock("GET", "/users/_scroll/ferris-scroll?scroll=30s")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "Non-existing or expired scroll identifier",
+                        "status": 404
+                    },
+                    "controller": "security",
+                    "action": "scrollUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut users = k
+            .security()
+            .search_users(None, QueryOptions::new().set_size(2))
+            .unwrap();
+
+        assert_eq!(users.next().unwrap().unwrap().id(), "ferris");
+        assert_eq!(users.next().unwrap().unwrap().id(), "crab");
+        let err = users.next().unwrap().unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SdkError>().unwrap().message(),
+            "the scroll cursor expired after 2 of 3 users were read, a longer `scroll` duration is needed."
+        );
+        assert!(users.next().is_none());
+        _scroll.assert();
+    }
+
+    fn mapping_response(action: &str) -> String {
+        json!({
+            "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+            "status": 200,
+            "error": null,
+            "controller": "security",
+            "action": action,
+            "collection": null,
+            "index": null,
+            "volatile": null,
+            "result": {
+                "properties": { "costCenter": { "type": "keyword" } }
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn update_user_ok_partial_content() {
+        let _m = mockito::mock("PUT", "/users/ferris/_update")
+            .match_body(Matcher::Json(json!({ "status": "active" })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "updateUser",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_source": { "name": "Ferris", "status": "active", "profileIds": ["admin"] }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let user = k
+            .security()
+            .update_user("ferris", json!({ "status": "active" }), QueryOptions::new())
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(user.id(), "ferris");
+        assert_eq!(user.content()["name"], json!("Ferris"));
+        assert_eq!(user.content()["status"], json!("active"));
+        assert_eq!(user.profile_ids(), &vec!["admin".to_string()]);
+    }
+
+    #[test]
+    fn replace_user_ok() {
+        let _m = mockito::mock("PUT", "/users/ferris/_replace")
+            .match_body(Matcher::Json(
+                json!({ "name": "Crab", "profileIds": ["default"] }),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "replaceUser",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_source": { "name": "Crab", "profileIds": ["default"] }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let user = k
+            .security()
+            .replace_user(
+                "ferris",
+                json!({ "name": "Crab", "profileIds": ["default"] }),
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(user.content().len(), 2);
+        assert_eq!(user.profile_ids(), &vec!["default".to_string()]);
+    }
+
+    #[test]
+    fn update_user_fail_not_an_object() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .security()
+            .update_user("ferris", json!("active"), QueryOptions::new())
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "[SecurityController::update_user] content argument must be a JSON object."
+        );
+    }
+
+    #[test]
+    fn delete_user_ok() {
+        let _m = mockito::mock("DELETE", "/users/ferris")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "deleteUser",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "_id": "ferris" }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let id = k
+            .security()
+            .delete_user("ferris", QueryOptions::new())
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(id, "ferris");
+    }
+
+    #[test]
+    fn m_delete_users_ok_one_missing() {
+        let _m = mockito::mock("POST", "/users/_mDelete")
+            .match_body(Matcher::Json(json!({ "ids": ["ferris", "nemo"] })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "mDeleteUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "successes": ["ferris"],
+                        "errors": [
+                            { "_id": "nemo", "reason": "User with id \"nemo\" not found" }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let (deleted, errors) = k
+            .security()
+            .m_delete_users(
+                vec!["ferris".to_string(), "nemo".to_string()],
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(deleted, vec!["ferris"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["_id"], json!("nemo"));
+    }
+
+    #[test]
+    fn update_profile_mapping_ok() {
+        let _m = mockito::mock("PUT", "/profiles/_mapping")
+            .match_body(Matcher::Json(json!({
+                "properties": { "costCenter": { "type": "keyword" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(mapping_response("updateProfileMapping"))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.security().update_profile_mapping(
+            json!({ "costCenter": { "type": "keyword" } }),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(
+            res.unwrap()["properties"]["costCenter"],
+            json!({ "type": "keyword" })
+        );
+    }
+
+    #[test]
+    fn update_role_mapping_ok() {
+        let _m = mockito::mock("PUT", "/roles/_mapping")
+            .match_body(Matcher::Json(json!({
+                "properties": { "costCenter": { "type": "keyword" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(mapping_response("updateRoleMapping"))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.security().update_role_mapping(
+            json!({ "costCenter": { "type": "keyword" } }),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(
+            res.unwrap()["properties"]["costCenter"],
+            json!({ "type": "keyword" })
+        );
+    }
+
+    #[test]
+    fn update_user_mapping_ok() {
+        let _m = mockito::mock("PUT", "/users/_mapping")
+            .match_body(Matcher::Json(json!({
+                "properties": { "costCenter": { "type": "keyword" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(mapping_response("updateUserMapping"))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.security().update_user_mapping(
+            json!({ "costCenter": { "type": "keyword" } }),
+            QueryOptions::new(),
+        );
+
+        _m.assert();
+        assert_eq!(
+            res.unwrap()["properties"]["costCenter"],
+            json!({ "type": "keyword" })
+        );
+    }
+
+    #[test]
+    fn update_profile_mapping_fail_not_an_object() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .security()
+            .update_profile_mapping(json!(["costCenter"]), QueryOptions::new())
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "[SecurityController::update_profile_mapping] mapping argument must be a JSON object."
+        );
+    }
+
+    #[test]
+    fn create_first_admin_ok_reset() {
+        let _m = mockito::mock("POST", "/_createFirstAdmin?_id=ferris&reset=true")
+            .match_body(Matcher::Json(json!({
+                "content": { "name": "Ferris" },
+                "credentials": { "local": { "username": "ferris", "password": "crab" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "createFirstAdmin",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                    "_id": "ferris",
+                    "_source": { "name": "Ferris", "profileIds": ["admin"] }
+                }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let admin = k
+            .security()
+            .create_first_admin(
+                Some("ferris"),
+                json!({ "name": "Ferris" }),
+                json!({ "local": { "username": "ferris", "password": "crab" } }),
+                true,
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(admin.id(), "ferris");
+        assert_eq!(admin.profile_ids(), &vec!["admin".to_string()]);
+    }
+
+    #[test]
+    fn create_first_admin_fail_admin_exists() {
+        let _m = mockito::mock("POST", "/_createFirstAdmin?_id=crab")
+            .with_status(403)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 403,
+                    "error": {
+                    "message": "Admin user is already set.",
+                    "status": 403,
+                    "id": "api.process.admin_exists"
+                },
+                    "controller": "security",
+                    "action": "createFirstAdmin",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .security()
+            .create_first_admin(
+                Some("crab"),
+                json!({ "name": "Crab" }),
+                json!({ "local": { "username": "crab", "password": "lobster" } }),
+                false,
+            )
+            .unwrap_err();
+
+        _m.assert();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(403));
+        assert_eq!(k_err.id(), &Some("api.process.admin_exists".to_string()));
+    }
+
+    #[test]
+    fn create_user_ok_with_id() {
+        let _m = mockito::mock("POST", "/users/_create?_id=ferris")
+            .match_body(Matcher::Json(json!({
+                "content": { "profileIds": ["default"], "name": "Ferris" },
+                "credentials": { "local": { "username": "ferris", "password": "crab" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "createUser",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "_id": "ferris",
+                        "_source": { "profileIds": ["default"], "name": "Ferris" }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let user = k
+            .security()
+            .create_user(
+                Some("ferris"),
+                json!({ "profileIds": ["default"], "name": "Ferris" }),
+                json!({ "local": { "username": "ferris", "password": "crab" } }),
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(user.id(), "ferris");
+        assert_eq!(user.profile_ids(), &vec!["default".to_string()]);
+        assert_eq!(user.content()["name"], json!("Ferris"));
+    }
+
+    #[test]
+    fn create_user_fail_duplicate_id() {
+        let _m = mockito::mock("POST", "/users/_create?_id=ferris")
+            .with_status(409)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 409,
+                    "error": {
+                        "message": "User ferris already exists.",
+                        "status": 409
+                    },
+                    "controller": "security",
+                    "action": "createUser",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let err = k
+            .security()
+            .create_user(
+                Some("ferris"),
+                json!({ "profileIds": ["default"] }),
+                json!({ "local": { "username": "ferris", "password": "crab" } }),
+                QueryOptions::new(),
+            )
+            .unwrap_err();
+
+        _m.assert();
+        let k_err = err.downcast_ref::<KuzzleError>().unwrap();
+        assert_eq!(k_err.status(), Some(409));
+        assert_eq!(k_err.message(), "User ferris already exists.");
+    }
+
+    #[test]
+    fn create_restricted_user_ok_generated_id() {
+        let _m = mockito::mock("POST", "/users/_createRestricted")
+            .match_body(Matcher::Json(json!({
+                "content": { "name": "Crab" },
+                "credentials": { "local": { "username": "crab", "password": "ferris" } }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "createRestrictedUser",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "_id": "AWxmZ9vm7ZqTqZZ4RTcJ",
+                        "_source": { "profileIds": ["anonymous"], "name": "Crab" }
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let user = k
+            .security()
+            .create_restricted_user(
+                None,
+                json!({ "name": "Crab" }),
+                json!({ "local": { "username": "crab", "password": "ferris" } }),
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        _m.assert();
+        assert_eq!(user.id(), "AWxmZ9vm7ZqTqZZ4RTcJ");
+        assert_eq!(user.profile_ids(), &vec!["anonymous".to_string()]);
     }
 }
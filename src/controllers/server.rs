@@ -1,7 +1,11 @@
+use crate::controllers::readiness::{Clock, SystemClock};
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions, SdkError};
-use serde_json::{to_value, Map, Value};
+use crate::types::{KuzzleRequest, QueryOptions, SdkError, StatsSnapshot};
+use serde_json::{from_value, to_value, Map, Value};
 use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub struct ServerController<'a>(pub &'a Kuzzle);
 
@@ -25,7 +29,7 @@ impl<'a> ServerController<'a> {
     ///
     /// ```
     ///
-    pub fn admin_exists(&self) -> Result<bool, Box<Error>> {
+    pub fn admin_exists(&self) -> Result<bool, Box<dyn Error>> {
         let req: KuzzleRequest = KuzzleRequest::new("server", "adminExists");
         let res = self.kuzzle().query(req, QueryOptions::new())?;
         match &res.error() {
@@ -60,7 +64,7 @@ impl<'a> ServerController<'a> {
     ///
     /// ```
     ///
-    pub fn get_all_stats(&self) -> Result<Map<String, Value>, Box<Error>> {
+    pub fn get_all_stats(&self) -> Result<Map<String, Value>, Box<dyn Error>> {
         let req: KuzzleRequest = KuzzleRequest::new("server", "getAllStats");
         let res = self.kuzzle().query(req, QueryOptions::new())?;
         match &res.error() {
@@ -69,9 +73,44 @@ impl<'a> ServerController<'a> {
         }
     }
 
+    /// Gets the stored internal statistic snapshots taken after `since`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let last_hour = SystemTime::now() - Duration::from_secs(3600);
+    /// let res = kuzzle.server().get_all_stats_since(last_hour);
+    ///
+    /// ```
+    ///
+    pub fn get_all_stats_since(
+        &self,
+        since: SystemTime,
+    ) -> Result<Vec<StatsSnapshot>, Box<dyn Error>> {
+        let since = since.duration_since(UNIX_EPOCH)?.as_millis() as u64;
+        let stats = self.get_all_stats()?;
+        let snapshots: Vec<StatsSnapshot> = from_value(stats["hits"].clone())?;
+
+        Ok(snapshots
+            .into_iter()
+            .filter(|snapshot| snapshot.timestamp() > since)
+            .collect())
+    }
+
     /// Returns the current Kuzzle configuration.
-    /// 
-    /// This route should only be accessible to administrators, 
+    ///
+    /// This route should only be accessible to administrators,
     /// as it might return sensitive information about the backend.
     ///
     /// # Example
@@ -91,7 +130,7 @@ impl<'a> ServerController<'a> {
     ///
     /// ```
     ///
-    pub fn get_config(&self) -> Result<Map<String, Value>, Box<Error>> {
+    pub fn get_config(&self) -> Result<Map<String, Value>, Box<dyn Error>> {
         let req: KuzzleRequest = KuzzleRequest::new("server", "getConfig");
         let res = self.kuzzle().query(req, QueryOptions::new())?;
         match &res.error() {
@@ -119,7 +158,7 @@ impl<'a> ServerController<'a> {
     ///
     /// ```
     ///
-    pub fn get_last_stats(&self) -> Result<Map<String, Value>, Box<Error>> {
+    pub fn get_last_stats(&self) -> Result<Map<String, Value>, Box<dyn Error>> {
         let req: KuzzleRequest = KuzzleRequest::new("server", "getLastStats");
         let res = self.kuzzle().query(req, QueryOptions::new())?;
         match &res.error() {
@@ -147,7 +186,7 @@ impl<'a> ServerController<'a> {
     ///
     /// ```
     ///
-    pub fn get_stats(&self, from: i64, to: i64) -> Result<Map<String, Value>, Box<Error>> {
+    pub fn get_stats(&self, from: i64, to: i64) -> Result<Map<String, Value>, Box<dyn Error>> {
         if from.to_string().len() != 13 || to.to_string().len() != 13 {
             return Err(Box::new(SdkError::new(
                 "ServerController::get_stats",
@@ -165,9 +204,9 @@ impl<'a> ServerController<'a> {
         }
     }
 
-    /// Returns information about Kuzzle: available API (base + extended), plugins, 
+    /// Returns information about Kuzzle: available API (base + extended), plugins,
     /// external services (Redis, Elasticsearch, ...), servers, etc.
-    /// 
+    ///
     /// # Example
     ///
     /// ```
@@ -185,7 +224,7 @@ impl<'a> ServerController<'a> {
     ///
     /// ```
     ///
-    pub fn info(&self) -> Result<Map<String, Value>, Box<Error>> {
+    pub fn info(&self) -> Result<Map<String, Value>, Box<dyn Error>> {
         let req: KuzzleRequest = KuzzleRequest::new("server", "info");
         let res = self.kuzzle().query(req, QueryOptions::new())?;
         match &res.error() {
@@ -195,7 +234,7 @@ impl<'a> ServerController<'a> {
     }
 
     /// Returns the current server timestamp, in Epoch-millis format.
-    /// 
+    ///
     /// # Example
     ///
     /// ```
@@ -213,7 +252,7 @@ impl<'a> ServerController<'a> {
     ///
     /// ```
     ///
-    pub fn now(&self) -> Result<u64, Box<Error>> {
+    pub fn now(&self) -> Result<u64, Box<dyn Error>> {
         let req: KuzzleRequest = KuzzleRequest::new("server", "now");
         let res = self.kuzzle().query(req, QueryOptions::new())?;
         match &res.error() {
@@ -224,14 +263,122 @@ impl<'a> ServerController<'a> {
                 .get("now")
                 .unwrap()
                 .as_u64()
-                .unwrap()
-                .clone()),
+                .unwrap()),
             Some(k_err) => Err(Box::new(k_err.clone())),
         }
     }
 
+    /// Returns a `StatsWatcher` polling `server/getLastStats` every
+    /// `interval` and calling `callback` with each snapshot, once run.
+    /// Its `handle` stops it, e.g. from another thread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use std::time::Duration;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let watcher = kuzzle.server().watch_stats(Duration::from_secs(10), |snapshot| {
+    ///     println!("{:?}", snapshot.completed_requests());
+    /// });
+    /// let handle = watcher.handle();
+    /// // Stops right away: `handle` would rather be sent to another thread.
+    /// handle.stop();
+    /// let res = watcher.run();
+    ///
+    /// ```
+    ///
+    pub fn watch_stats<F>(&self, interval: Duration, callback: F) -> StatsWatcher<'a, F>
+    where
+        F: FnMut(&StatsSnapshot),
+    {
+        StatsWatcher {
+            kuzzle: self.kuzzle(),
+            interval,
+            callback,
+            handle: WatchHandle::default(),
+            clock: Box::new(SystemClock),
+        }
+    }
+
     fn kuzzle(&self) -> &'a Kuzzle {
-        &self.0
+        self.0
+    }
+}
+
+/// A handle stopping a `StatsWatcher`. It can be cloned and sent to
+/// another thread.
+#[derive(Debug, Clone, Default)]
+pub struct WatchHandle {
+    stopped: Arc<AtomicBool>,
+}
+
+impl WatchHandle {
+    /// Stops the watcher, after its current poll if any.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether `stop` was called.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+}
+
+/// Polls `server/getLastStats` periodically, see `ServerController::watch_stats`.
+pub struct StatsWatcher<'a, F> {
+    kuzzle: &'a Kuzzle,
+    interval: Duration,
+    callback: F,
+    handle: WatchHandle,
+    clock: Box<dyn Clock>,
+}
+
+impl<'a, F> StatsWatcher<'a, F>
+where
+    F: FnMut(&StatsSnapshot),
+{
+    /// Returns a handle stopping this watcher.
+    pub fn handle(&self) -> WatchHandle {
+        self.handle.clone()
+    }
+
+    /// Polls statistics until the watcher is stopped, or until a poll fails.
+    pub fn run(self) -> Result<(), Box<dyn Error>> {
+        self.run_until(None)
+    }
+
+    /// Polls statistics until the watcher is stopped, `window` has
+    /// elapsed, or a poll fails.
+    pub fn run_for(self, window: Duration) -> Result<(), Box<dyn Error>> {
+        self.run_until(Some(window))
+    }
+
+    fn run_until(mut self, window: Option<Duration>) -> Result<(), Box<dyn Error>> {
+        let start = self.clock.now();
+
+        while !self.handle.is_stopped() {
+            let stats = self.kuzzle.server().get_last_stats()?;
+            let snapshot: StatsSnapshot = from_value(Value::Object(stats))?;
+            (self.callback)(&snapshot);
+
+            self.clock.sleep(self.interval);
+            if let Some(window) = window {
+                if self.clock.now() - start >= window {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -241,6 +388,8 @@ mod tests {
     use crate::protocols::Http;
     use crate::types::KuzzleOptions;
     use mockito;
+    use std::cell::Cell;
+    use std::time::Instant;
 
     #[test]
     fn admin_exists_ok_true() {
@@ -301,6 +450,60 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn get_all_stats_since_ok() {
+        let _m = mockito::mock("GET", "/_getAllStats")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "getAllStats",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                      "total": 3,
+                      "hits": [
+                        {
+                          "completedRequests": { "http": 24 },
+                          "failedRequests": {},
+                          "ongoingRequests": {},
+                          "connections": {},
+                          "timestamp": 1453110641308
+                        },
+                        {
+                          "completedRequests": { "http": 30 },
+                          "failedRequests": { "http": 1 },
+                          "ongoingRequests": {},
+                          "connections": { "websocket": 13 },
+                          "timestamp": 1453110701308
+                        },
+                        {
+                          "completedRequests": { "http": 42 },
+                          "timestamp": 1453110761308
+                        }
+                      ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let since = UNIX_EPOCH + Duration::from_millis(1453110641308);
+        let snapshots = k.server().get_all_stats_since(since).unwrap();
+
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].timestamp(), 1453110701308);
+        assert_eq!(snapshots[0].failed_requests()["http"], 1);
+        assert_eq!(snapshots[0].connections()["websocket"], 13);
+        assert_eq!(snapshots[1].completed_requests()["http"], 42);
+        assert!(snapshots[1].failed_requests().is_empty());
+    }
+
     #[test]
     fn get_all_stats_ok() {
         let _m = mockito::mock("GET", "/_getAllStats")
@@ -859,4 +1062,94 @@ mod tests {
 
         assert!(res.is_err());
     }
+
+    /// A clock whose time only moves forward when slept on.
+    struct FakeClock {
+        start: Instant,
+        elapsed: Cell<Duration>,
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> Instant {
+            self.start + self.elapsed.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.elapsed.set(self.elapsed.get() + duration);
+        }
+    }
+
+    const LAST_STATS_RESPONSE: &str = r#"{
+        "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+        "status": 200,
+        "error": null,
+        "controller": "server",
+        "action": "getLastStats",
+        "collection": null,
+        "index": null,
+        "volatile": null,
+        "result": {
+            "completedRequests": { "http": 24 },
+            "connections": { "websocket": 13 },
+            "timestamp": 1453110641308
+        }
+    }"#;
+
+    #[test]
+    fn watch_stats_ok_polls_every_interval() {
+        let _m = mockito::mock("GET", "/_getLastStats")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(LAST_STATS_RESPONSE)
+            .expect(10)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut timestamps = Vec::new();
+        let mut watcher = k
+            .server()
+            .watch_stats(Duration::from_millis(100), |snapshot| {
+                timestamps.push(snapshot.timestamp())
+            });
+        watcher.clock = Box::new(FakeClock {
+            start: Instant::now(),
+            elapsed: Cell::new(Duration::from_millis(0)),
+        });
+        watcher.run_for(Duration::from_secs(1)).unwrap();
+
+        _m.assert();
+        assert_eq!(timestamps, vec![1453110641308; 10]);
+    }
+
+    #[test]
+    fn watch_stats_ok_stopped_by_handle() {
+        let _m = mockito::mock("GET", "/_getLastStats")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(LAST_STATS_RESPONSE)
+            .expect(3)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let handle = WatchHandle::default();
+        let stopper = handle.clone();
+        let mut polls = 0;
+        let mut watcher = k
+            .server()
+            .watch_stats(Duration::from_millis(100), move |_| {
+                polls += 1;
+                if polls == 3 {
+                    stopper.stop();
+                }
+            });
+        watcher.handle = handle.clone();
+        watcher.clock = Box::new(FakeClock {
+            start: Instant::now(),
+            elapsed: Cell::new(Duration::from_millis(0)),
+        });
+        watcher.run().unwrap();
+
+        _m.assert();
+        assert!(handle.is_stopped());
+    }
 }
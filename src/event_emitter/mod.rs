@@ -1,3 +1,3 @@
-mod event_emitter;
+mod emitter;
 
-pub use self::event_emitter::EventEmitter;
+pub use self::emitter::EventEmitter;
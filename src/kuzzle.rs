@@ -1,12 +1,44 @@
 use crate::controllers::*;
 use crate::protocols::Protocol;
-use crate::types::{KuzzleRequest, KuzzleResponse, QueryOptions};
+use crate::types::{
+    ConnectionError, ConnectionState, Document, KuzzleRequest, KuzzleResponse, Notification,
+    QueryOptions, QueueStats, SdkError, ServerLimits, SubscribeOptions, SubscriptionInfo,
+};
+use serde_json::{Map, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
+use std::process;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Cached documents, by index, collection and id, with when they were cached.
+type CachedDocuments = HashMap<(String, String, String), (Document, Instant)>;
 
 /// Kuzzle is the Kuzzle SDK client used to dial with the Kuzzle server.
 pub struct Kuzzle {
-    _protocol: Box<Protocol>,
-    _jwt: String,
+    _protocol: Box<dyn Protocol>,
+    _read_protocol: Option<Box<dyn Protocol>>,
+    _jwt: RefCell<String>,
+    _subscriptions: RefCell<Vec<Subscription>>,
+    _mapping_cache: Cell<bool>,
+    _mappings: RefCell<HashMap<(String, String), Value>>,
+    _document_cache: Cell<bool>,
+    _documents: RefCell<CachedDocuments>,
+    _document_cache_ttl: Cell<Duration>,
+    _volatile: RefCell<Map<String, Value>>,
+    _server_limits: Cell<ServerLimits>,
+    _sdk_instance_id: String,
+}
+
+/// A subscription registered on the client, with what it takes to renew it.
+#[derive(Clone)]
+struct Subscription {
+    info: SubscriptionInfo,
+    options: SubscribeOptions,
+    callback: Rc<dyn Fn(Notification)>,
 }
 
 impl Kuzzle {
@@ -31,72 +63,2022 @@ impl Kuzzle {
     {
         Kuzzle {
             _protocol: Box::new(protocol),
-            _jwt: String::new(),
+            _read_protocol: None,
+            _jwt: RefCell::new(String::new()),
+            _subscriptions: RefCell::new(Vec::new()),
+            _mapping_cache: Cell::new(false),
+            _mappings: RefCell::new(HashMap::new()),
+            _document_cache: Cell::new(false),
+            _documents: RefCell::new(HashMap::new()),
+            _document_cache_ttl: Cell::new(DEFAULT_DOCUMENT_CACHE_TTL),
+            _volatile: RefCell::new(Map::new()),
+            _server_limits: Cell::new(ServerLimits::default()),
+            _sdk_instance_id: new_sdk_instance_id(),
+        }
+    }
+
+    /// Kuzzle SDK constructor for CQRS setups: read actions (gets, searches,
+    /// counts, `server` actions...) are sent through `read_protocol`, e.g.
+    /// to a replica, and every other action through `write_protocol`.
+    /// Authentication and realtime actions always use `write_protocol`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let _kuzzle = Kuzzle::with_read_write(
+    ///     Http::new(KuzzleOptions::new("replica.local", 7512)),
+    ///     Http::new(KuzzleOptions::new("primary.local", 7512)),
+    /// );
+    /// ```
+    pub fn with_read_write<R, W>(read_protocol: R, write_protocol: W) -> Kuzzle
+    where
+        R: 'static + Protocol,
+        W: 'static + Protocol,
+    {
+        let mut kuzzle = Kuzzle::new(write_protocol);
+        kuzzle._read_protocol = Some(Box::new(read_protocol));
+        kuzzle
+    }
+
+    /// Opens the connection to Kuzzle, and the one to the read replica if
+    /// any. Requests can be sent once connected, protocols without
+    /// persistent connection such as `Http` being always ready.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// assert!(k.connect().is_ok());
+    /// ```
+    pub fn connect(&self) -> Result<(), Box<dyn Error>> {
+        self._protocol.connect()?;
+        if let Some(read_protocol) = &self._read_protocol {
+            read_protocol.connect()?;
+        }
+        Ok(())
+    }
+
+    /// Closes the connections opened by `connect`. The requests still
+    /// waiting for their response fail.
+    pub fn disconnect(&self) {
+        self._protocol.close();
+        if let Some(read_protocol) = &self._read_protocol {
+            read_protocol.close();
         }
     }
 
+    /// Waits up to `timeout` for realtime notifications, handing each one
+    /// to the callback of its subscription, and returns how many were
+    /// received. Notifications received while waiting for a response are
+    /// dispatched as well. Nothing is ever received over `Http`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use std::time::Duration;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// assert_eq!(k.receive_notifications(Duration::from_millis(10)).unwrap(), 0);
+    /// ```
+    pub fn receive_notifications(&self, timeout: Duration) -> Result<usize, Box<dyn Error>> {
+        self.recover_connection()?;
+        self._protocol.receive_notifications(timeout)
+    }
+
     /// Execute the given KuzzleRequest and returns a `Result` which contains
     /// `KuzzleResponse` if execute was ok or a `KuzzleError` else.
+    ///
+    /// Request-level `QueryOptions` (such as `refresh`, `node`, `version`,
+    /// `notify`, `silent`, `lang` or the `from`/`size`/`scroll` pagination) are
+    /// forwarded as request arguments.
+    ///
+    /// The stored JWT, if any, is attached to the request, except for
+    /// `auth/checkToken`.
+    ///
+    /// The global volatile data is merged into the request volatile data,
+    /// the entries set through `QueryOptions` taking precedence.
+    ///
+    /// A write action drops the cached documents and mappings it may change,
+    /// see `set_document_cache` and `set_mapping_cache`.
+    ///
+    /// A non-queuable request fails right away with a `ConnectionError`
+    /// when the protocol is known to be offline.
+    ///
+    /// Subscriptions, and the actions without HTTP route such as
+    /// `realtime/validate`, fail with an `SdkError`, without being sent, on
+    /// protocols which cannot receive notifications such as `Http`.
+    ///
+    /// A lost connection is re-established first when the protocol is set
+    /// to auto-reconnect. Once reconnected, the subscriptions are renewed
+    /// (see `resubscribe_all`) and the offline queue replayed, when the
+    /// protocol is set to auto-resubscribe and auto-replay.
     pub fn query(
         &self,
         req: KuzzleRequest,
         options: QueryOptions,
-    ) -> Result<KuzzleResponse, Box<Error>> {
-        self._protocol.send(req, options)
+    ) -> Result<KuzzleResponse, Box<dyn Error>> {
+        self.recover_connection()?;
+        let protocol = self.protocol_for(&req);
+        check_protocol("Kuzzle::query", protocol, &req)?;
+        check_connection(
+            protocol,
+            &options,
+            &format!("request {}/{}", req.controller(), req.action()),
+        )?;
+        if !is_read_action(&req) {
+            self.invalidate_cached(&req);
+        }
+
+        let req = self.prepare(req, &options);
+        protocol.send(req, options)
+    }
+
+    /// Sends several requests at once, returning their responses in the
+    /// same order. They are packed in a single round trip if the protocol
+    /// supports it, and sent one after the other otherwise. Each request
+    /// goes through the same checks as with `query`, and gets the stored
+    /// JWT, the global volatile data and the given `QueryOptions`.
+    ///
+    /// A response holding an error does not fail the batch: check each
+    /// `KuzzleResponse::error`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, KuzzleRequest, QueryOptions};
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// let res = k.batch(
+    ///     vec![
+    ///         KuzzleRequest::new("server", "now"),
+    ///         KuzzleRequest::new("index", "list"),
+    ///     ],
+    ///     QueryOptions::new(),
+    /// );
+    /// ```
+    pub fn batch(
+        &self,
+        requests: Vec<KuzzleRequest>,
+        options: QueryOptions,
+    ) -> Result<Vec<KuzzleResponse>, Box<dyn Error>> {
+        self.recover_connection()?;
+        let count = requests.len();
+        let protocol = if requests.iter().all(is_read_action) {
+            self.read_protocol()
+        } else {
+            &*self._protocol
+        };
+        for req in &requests {
+            check_protocol("Kuzzle::batch", protocol, req)?;
+        }
+        check_connection(protocol, &options, &format!("batch of {} requests", count))?;
+        for req in requests.iter().filter(|req| !is_read_action(req)) {
+            self.invalidate_cached(req);
+        }
+        let requests = requests
+            .into_iter()
+            .map(|req| self.prepare(req, &options))
+            .collect();
+
+        let responses = protocol.send_batch(requests, options)?;
+        if responses.len() != count {
+            return Err(Box::new(SdkError::new(
+                "Kuzzle::batch",
+                &format!(
+                    "{} requests sent but {} responses received.",
+                    count,
+                    responses.len()
+                ),
+            )));
+        }
+        Ok(responses)
+    }
+
+    /// The protocol a request is sent through: the read protocol for read
+    /// actions, if any, the main protocol otherwise.
+    fn protocol_for(&self, req: &KuzzleRequest) -> &dyn Protocol {
+        if is_read_action(req) {
+            self.read_protocol()
+        } else {
+            &*self._protocol
+        }
+    }
+
+    fn read_protocol(&self) -> &dyn Protocol {
+        match &self._read_protocol {
+            Some(protocol) => &**protocol,
+            None => &*self._protocol,
+        }
+    }
+
+    /// Attaches the JWT and volatile data to a request, and turns the
+    /// request-level options into request arguments.
+    fn prepare(&self, req: KuzzleRequest, options: &QueryOptions) -> KuzzleRequest {
+        let mut req = req;
+
+        // auth/checkToken is anonymous: the token to check is in the body.
+        let anonymous = req.controller() == "auth" && req.action() == "checkToken";
+        let jwt = self.jwt();
+        if !jwt.is_empty() && req.jwt().is_none() && !anonymous {
+            req = req.set_jwt(&jwt);
+        }
+
+        let mut volatile = self.global_volatile();
+        volatile.extend(options.volatile().clone());
+        volatile.extend(req.volatile().clone());
+        for (key, value) in volatile {
+            req = req.add_to_volatile(key, value);
+        }
+
+        // Tags the requests able to trigger notifications received by this
+        // client, see `SubscribeOptions::set_subscribe_to_self`.
+        if self.supports_notifications() {
+            req = req.add_to_volatile(
+                "sdkInstanceId".to_string(),
+                Value::from(self._sdk_instance_id.clone()),
+            );
+        }
+
+        if let Some(refresh) = options.refresh().as_query_string() {
+            req = req.add_to_query_strings("refresh".to_string(), Value::from(refresh));
+        }
+
+        if let Some(node) = options.node() {
+            req = req.add_to_query_strings("node".to_string(), Value::from(node.clone()));
+        }
+
+        if let Some(from) = options.from() {
+            req = req.add_to_query_strings("from".to_string(), Value::from(*from));
+        }
+
+        if let Some(size) = options.size() {
+            req = req.add_to_query_strings("size".to_string(), Value::from(*size));
+        }
+
+        if let Some(scroll) = options.scroll() {
+            req = req.add_to_query_strings("scroll".to_string(), Value::from(scroll.clone()));
+        }
+
+        if let Some(lang) = options.lang() {
+            req = req.add_to_query_strings("lang".to_string(), Value::from(lang.clone()));
+        }
+
+        if let Some(version) = options.version() {
+            req = req.add_to_query_strings("version".to_string(), Value::from(*version));
+        }
+
+        if !options.notify() {
+            req = req.add_to_query_strings("notify".to_string(), Value::from(false));
+        }
+
+        if options.silent() {
+            req = req.add_to_query_strings("silent".to_string(), Value::from(true));
+        }
+
+        req
+    }
+
+    /// Cancels a pending request, given its request id.
+    /// A request waiting for its response, e.g. cancelled from a
+    /// notification callback, stops waiting and fails, its late response
+    /// being ignored. A request in the offline queue is dropped from it.
+    /// Returns `false` if no such request is pending.
+    pub fn cancel_request(&self, request_id: &str) -> bool {
+        self._protocol.cancel_request(request_id) || self.read_protocol().cancel_request(request_id)
+    }
+
+    /// Returns the offline queue length, the age of its oldest request and
+    /// the number of requests dropped so far, for backpressure decisions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// let stats = k.queue_stats();
+    /// assert!(stats.is_empty());
+    /// ```
+    pub fn queue_stats(&self) -> QueueStats {
+        self._protocol.queue_stats()
+    }
+
+    /// Sends the requests buffered in the offline queue while the connection
+    /// was lost, oldest first, and returns how many were replayed.
+    /// On failure, the replay stops and the requests not replayed yet stay
+    /// in the queue. Replaying fails while the connection is still down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// assert_eq!(k.replay_queue().unwrap(), 0);
+    /// ```
+    pub fn replay_queue(&self) -> Result<usize, Box<dyn Error>> {
+        self._protocol.replay_queue()
+    }
+
+    /// Stops the running `replay_queue` before its next request, e.g. on
+    /// shutdown, leaving the requests not replayed yet in the queue.
+    pub fn stop_replay(&self) {
+        self._protocol.stop_replay();
+    }
+
+    /// Returns the active realtime subscriptions, oldest first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// assert!(k.subscriptions().is_empty());
+    /// ```
+    pub fn subscriptions(&self) -> Vec<SubscriptionInfo> {
+        self._subscriptions
+            .borrow()
+            .iter()
+            .map(|subscription| subscription.info.clone())
+            .collect()
+    }
+
+    /// Removes every active realtime subscription, e.g. during teardown.
+    /// The registry is emptied even if some unsubscriptions fail, the first
+    /// error being returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// assert!(k.unsubscribe_all().is_ok());
+    /// ```
+    pub fn unsubscribe_all(&self) -> Result<(), Box<dyn Error>> {
+        let mut first_error = None;
+
+        for subscription in self.subscriptions() {
+            let res = self
+                .realtime()
+                .unsubscribe(subscription.room_id(), QueryOptions::new());
+            if let Err(err) = res {
+                first_error.get_or_insert(err);
+            }
+        }
+        for subscription in self._subscriptions.borrow_mut().drain(..) {
+            self._protocol.unlisten(subscription.info.channel());
+        }
+
+        match first_error {
+            None => Ok(()),
+            Some(err) => Err(err),
+        }
+    }
+
+    /// Subscribes again to every registered subscription, with the same
+    /// filters, options and callback, as the server forgets the
+    /// subscriptions of a lost connection. Their room ids may change.
+    /// This is done on the first request following a reconnection if the
+    /// protocol is set to auto-resubscribe.
+    ///
+    /// The subscriptions which cannot be renewed are unregistered, the
+    /// first error being returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Websocket;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let k = Kuzzle::new(Websocket::new(KuzzleOptions::new("localhost", 7512)));
+    /// assert!(k.resubscribe_all().is_ok());
+    /// ```
+    pub fn resubscribe_all(&self) -> Result<(), Box<dyn Error>> {
+        let subscriptions = self._subscriptions.borrow().clone();
+        let mut renewed = Vec::new();
+        let mut first_error = None;
+
+        for subscription in subscriptions {
+            self._protocol.unlisten(subscription.info.channel());
+            let res = self
+                .realtime()
+                .resubscribe(&subscription.info, &subscription.options);
+            match res {
+                Ok(info) => {
+                    self.listen(info.channel(), &subscription.callback);
+                    renewed.push(Subscription {
+                        info,
+                        ..subscription
+                    });
+                }
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        *self._subscriptions.borrow_mut() = renewed;
+
+        match first_error {
+            None => Ok(()),
+            Some(err) => Err(err),
+        }
+    }
+
+    /// Re-establishes the lost connections, see
+    /// `KuzzleOptions::set_auto_reconnect`. Once reconnected, the
+    /// subscriptions are renewed and the offline queue replayed, as set by
+    /// `KuzzleOptions::set_auto_resubscribe` and `set_auto_replay`.
+    fn recover_connection(&self) -> Result<(), Box<dyn Error>> {
+        self._protocol.reconnect();
+        if let Some(read_protocol) = &self._read_protocol {
+            read_protocol.reconnect();
+        }
+
+        if self._protocol.take_reconnection() {
+            if self._protocol.auto_resubscribe() {
+                self.resubscribe_all()?;
+            }
+            if self._protocol.auto_replay() {
+                self._protocol.replay_queue()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn listen(&self, channel: &str, callback: &Rc<dyn Fn(Notification)>) {
+        let callback = callback.clone();
+        self._protocol.listen(
+            channel,
+            Box::new(move |notification| callback(notification)),
+        );
+    }
+
+    /// Whether realtime notifications can be received, i.e. whether
+    /// subscriptions are supported by the protocol.
+    pub(crate) fn supports_notifications(&self) -> bool {
+        self._protocol.supports_notifications()
+    }
+
+    pub(crate) fn add_subscription(
+        &self,
+        info: SubscriptionInfo,
+        options: SubscribeOptions,
+        callback: Box<dyn Fn(Notification)>,
+    ) {
+        let callback: Rc<dyn Fn(Notification)> = Rc::from(callback);
+        self.listen(info.channel(), &callback);
+        self._subscriptions.borrow_mut().push(Subscription {
+            info,
+            options,
+            callback,
+        });
+    }
+
+    pub(crate) fn remove_subscription(&self, room_id: &str) {
+        let mut subscriptions = self._subscriptions.borrow_mut();
+        for subscription in subscriptions.iter() {
+            if subscription.info.room_id() == room_id {
+                self._protocol.unlisten(subscription.info.channel());
+            }
+        }
+        subscriptions.retain(|subscription| subscription.info.room_id() != room_id);
+    }
+
+    /// Enables or disables the in-memory cache of collection mappings,
+    /// which is disabled by default. Once enabled,
+    /// `CollectionController::get_mapping` only asks Kuzzle for a mapping
+    /// which is not cached yet, and the requests changing a mapping, even
+    /// when sent with `query` or `batch`, drop the cached entry. Disabling
+    /// the cache clears it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// k.set_mapping_cache(true);
+    /// assert!(k.mapping_cache());
+    /// ```
+    pub fn set_mapping_cache(&self, enabled: bool) {
+        self._mapping_cache.set(enabled);
+        if !enabled {
+            self.clear_mapping_cache();
+        }
+    }
+
+    /// Whether the collection mappings cache is enabled.
+    pub fn mapping_cache(&self) -> bool {
+        self._mapping_cache.get()
+    }
+
+    /// Drops every cached collection mapping, e.g. after they have been
+    /// changed by another client.
+    pub fn clear_mapping_cache(&self) {
+        self._mappings.borrow_mut().clear();
+    }
+
+    pub(crate) fn cached_mapping(&self, index: &str, collection: &str) -> Option<Value> {
+        self._mappings
+            .borrow()
+            .get(&(index.to_string(), collection.to_string()))
+            .cloned()
+    }
+
+    pub(crate) fn cache_mapping(&self, index: &str, collection: &str, mapping: Value) {
+        if self.mapping_cache() {
+            self._mappings
+                .borrow_mut()
+                .insert((index.to_string(), collection.to_string()), mapping);
+        }
+    }
+
+    fn invalidate_mapping(&self, index: &str, collection: &str) {
+        self._mappings
+            .borrow_mut()
+            .remove(&(index.to_string(), collection.to_string()));
+    }
+
+    fn invalidate_index_mappings(&self, index: &str) {
+        self._mappings.borrow_mut().retain(|(i, _), _| i != index);
+    }
+
+    /// Enables or disables the in-memory cache of documents, which is
+    /// disabled by default. Once enabled, `DocumentController::get` only
+    /// asks Kuzzle for a document which is not cached yet, and the writes
+    /// made through this client, including the requests sent with `query`
+    /// or `batch`, drop the cached copies of the documents they change.
+    /// Kuzzle has no conditional fetch and writes made by other clients are
+    /// not seen, so a cached document is only served for
+    /// `document_cache_ttl` (60 seconds by default), and at most 10,000
+    /// documents are kept, the oldest ones being dropped first. Disabling
+    /// the cache clears it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// k.set_document_cache(true);
+    /// assert!(k.document_cache());
+    /// ```
+    pub fn set_document_cache(&self, enabled: bool) {
+        self._document_cache.set(enabled);
+        if !enabled {
+            self.clear_document_cache();
+        }
+    }
+
+    /// Whether the documents cache is enabled.
+    pub fn document_cache(&self) -> bool {
+        self._document_cache.get()
+    }
+
+    /// Sets how long a cached document is served before being fetched
+    /// again from Kuzzle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use std::time::Duration;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// k.set_document_cache_ttl(Duration::from_secs(5));
+    /// assert_eq!(k.document_cache_ttl(), Duration::from_secs(5));
+    /// ```
+    pub fn set_document_cache_ttl(&self, ttl: Duration) {
+        self._document_cache_ttl.set(ttl);
+    }
+
+    /// How long a cached document is served.
+    pub fn document_cache_ttl(&self) -> Duration {
+        self._document_cache_ttl.get()
+    }
+
+    /// Drops every cached document.
+    pub fn clear_document_cache(&self) {
+        self._documents.borrow_mut().clear();
+    }
+
+    pub(crate) fn cached_document(
+        &self,
+        index: &str,
+        collection: &str,
+        id: &str,
+    ) -> Option<Document> {
+        let key = (index.to_string(), collection.to_string(), id.to_string());
+        let mut documents = self._documents.borrow_mut();
+        match documents.get(&key) {
+            Some((document, cached_at)) if cached_at.elapsed() < self.document_cache_ttl() => {
+                Some(document.clone())
+            }
+            Some(_) => {
+                documents.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn cache_document(&self, index: &str, collection: &str, document: Document) {
+        if !self.document_cache() {
+            return;
+        }
+
+        let ttl = self.document_cache_ttl();
+        let mut documents = self._documents.borrow_mut();
+        if documents.len() >= DOCUMENT_CACHE_MAX_ENTRIES {
+            documents.retain(|_, (_, cached_at)| cached_at.elapsed() < ttl);
+        }
+        if documents.len() >= DOCUMENT_CACHE_MAX_ENTRIES {
+            let oldest = documents
+                .iter()
+                .min_by_key(|(_, (_, cached_at))| *cached_at)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                documents.remove(&key);
+            }
+        }
+        documents.insert(
+            (
+                index.to_string(),
+                collection.to_string(),
+                document.id().clone(),
+            ),
+            (document, Instant::now()),
+        );
+    }
+
+    fn invalidate_document(&self, index: &str, collection: &str, id: &str) {
+        self._documents.borrow_mut().remove(&(
+            index.to_string(),
+            collection.to_string(),
+            id.to_string(),
+        ));
+    }
+
+    fn invalidate_documents(&self, index: &str, collection: &str) {
+        self._documents
+            .borrow_mut()
+            .retain(|(i, c, _), _| i != index || c != collection);
+    }
+
+    fn invalidate_index_documents(&self, index: &str) {
+        self._documents
+            .borrow_mut()
+            .retain(|(i, _, _), _| i != index);
+    }
+
+    /// Drops the cached documents and mappings a write request may change.
+    /// Documents are dropped by id when the request names them, and with
+    /// their whole collection otherwise.
+    fn invalidate_cached(&self, req: &KuzzleRequest) {
+        let index = req.index().as_ref().map_or("", String::as_str);
+        let collection = req.collection().as_ref().map_or("", String::as_str);
+        match (req.controller().as_str(), req.action().as_str()) {
+            ("document", _) => match (req.id(), written_ids(req)) {
+                (Some(id), _) => self.invalidate_document(index, collection, id),
+                (None, Some(ids)) => {
+                    for id in ids {
+                        self.invalidate_document(index, collection, id);
+                    }
+                }
+                (None, None) => self.invalidate_documents(index, collection),
+            },
+            ("bulk", _) => self.invalidate_documents(index, collection),
+            ("collection", "truncate") | ("collection", "delete") => {
+                self.invalidate_documents(index, collection);
+                self.invalidate_mapping(index, collection);
+            }
+            ("collection", "updateMapping") => self.invalidate_mapping(index, collection),
+            ("index", "delete") => {
+                self.invalidate_index_documents(index);
+                self.invalidate_index_mappings(index);
+            }
+            ("index", "mDelete") => {
+                let indexes = req.body().get("indexes").and_then(Value::as_array);
+                for index in indexes.into_iter().flatten().filter_map(Value::as_str) {
+                    self.invalidate_index_documents(index);
+                    self.invalidate_index_mappings(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Sets volatile data sent along with every request, e.g. a tenant id
+    /// read by a Kuzzle plugin. A request can override its entries through
+    /// `QueryOptions::set_volatile`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use serde_json::{json, Map};
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// let mut volatile = Map::new();
+    /// volatile.insert("tenantId".to_string(), json!("ferris-corp"));
+    /// k.set_global_volatile(volatile);
+    /// assert_eq!(k.global_volatile()["tenantId"], "ferris-corp");
+    /// ```
+    pub fn set_global_volatile(&self, volatile: Map<String, Value>) {
+        *self._volatile.borrow_mut() = volatile;
+    }
+
+    /// Kuzzle global volatile data getter.
+    pub fn global_volatile(&self) -> Map<String, Value> {
+        self._volatile.borrow().clone()
+    }
+
+    /// Fetches the limits advertised by `server/getConfig` and keeps them,
+    /// so that `BulkController::import_ndjson` splits its chunks and
+    /// `DocumentController::search` checks its pagination against the
+    /// values of this deployment rather than Kuzzle's defaults.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// let res = k.load_server_limits();
+    /// ```
+    pub fn load_server_limits(&self) -> Result<ServerLimits, Box<dyn Error>> {
+        let config = self.server().get_config()?;
+        let limits: ServerLimits = match config.get("limits") {
+            Some(limits) => serde_json::from_value(limits.clone())?,
+            None => ServerLimits::default(),
+        };
+        self._server_limits.set(limits);
+        Ok(limits)
+    }
+
+    /// Kuzzle server limits getter, Kuzzle's defaults unless
+    /// `load_server_limits` was called.
+    pub fn server_limits(&self) -> ServerLimits {
+        self._server_limits.get()
+    }
+
+    /// Identifies this client in the volatile data of its requests, and of
+    /// the notifications they trigger.
+    pub fn sdk_instance_id(&self) -> &str {
+        &self._sdk_instance_id
     }
 
     /// Kuzzle JWT getter
     pub fn jwt(&self) -> String {
-        self._jwt.clone()
+        self._jwt.borrow().clone()
     }
 
-    /// Kuzzle JWT setter
-    pub fn set_jwt(&mut self, jwt: String) {
-        self._jwt = jwt;
+    /// Kuzzle JWT setter.
+    /// Takes `&self` so controllers, such as `AuthController::login`,
+    /// can store the token they obtain.
+    pub fn set_jwt(&self, jwt: String) {
+        *self._jwt.borrow_mut() = jwt;
     }
 
     /// Kuzzle AuthController's getter
-    pub fn auth(&self) -> AuthController {
-        AuthController(&self)
+    pub fn auth(&self) -> AuthController<'_> {
+        AuthController(self)
     }
 
     /// Kuzzle BulkController's getter
-    pub fn bulk(&self) -> BulkController {
-        BulkController(&self)
+    pub fn bulk(&self) -> BulkController<'_> {
+        BulkController(self)
     }
 
     /// Kuzzle CollectionController's getter
-    pub fn collection(&self) -> CollectionController {
-        CollectionController(&self)
+    pub fn collection(&self) -> CollectionController<'_> {
+        CollectionController(self)
     }
 
     /// Kuzzle DocumentController's getter
-    pub fn document(&self) -> DocumentController {
-        DocumentController(&self)
+    pub fn document(&self) -> DocumentController<'_> {
+        DocumentController(self)
     }
 
     /// Kuzzle IndexController's getter
-    pub fn index(&self) -> IndexController {
-        IndexController(&self)
+    pub fn index(&self) -> IndexController<'_> {
+        IndexController(self)
     }
 
     /// Kuzzle MemoryStorageController's getter
-    pub fn ms(&self) -> MemoryStorageController {
-        MemoryStorageController(&self)
+    pub fn ms(&self) -> MemoryStorageController<'_> {
+        MemoryStorageController(self)
     }
 
     /// Kuzzle RealtimeController's getter
-    pub fn realtime(&self) -> RealtimeController {
-        RealtimeController(&self)
+    pub fn realtime(&self) -> RealtimeController<'_> {
+        RealtimeController(self)
     }
 
     /// Kuzzle SecurityController's getter
-    pub fn security(&self) -> SecurityController {
-        SecurityController(&self)
+    pub fn security(&self) -> SecurityController<'_> {
+        SecurityController(self)
     }
 
     /// Kuzzle ServerController's getter
-    pub fn server(&self) -> ServerController {
-        ServerController(&self)
+    pub fn server(&self) -> ServerController<'_> {
+        ServerController(self)
+    }
+}
+
+/// How long a cached document is served by default.
+const DEFAULT_DOCUMENT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// How many documents the documents cache keeps at most.
+const DOCUMENT_CACHE_MAX_ENTRIES: usize = 10_000;
+
+/// Memory storage commands which only read data.
+const MS_READ_ACTIONS: &[&str] = &[
+    "bitcount",
+    "bitpos",
+    "dbsize",
+    "exists",
+    "geodist",
+    "geohash",
+    "geopos",
+    "georadius",
+    "georadiusbymember",
+    "get",
+    "getbit",
+    "getrange",
+    "hexists",
+    "hget",
+    "hgetall",
+    "hkeys",
+    "hlen",
+    "hmget",
+    "hscan",
+    "hstrlen",
+    "hvals",
+    "keys",
+    "lindex",
+    "llen",
+    "lrange",
+    "mget",
+    "object",
+    "pfcount",
+    "pttl",
+    "randomkey",
+    "scan",
+    "scard",
+    "sdiff",
+    "sinter",
+    "sismember",
+    "smembers",
+    "srandmember",
+    "sscan",
+    "strlen",
+    "sunion",
+    "time",
+    "ttl",
+    "type",
+    "zcard",
+    "zcount",
+    "zlexcount",
+    "zrange",
+    "zrangebylex",
+    "zrangebyscore",
+    "zrank",
+    "zrevrange",
+    "zrevrangebylex",
+    "zrevrangebyscore",
+    "zrevrank",
+    "zscan",
+    "zscore",
+];
+
+/// Actions only available on protocols receiving notifications.
+const NOTIFICATION_ACTIONS: &[(&str, &str)] = &[("realtime", "join"), ("realtime", "subscribe")];
+
+/// Actions without HTTP route, only exposed on Kuzzle's WebSocket entry point.
+const WEBSOCKET_ONLY_ACTIONS: &[(&str, &str)] = &[("realtime", "validate")];
+
+/// Checks that the protocol is able to carry out the request.
+fn check_protocol(
+    cause: &str,
+    protocol: &dyn Protocol,
+    req: &KuzzleRequest,
+) -> Result<(), SdkError> {
+    let action = (req.controller().as_str(), req.action().as_str());
+    if NOTIFICATION_ACTIONS.contains(&action) && !protocol.supports_notifications() {
+        return Err(SdkError::new(
+            cause,
+            "subscriptions require the WebSocket protocol.",
+        ));
+    }
+    if WEBSOCKET_ONLY_ACTIONS.contains(&action) && !protocol.supports_notifications() {
+        return Err(SdkError::new(
+            cause,
+            &format!("{}/{} requires the WebSocket protocol.", action.0, action.1),
+        ));
+    }
+    Ok(())
+}
+
+/// Fails with a `ConnectionError` when the protocol is known to be offline
+/// and the request, described by `what`, is not to be queued.
+fn check_connection(
+    protocol: &dyn Protocol,
+    options: &QueryOptions,
+    what: &str,
+) -> Result<(), ConnectionError> {
+    if protocol.state() == ConnectionState::Offline && !options.queuable() {
+        return Err(ConnectionError::new(&format!(
+            "Unable to execute {}: not connected to Kuzzle.",
+            what
+        )));
+    }
+    Ok(())
+}
+
+/// Returns an id unique to a Kuzzle client, within and across processes.
+fn new_sdk_instance_id() -> String {
+    static INSTANCES: AtomicUsize = AtomicUsize::new(0);
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    format!(
+        "{:x}-{:x}-{:x}",
+        nanos,
+        process::id(),
+        INSTANCES.fetch_add(1, Ordering::SeqCst)
+    )
+}
+
+/// The ids of the documents a multiple-documents write names in its body,
+/// e.g. `document/mDelete` or `document/mUpdate`, if it names all of them.
+fn written_ids(req: &KuzzleRequest) -> Option<Vec<&str>> {
+    let body = req.body();
+    if let Some(ids) = body.get("ids").and_then(Value::as_array) {
+        return ids.iter().map(Value::as_str).collect();
+    }
+    body.get("documents")
+        .and_then(Value::as_array)?
+        .iter()
+        .map(|document| document.get("_id").and_then(Value::as_str))
+        .collect()
+}
+
+/// Whether an action only reads data, so it can be served by a read replica.
+/// Authentication and realtime actions are tied to the connection they are
+/// made on, and never considered as reads.
+fn is_read_action(req: &KuzzleRequest) -> bool {
+    let action = req.action().as_str();
+    match req.controller().as_str() {
+        "auth" | "realtime" | "bulk" => false,
+        "server" => true,
+        "ms" => MS_READ_ACTIONS.contains(&action),
+        _ => {
+            ["count", "exists", "list", "validate"].contains(&action)
+                || action.starts_with("get")
+                || action.starts_with("mGet")
+                || action.starts_with("search")
+                || action.starts_with("scroll")
+        }
+    }
+}
+
+impl fmt::Debug for Kuzzle {
+    // Never print the JWT: the client may end up in logs.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let jwt = if self._jwt.borrow().is_empty() {
+            ""
+        } else {
+            "***"
+        };
+        f.debug_struct("Kuzzle").field("jwt", &jwt).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::{Http, Websocket};
+    use crate::types::{KuzzleOptions, RefreshMode, SubscribeOptions};
+    use mockito::{self, Matcher};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// A protocol stuck in the given state, counting the requests sent.
+    struct FakeProtocol {
+        state: ConnectionState,
+        sent: Rc<Cell<usize>>,
+    }
+
+    impl Protocol for FakeProtocol {
+        fn once(&self) {}
+        fn listener_count(&self) {}
+        fn connect(&self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+        fn send(
+            &self,
+            req: KuzzleRequest,
+            _options: QueryOptions,
+        ) -> Result<KuzzleResponse, Box<dyn Error>> {
+            self.sent.set(self.sent.get() + 1);
+            let result = match req.action().as_str() {
+                "subscribe" => serde_json::json!({
+                    "roomId": format!("room-{}", self.sent.get()),
+                    "channel": format!("channel-{}", self.sent.get())
+                }),
+                "unsubscribe" => serde_json::json!({ "roomId": req.body()["roomId"] }),
+                _ => serde_json::json!({ "now": 1928374619383u64 }),
+            };
+            Ok(serde_json::from_value(serde_json::json!({
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "controller": req.controller(),
+                "action": req.action(),
+                "result": result
+            }))?)
+        }
+        fn supports_notifications(&self) -> bool {
+            true
+        }
+        fn cancel_request(&self, _request_id: &str) -> bool {
+            false
+        }
+        fn close(&self) {}
+        fn state(&self) -> ConnectionState {
+            self.state
+        }
+        fn request_history(&self) {}
+        fn start_queuing(&self) {}
+        fn stop_queuing(&self) {}
+        fn clear_queue(&self) {}
+        fn queue_stats(&self) -> QueueStats {
+            QueueStats::default()
+        }
+    }
+
+    fn fake_kuzzle(state: ConnectionState) -> (Kuzzle, Rc<Cell<usize>>) {
+        let sent = Rc::new(Cell::new(0));
+        let protocol = FakeProtocol {
+            state,
+            sent: sent.clone(),
+        };
+        (Kuzzle::new(protocol), sent)
+    }
+
+    #[test]
+    fn subscriptions_reflect_subscribe_and_unsubscribe() {
+        let (k, _) = fake_kuzzle(ConnectionState::Connected);
+        let filters = serde_json::json!({ "equals": { "species": "crab" } });
+
+        let room_id = k
+            .realtime()
+            .subscribe(
+                "ferris_index",
+                "crabs",
+                filters.clone(),
+                Box::new(|_| {}),
+                SubscribeOptions::new(),
+            )
+            .unwrap();
+        let subscriptions = k.subscriptions();
+        assert_eq!(subscriptions.len(), 1);
+        assert_eq!(subscriptions[0].room_id(), &room_id);
+        assert_eq!(subscriptions[0].channel(), "channel-1");
+        assert_eq!(subscriptions[0].collection(), "crabs");
+        assert_eq!(subscriptions[0].filters(), &filters);
+
+        k.realtime()
+            .unsubscribe(&room_id, QueryOptions::new())
+            .unwrap();
+        assert!(k.subscriptions().is_empty());
+    }
+
+    type Channels = Rc<RefCell<Vec<String>>>;
+
+    /// A protocol whose connection is re-established on demand, keeping
+    /// the channels listened to.
+    struct ReconnectingProtocol {
+        inner: FakeProtocol,
+        auto_resubscribe: bool,
+        reconnected: Rc<Cell<bool>>,
+        channels: Channels,
+    }
+
+    impl Protocol for ReconnectingProtocol {
+        fn once(&self) {}
+        fn listener_count(&self) {}
+        fn connect(&self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+        fn send(
+            &self,
+            req: KuzzleRequest,
+            options: QueryOptions,
+        ) -> Result<KuzzleResponse, Box<dyn Error>> {
+            self.inner.send(req, options)
+        }
+        fn supports_notifications(&self) -> bool {
+            true
+        }
+        fn listen(&self, channel: &str, _callback: Box<dyn Fn(Notification)>) {
+            self.channels.borrow_mut().push(channel.to_string());
+        }
+        fn unlisten(&self, channel: &str) {
+            self.channels
+                .borrow_mut()
+                .retain(|listened| listened != channel);
+        }
+        fn auto_resubscribe(&self) -> bool {
+            self.auto_resubscribe
+        }
+        fn take_reconnection(&self) -> bool {
+            self.reconnected.replace(false)
+        }
+        fn cancel_request(&self, _request_id: &str) -> bool {
+            false
+        }
+        fn close(&self) {}
+        fn state(&self) -> ConnectionState {
+            ConnectionState::Connected
+        }
+        fn request_history(&self) {}
+        fn start_queuing(&self) {}
+        fn stop_queuing(&self) {}
+        fn clear_queue(&self) {}
+        fn queue_stats(&self) -> QueueStats {
+            QueueStats::default()
+        }
+    }
+
+    fn subscribed_reconnecting_kuzzle(
+        auto_resubscribe: bool,
+    ) -> (Kuzzle, Rc<Cell<usize>>, Rc<Cell<bool>>, Channels) {
+        let sent = Rc::new(Cell::new(0));
+        let reconnected = Rc::new(Cell::new(false));
+        let channels = Rc::new(RefCell::new(Vec::new()));
+        let k = Kuzzle::new(ReconnectingProtocol {
+            inner: FakeProtocol {
+                state: ConnectionState::Connected,
+                sent: sent.clone(),
+            },
+            auto_resubscribe,
+            reconnected: reconnected.clone(),
+            channels: channels.clone(),
+        });
+        for collection in &["crabs", "lobsters"] {
+            k.realtime()
+                .subscribe(
+                    "ferris_index",
+                    collection,
+                    serde_json::json!({ "equals": { "species": collection } }),
+                    Box::new(|_| {}),
+                    SubscribeOptions::new(),
+                )
+                .unwrap();
+        }
+        (k, sent, reconnected, channels)
+    }
+
+    #[test]
+    fn query_resubscribes_after_reconnection() {
+        let (k, sent, reconnected, channels) = subscribed_reconnecting_kuzzle(true);
+        assert_eq!(*channels.borrow(), vec!["channel-1", "channel-2"]);
+
+        reconnected.set(true);
+        k.query(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+
+        // Both subscriptions are renewed before the request itself.
+        assert_eq!(sent.get(), 5);
+        assert_eq!(*channels.borrow(), vec!["channel-3", "channel-4"]);
+        let subscriptions = k.subscriptions();
+        assert_eq!(subscriptions[0].room_id(), "room-3");
+        assert_eq!(subscriptions[0].collection(), "crabs");
+        assert_eq!(
+            subscriptions[1].filters(),
+            &serde_json::json!({ "equals": { "species": "lobsters" } })
+        );
+
+        k.query(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+        assert_eq!(sent.get(), 6);
+    }
+
+    #[test]
+    fn query_no_resubscription_when_disabled() {
+        let (k, sent, reconnected, channels) = subscribed_reconnecting_kuzzle(false);
+
+        reconnected.set(true);
+        k.query(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+
+        assert_eq!(sent.get(), 3);
+        assert_eq!(*channels.borrow(), vec!["channel-1", "channel-2"]);
+    }
+
+    #[test]
+    fn check_protocol_subscriptions() {
+        let subscribe = KuzzleRequest::new("realtime", "subscribe");
+        let http = Http::new(KuzzleOptions::new("localhost", 7512));
+        let websocket = Websocket::new(KuzzleOptions::new("localhost", 7512));
+
+        let err = check_protocol("Kuzzle::query", &http, &subscribe).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Kuzzle::query] subscriptions require the WebSocket protocol."
+        );
+        assert!(check_protocol("Kuzzle::query", &websocket, &subscribe).is_ok());
+        assert!(check_protocol(
+            "Kuzzle::query",
+            &http,
+            &KuzzleRequest::new("realtime", "publish")
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn check_protocol_websocket_only_actions() {
+        let validate = KuzzleRequest::new("realtime", "validate");
+        let http = Http::new(KuzzleOptions::new("localhost", 7512));
+        let websocket = Websocket::new(KuzzleOptions::new("localhost", 7512));
+
+        let err = check_protocol("Kuzzle::query", &http, &validate).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Kuzzle::query] realtime/validate requires the WebSocket protocol."
+        );
+        assert!(check_protocol("Kuzzle::query", &websocket, &validate).is_ok());
+    }
+
+    #[test]
+    fn batch_fail_subscription_over_http() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+
+        let err = k
+            .batch(
+                vec![
+                    KuzzleRequest::new("server", "now"),
+                    KuzzleRequest::new("realtime", "subscribe"),
+                ],
+                QueryOptions::new(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Kuzzle::batch] subscriptions require the WebSocket protocol."
+        );
+    }
+
+    #[test]
+    fn batch_fail_fast_offline_non_queuable() {
+        let (k, sent) = fake_kuzzle(ConnectionState::Offline);
+        let err = k
+            .batch(
+                vec![
+                    KuzzleRequest::new("server", "now"),
+                    KuzzleRequest::new("server", "info"),
+                ],
+                QueryOptions::new().set_queuable(false),
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<ConnectionError>().unwrap().message(),
+            "Unable to execute batch of 2 requests: not connected to Kuzzle."
+        );
+        assert_eq!(sent.get(), 0);
+    }
+
+    #[test]
+    fn batch_ok_forwards_options() {
+        let _now = mockito::mock("GET", "/_now?silent=true")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "now": 1447151167622 }
+                }"#,
+            )
+            .expect(2)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let responses = k
+            .batch(
+                vec![
+                    KuzzleRequest::new("server", "now"),
+                    KuzzleRequest::new("server", "now"),
+                ],
+                QueryOptions::new().set_silent(true),
+            )
+            .unwrap();
+
+        _now.assert();
+        assert_eq!(responses.len(), 2);
+    }
+
+    #[test]
+    fn unsubscribe_all_empties_registry() {
+        let (k, sent) = fake_kuzzle(ConnectionState::Connected);
+        for collection in &["crabs", "lobsters"] {
+            k.realtime()
+                .subscribe(
+                    "ferris_index",
+                    collection,
+                    serde_json::json!({}),
+                    Box::new(|_| {}),
+                    SubscribeOptions::new(),
+                )
+                .unwrap();
+        }
+        assert_eq!(k.subscriptions().len(), 2);
+
+        assert!(k.unsubscribe_all().is_ok());
+        assert!(k.subscriptions().is_empty());
+        assert_eq!(sent.get(), 4);
+    }
+
+    #[test]
+    fn debug_redacts_jwt() {
+        let (k, _) = fake_kuzzle(ConnectionState::Connected);
+        k.set_jwt("ferris-jwt".to_string());
+        let output = format!("{:?}", k);
+
+        assert!(!output.contains("ferris-jwt"));
+        assert_eq!(output, r#"Kuzzle { jwt: "***" }"#);
+    }
+
+    #[test]
+    fn query_fail_fast_offline_non_queuable() {
+        let (k, sent) = fake_kuzzle(ConnectionState::Offline);
+        let res = k.query(
+            KuzzleRequest::new("server", "now"),
+            QueryOptions::new().set_queuable(false),
+        );
+
+        assert!(res.unwrap_err().downcast_ref::<ConnectionError>().is_some());
+        assert_eq!(sent.get(), 0);
+    }
+
+    #[test]
+    fn query_offline_queuable_is_sent() {
+        let (k, sent) = fake_kuzzle(ConnectionState::Offline);
+        let res = k.query(KuzzleRequest::new("server", "now"), QueryOptions::new());
+
+        assert!(res.is_ok());
+        assert_eq!(sent.get(), 1);
+    }
+
+    #[test]
+    fn query_connected_non_queuable_is_sent() {
+        let (k, sent) = fake_kuzzle(ConnectionState::Connected);
+        let res = k.query(
+            KuzzleRequest::new("server", "now"),
+            QueryOptions::new().set_queuable(false),
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(sent.get(), 1);
+    }
+
+    #[test]
+    fn query_forwards_node() {
+        let _m = mockito::mock("GET", "/_now?node=knode-crab-42")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "now": 1928374619383
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.query(
+            KuzzleRequest::new("server", "now"),
+            QueryOptions::new().set_node("knode-crab-42"),
+        );
+
+        _m.assert();
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn query_forwards_refresh_wait_for() {
+        let _m = mockito::mock("POST", "/ferris_index/_create?refresh=wait_for")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "index",
+                    "action": "create",
+                    "collection": null,
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "acknowledged": true,
+                        "shards_acknowledged": true
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.query(
+            KuzzleRequest::new("index", "create").set_index("ferris_index"),
+            QueryOptions::new().set_refresh(RefreshMode::WaitFor),
+        );
+
+        _m.assert();
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn query_omits_refresh_false() {
+        let _m = mockito::mock("POST", "/ferris_index/_create")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "index",
+                    "action": "create",
+                    "collection": null,
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "acknowledged": true,
+                        "shards_acknowledged": true
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.query(
+            KuzzleRequest::new("index", "create").set_index("ferris_index"),
+            QueryOptions::new().set_refresh(RefreshMode::False),
+        );
+
+        _m.assert();
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn query_sends_global_volatile() {
+        let _now = mockito::mock("GET", "/_now")
+            .match_header(
+                "x-kuzzle-volatile",
+                Matcher::Json(serde_json::json!({ "tenantId": "ferris-corp" })),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": { "tenantId": "ferris-corp" },
+                    "result": { "now": 1928374619383 }
+                }"#,
+            )
+            .expect(1)
+            .create();
+        let _create = mockito::mock("POST", "/ferris_index/_create")
+            .match_header(
+                "x-kuzzle-volatile",
+                Matcher::Json(serde_json::json!({ "tenantId": "ferris-corp" })),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "index",
+                    "action": "create",
+                    "collection": null,
+                    "index": "ferris_index",
+                    "volatile": { "tenantId": "ferris-corp" },
+                    "result": { "acknowledged": true }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut volatile = Map::new();
+        volatile.insert("tenantId".to_string(), Value::from("ferris-corp"));
+        k.set_global_volatile(volatile);
+
+        assert!(k
+            .query(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .is_ok());
+        assert!(k
+            .query(
+                KuzzleRequest::new("index", "create").set_index("ferris_index"),
+                QueryOptions::new(),
+            )
+            .is_ok());
+
+        _now.assert();
+        _create.assert();
+    }
+
+    #[test]
+    fn query_volatile_overrides_global_volatile() {
+        let _m = mockito::mock("GET", "/_now")
+            .match_header(
+                "x-kuzzle-volatile",
+                Matcher::Json(serde_json::json!({
+                    "tenantId": "crab-corp",
+                    "origin": "migration"
+                })),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "now": 1928374619383 }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut global = Map::new();
+        global.insert("tenantId".to_string(), Value::from("ferris-corp"));
+        global.insert("origin".to_string(), Value::from("migration"));
+        k.set_global_volatile(global);
+
+        let mut volatile = Map::new();
+        volatile.insert("tenantId".to_string(), Value::from("crab-corp"));
+        let res = k.query(
+            KuzzleRequest::new("server", "now"),
+            QueryOptions::new().set_volatile(volatile),
+        );
+
+        assert!(res.is_ok());
+        _m.assert();
+    }
+
+    #[test]
+    fn query_forwards_notify_and_silent_independently() {
+        let response = r#"{
+            "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+            "status": 200,
+            "error": null,
+            "controller": "document",
+            "action": "create",
+            "collection": "ferris_collection",
+            "index": "ferris_index",
+            "volatile": null,
+            "result": { "_id": "ferris", "_version": 1 }
+        }"#;
+        let _quiet = mockito::mock(
+            "POST",
+            "/ferris_index/ferris_collection/_create?notify=false",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response)
+        .expect(1)
+        .create();
+        let _silent = mockito::mock(
+            "POST",
+            "/ferris_index/ferris_collection/_create?silent=true",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response)
+        .expect(1)
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let body = serde_json::json!({ "name": "Ferris" });
+        let quiet = k.document().create(
+            "ferris_index",
+            "ferris_collection",
+            None,
+            body.clone(),
+            QueryOptions::new().set_notify(false),
+        );
+        let silent = k.document().create(
+            "ferris_index",
+            "ferris_collection",
+            None,
+            body,
+            QueryOptions::new().set_silent(true),
+        );
+
+        assert!(quiet.is_ok());
+        assert!(silent.is_ok());
+        _quiet.assert();
+        _silent.assert();
+    }
+
+    fn cached(id: &str) -> Document {
+        serde_json::from_value(serde_json::json!({
+            "_id": id,
+            "_version": 1,
+            "_source": { "name": id }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn query_ok_invalidates_written_documents() {
+        let (k, _) = fake_kuzzle(ConnectionState::Connected);
+        k.set_document_cache(true);
+        k.cache_document("ferris_index", "crabs", cached("ferris"));
+        k.cache_document("ferris_index", "crabs", cached("crab"));
+        k.cache_document("ferris_index", "crabs", cached("lobster"));
+
+        k.query(
+            KuzzleRequest::new("document", "get")
+                .set_index("ferris_index")
+                .set_collection("crabs")
+                .set_id("ferris"),
+            QueryOptions::new(),
+        )
+        .unwrap();
+        assert!(k
+            .cached_document("ferris_index", "crabs", "ferris")
+            .is_some());
+
+        k.query(
+            KuzzleRequest::new("document", "replace")
+                .set_index("ferris_index")
+                .set_collection("crabs")
+                .set_id("ferris"),
+            QueryOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(k.cached_document("ferris_index", "crabs", "ferris"), None);
+        assert!(k.cached_document("ferris_index", "crabs", "crab").is_some());
+
+        k.query(
+            KuzzleRequest::new("document", "mDelete")
+                .set_index("ferris_index")
+                .set_collection("crabs")
+                .add_to_body("ids".to_string(), serde_json::json!(["crab"])),
+            QueryOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(k.cached_document("ferris_index", "crabs", "crab"), None);
+        assert!(k
+            .cached_document("ferris_index", "crabs", "lobster")
+            .is_some());
+    }
+
+    #[test]
+    fn batch_ok_invalidates_written_documents() {
+        let (k, _) = fake_kuzzle(ConnectionState::Connected);
+        k.set_document_cache(true);
+        k.set_mapping_cache(true);
+        k.cache_document("ferris_index", "crabs", cached("ferris"));
+        k.cache_document("ferris_index", "lobsters", cached("lobster"));
+        k.cache_document("nemo_index", "fishes", cached("nemo"));
+        k.cache_mapping("nemo_index", "fishes", serde_json::json!({}));
+
+        k.batch(
+            vec![
+                KuzzleRequest::new("bulk", "import")
+                    .set_index("ferris_index")
+                    .set_collection("crabs"),
+                KuzzleRequest::new("document", "get")
+                    .set_index("ferris_index")
+                    .set_collection("lobsters")
+                    .set_id("lobster"),
+            ],
+            QueryOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(k.cached_document("ferris_index", "crabs", "ferris"), None);
+        assert!(k
+            .cached_document("ferris_index", "lobsters", "lobster")
+            .is_some());
+
+        k.batch(
+            vec![KuzzleRequest::new("index", "mDelete").add_to_body(
+                "indexes".to_string(),
+                serde_json::json!(["ferris_index", "nemo_index"]),
+            )],
+            QueryOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            k.cached_document("ferris_index", "lobsters", "lobster"),
+            None
+        );
+        assert_eq!(k.cached_document("nemo_index", "fishes", "nemo"), None);
+        assert_eq!(k.cached_mapping("nemo_index", "fishes"), None);
+    }
+
+    #[test]
+    fn document_cache_ok_expires_after_ttl() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_document_cache(true);
+        assert_eq!(k.document_cache_ttl(), Duration::from_secs(60));
+
+        k.cache_document("ferris_index", "crabs", cached("ferris"));
+        assert_eq!(
+            k.cached_document("ferris_index", "crabs", "ferris"),
+            Some(cached("ferris"))
+        );
+
+        k.set_document_cache_ttl(Duration::from_secs(0));
+        assert_eq!(k.cached_document("ferris_index", "crabs", "ferris"), None);
+    }
+
+    #[test]
+    fn document_cache_ok_drops_oldest_when_full() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.set_document_cache(true);
+        for i in 0..=DOCUMENT_CACHE_MAX_ENTRIES {
+            k.cache_document("ferris_index", "crabs", cached(&format!("crab-{}", i)));
+        }
+
+        assert_eq!(k._documents.borrow().len(), DOCUMENT_CACHE_MAX_ENTRIES);
+        assert_eq!(k.cached_document("ferris_index", "crabs", "crab-0"), None);
+        assert!(k
+            .cached_document(
+                "ferris_index",
+                "crabs",
+                &format!("crab-{}", DOCUMENT_CACHE_MAX_ENTRIES)
+            )
+            .is_some());
+    }
+
+    #[test]
+    fn batch_ok_responses_in_input_order() {
+        let response = |action: &str, result: &str| {
+            format!(
+                r#"{{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "{}",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {}
+                }}"#,
+                action, result
+            )
+        };
+        let _info = mockito::mock("GET", "/_serverInfo")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response("info", r#"{ "serverInfo": {} }"#))
+            .expect(1)
+            .create();
+        let _now = mockito::mock("GET", "/_now")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response("now", r#"{ "now": 1447151167622 }"#))
+            .expect(1)
+            .create();
+        let _list = mockito::mock("GET", "/_list")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response("list", r#"{ "indexes": ["ferris_index"] }"#))
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let responses = k
+            .batch(
+                vec![
+                    KuzzleRequest::new("server", "now"),
+                    KuzzleRequest::new("index", "list"),
+                    KuzzleRequest::new("server", "info"),
+                ],
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        _info.assert();
+        _now.assert();
+        _list.assert();
+        let actions: Vec<&str> = responses
+            .iter()
+            .map(|res| res.action().as_ref().unwrap().as_str())
+            .collect();
+        assert_eq!(actions, vec!["now", "list", "info"]);
+        assert_eq!(responses[0].result()["now"], 1447151167622u64);
+    }
+
+    /// A protocol packing batches in a single round trip.
+    struct PackingProtocol {
+        batches: Rc<Cell<usize>>,
+        inner: FakeProtocol,
+    }
+
+    impl Protocol for PackingProtocol {
+        fn once(&self) {}
+        fn listener_count(&self) {}
+        fn connect(&self) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+        fn send(
+            &self,
+            req: KuzzleRequest,
+            options: QueryOptions,
+        ) -> Result<KuzzleResponse, Box<dyn Error>> {
+            self.inner.send(req, options)
+        }
+        fn send_batch(
+            &self,
+            reqs: Vec<KuzzleRequest>,
+            options: QueryOptions,
+        ) -> Result<Vec<KuzzleResponse>, Box<dyn Error>> {
+            self.batches.set(self.batches.get() + 1);
+            reqs.into_iter()
+                .map(|req| self.inner.send(req, options.clone()))
+                .collect()
+        }
+        fn supports_notifications(&self) -> bool {
+            self.inner.supports_notifications()
+        }
+        fn cancel_request(&self, _request_id: &str) -> bool {
+            false
+        }
+        fn close(&self) {}
+        fn state(&self) -> ConnectionState {
+            ConnectionState::Connected
+        }
+        fn request_history(&self) {}
+        fn start_queuing(&self) {}
+        fn stop_queuing(&self) {}
+        fn clear_queue(&self) {}
+        fn queue_stats(&self) -> QueueStats {
+            QueueStats::default()
+        }
+    }
+
+    #[test]
+    fn batch_ok_packed_by_protocol() {
+        let batches = Rc::new(Cell::new(0));
+        let sent = Rc::new(Cell::new(0));
+        let k = Kuzzle::new(PackingProtocol {
+            batches: batches.clone(),
+            inner: FakeProtocol {
+                state: ConnectionState::Connected,
+                sent: sent.clone(),
+            },
+        });
+
+        let responses = k
+            .batch(
+                vec![
+                    KuzzleRequest::new("server", "now"),
+                    KuzzleRequest::new("realtime", "subscribe"),
+                ],
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        assert_eq!(batches.get(), 1);
+        assert_eq!(sent.get(), 2);
+        assert_eq!(responses[0].action(), &Some("now".to_string()));
+        assert_eq!(responses[1].result()["roomId"], "room-2");
+    }
+    #[test]
+    fn with_read_write_routes_reads_to_read_protocol() {
+        let read = Rc::new(Cell::new(0));
+        let write = Rc::new(Cell::new(0));
+        let k = Kuzzle::with_read_write(
+            FakeProtocol {
+                state: ConnectionState::Connected,
+                sent: read.clone(),
+            },
+            FakeProtocol {
+                state: ConnectionState::Connected,
+                sent: write.clone(),
+            },
+        );
+
+        // GET /:index/:collection/:_id
+        k.query(KuzzleRequest::new("document", "get"), QueryOptions::new())
+            .unwrap();
+        assert_eq!((read.get(), write.get()), (1, 0));
+
+        // POST /:index/:collection/_create
+        k.query(
+            KuzzleRequest::new("document", "create"),
+            QueryOptions::new(),
+        )
+        .unwrap();
+        assert_eq!((read.get(), write.get()), (1, 1));
+
+        // POST /:index/:collection/_count, still a read
+        k.query(KuzzleRequest::new("document", "count"), QueryOptions::new())
+            .unwrap();
+        k.query(KuzzleRequest::new("ms", "zrange"), QueryOptions::new())
+            .unwrap();
+        assert_eq!((read.get(), write.get()), (3, 1));
+
+        // Sessions stay on the write protocol.
+        k.query(
+            KuzzleRequest::new("auth", "getCurrentUser"),
+            QueryOptions::new(),
+        )
+        .unwrap();
+        k.query(KuzzleRequest::new("ms", "set"), QueryOptions::new())
+            .unwrap();
+        assert_eq!((read.get(), write.get()), (3, 3));
+    }
+
+    #[test]
+    fn new_sends_reads_and_writes_through_single_protocol() {
+        let (k, sent) = fake_kuzzle(ConnectionState::Connected);
+
+        k.query(KuzzleRequest::new("document", "get"), QueryOptions::new())
+            .unwrap();
+        k.query(
+            KuzzleRequest::new("document", "create"),
+            QueryOptions::new(),
+        )
+        .unwrap();
+
+        assert_eq!(sent.get(), 2);
     }
 }
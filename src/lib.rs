@@ -11,6 +11,9 @@
 extern crate serde_derive;
 extern crate serde_json;
 
+#[macro_use]
+mod macros;
+
 pub mod controllers;
 pub mod event_emitter;
 pub mod kuzzle;
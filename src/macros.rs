@@ -0,0 +1,79 @@
+/// Builds a `KuzzleRequest` from a controller, an action and an optional
+/// set of `index`, `collection`, `id`, `body` and `query` arguments,
+/// always given in that order.
+///
+/// Body and query string values go through `KuzzleRequest::try_add_to_body`
+/// and `try_add_to_query_strings`, rejecting non-finite floats, and errors
+/// are propagated with `?`, so the macro must be used inside a function
+/// returning `Result<_, Box<dyn Error>>`.
+///
+/// ```ignore
+/// let req = kuzzle_request!("index", "setAutoRefresh",
+///     index: index,
+///     body: { "autoRefresh" => auto_refresh }
+/// );
+/// ```
+macro_rules! kuzzle_request {
+    (
+        $controller:expr, $action:expr
+        $(, index: $index:expr)?
+        $(, collection: $collection:expr)?
+        $(, id: $id:expr)?
+        $(, body: { $($body_key:expr => $body_value:expr),* $(,)? })?
+        $(, query: { $($query_key:expr => $query_value:expr),* $(,)? })?
+        $(,)?
+    ) => {{
+        #[allow(unused_mut)]
+        let mut req = $crate::types::KuzzleRequest::new($controller, $action);
+        $(req = req.set_index($index);)?
+        $(req = req.set_collection($collection);)?
+        $(req = req.set_id($id);)?
+        $($(req = req.try_add_to_body($body_key.to_string(), &$body_value)?;)*)?
+        $($(req = req.try_add_to_query_strings($query_key.to_string(), &$query_value)?;)*)?
+        req
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::KuzzleRequest;
+    use serde_json::to_value;
+    use std::error::Error;
+
+    #[test]
+    fn kuzzle_request_controller_action_only() -> Result<(), Box<dyn Error>> {
+        let req = kuzzle_request!("index", "list");
+        assert_eq!(KuzzleRequest::new("index", "list"), req);
+        Ok(())
+    }
+
+    #[test]
+    fn kuzzle_request_with_index() -> Result<(), Box<dyn Error>> {
+        let req = kuzzle_request!("index", "create", index: "nyc-open-data");
+        assert_eq!(
+            KuzzleRequest::new("index", "create").set_index("nyc-open-data"),
+            req
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn kuzzle_request_with_all_arguments() -> Result<(), Box<dyn Error>> {
+        let req = kuzzle_request!("document", "replace",
+            index: "nyc-open-data",
+            collection: "yellow-taxi",
+            id: "some-id",
+            body: { "autoRefresh" => true, "indexes" => vec!["a", "b"] },
+            query: { "refresh" => "wait_for" },
+        );
+        let expected = KuzzleRequest::new("document", "replace")
+            .set_index("nyc-open-data")
+            .set_collection("yellow-taxi")
+            .set_id("some-id")
+            .add_to_body("autoRefresh".to_string(), to_value(true).unwrap())
+            .add_to_body("indexes".to_string(), to_value(vec!["a", "b"]).unwrap())
+            .add_to_query_strings("refresh".to_string(), to_value("wait_for").unwrap());
+        assert_eq!(expected, req);
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use crate::types::KuzzleOptions;
+use serde_json::{Map, Value};
+use std::env;
+use std::fs;
+use std::process;
+
+/// Returns the metadata identifying this client, if enabled with
+/// `KuzzleOptions::set_client_metadata`: the `hostname` (when known),
+/// `pid` and `sdkVersion`, then the configured entries, which take
+/// precedence.
+pub(crate) fn client_metadata(options: &KuzzleOptions) -> Option<Value> {
+    let configured = options.client_metadata().as_ref()?;
+
+    let mut metadata = Map::new();
+    if let Some(hostname) = hostname() {
+        metadata.insert("hostname".to_string(), Value::from(hostname));
+    }
+    metadata.insert("pid".to_string(), Value::from(process::id()));
+    metadata.insert(
+        "sdkVersion".to_string(),
+        Value::from(env!("CARGO_PKG_VERSION")),
+    );
+    metadata.extend(configured.clone());
+    Some(Value::Object(metadata))
+}
+
+fn hostname() -> Option<String> {
+    env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn client_metadata_disabled() {
+        assert_eq!(
+            client_metadata(&KuzzleOptions::new("localhost", 7512)),
+            None
+        );
+    }
+
+    #[test]
+    fn client_metadata_ok() {
+        let mut configured = Map::new();
+        configured.insert("service".to_string(), json!("crab-tracker"));
+        configured.insert("sdkVersion".to_string(), json!("ferris-build"));
+        let options = KuzzleOptions::new("localhost", 7512).set_client_metadata(configured);
+
+        let metadata = client_metadata(&options).unwrap();
+        assert_eq!(metadata["pid"], json!(process::id()));
+        assert_eq!(metadata["service"], json!("crab-tracker"));
+        assert_eq!(metadata["sdkVersion"], json!("ferris-build"));
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 type Routes = HashMap<String, HashMap<String, Route>>;
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct Route {
     pub url: String,
     pub verb: String,
@@ -36,19 +36,55 @@ impl Http {
     /// ```
     pub fn new(options: KuzzleOptions) -> Http {
         Http {
-            _client: Client::new(),
+            _client: build_client(&options),
             _options: options,
             _routes: Http::read_routes_from_file(".http_routes.json"),
         }
     }
 
-    fn _get_route(&self, controller: &str, action: &str) -> Route {
-        self._routes
+    /// Looks up the route of a controller action. Kuzzle names are camelCase,
+    /// snake_case ones (e.g. `get_auto_refresh`) are normalized.
+    fn _get_route(&self, controller: &str, action: &str) -> Result<Route, Box<dyn Error>> {
+        let actions = match self
+            ._routes
             .get(controller)
-            .unwrap()
+            .or_else(|| self._routes.get(&to_camel_case(controller)))
+        {
+            Some(actions) => actions,
+            None => {
+                let mut controllers: Vec<&String> = self._routes.keys().collect();
+                controllers.sort();
+                return Err(Box::new(SdkError::new(
+                    "Http::send",
+                    &format!(
+                        "unknown controller {}, valid controllers are: {}.",
+                        controller,
+                        join(controllers)
+                    ),
+                )));
+            }
+        };
+
+        match actions
             .get(action)
-            .unwrap()
-            .clone()
+            .or_else(|| actions.get(&to_camel_case(action)))
+        {
+            Some(route) => Ok(route.clone()),
+            None => {
+                let mut valid_actions: Vec<&String> = actions.keys().collect();
+                valid_actions.sort();
+                Err(Box::new(SdkError::new(
+                    "Http::send",
+                    &format!(
+                        "{}/{} is not available over HTTP, valid {} actions are: {}.",
+                        controller,
+                        action,
+                        controller,
+                        join(valid_actions)
+                    ),
+                )))
+            }
+        }
     }
 
     fn read_routes_from_file(file: &str) -> Routes {
@@ -73,10 +109,78 @@ impl Http {
     }
 }
 
+/// Builds the HTTP client, applying the connection options.
+fn build_client(options: &KuzzleOptions) -> Client {
+    let mut builder = Client::builder();
+    if let Some(timeout) = options.connect_timeout() {
+        builder = builder.connect_timeout(*timeout);
+    }
+    builder.build().expect("cannot build the HTTP client")
+}
+
+/// Turns a snake_case name into camelCase, e.g. `m_delete` into `mDelete`.
+fn to_camel_case(name: &str) -> String {
+    let mut camel_case = String::with_capacity(name.len());
+    let mut upper_next = false;
+
+    for c in name.chars() {
+        if c == '_' && !camel_case.is_empty() {
+            upper_next = true;
+        } else if upper_next {
+            camel_case.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            camel_case.push(c);
+        }
+    }
+
+    camel_case
+}
+
+/// Renders a JSON value as it must appear in an url.
+fn to_query_value(value: Value) -> String {
+    match value {
+        Value::String(value) => value,
+        value => value.to_string(),
+    }
+}
+
+/// Reads a response body, failing as soon as it exceeds the configured
+/// maximum size: the Content-Length header is missing from chunked
+/// responses, so it can't be relied on alone.
+fn read_body<R: Read>(mut reader: R, max_bytes: &Option<u64>) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut body = Vec::new();
+    match max_bytes {
+        Some(max_bytes) => {
+            reader.by_ref().take(max_bytes + 1).read_to_end(&mut body)?;
+            if body.len() as u64 > *max_bytes {
+                return Err(Box::new(SizeLimitError::new(*max_bytes)));
+            }
+        }
+        None => {
+            reader.read_to_end(&mut body)?;
+        }
+    }
+    Ok(body)
+}
+
+fn join(names: Vec<&String>) -> String {
+    names
+        .iter()
+        .map(|name| name.as_str())
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+use crate::protocols::client_metadata::client_metadata;
 use crate::protocols::Protocol;
-use crate::types::{KuzzleRequest, KuzzleResponse, QueryOptions};
+use crate::types::{
+    ConnectionState, KuzzleError, KuzzleRequest, KuzzleResponse, QueryOptions, QueueStats,
+    SdkError, SizeLimitError,
+};
 
 use reqwest::{Client, Method, Url};
+use serde_json::Value;
 use std::error::Error;
 
 #[cfg(test)]
@@ -89,22 +193,43 @@ impl Protocol for Http {
     fn listener_count(&self) {
         unimplemented!();
     }
-    fn connect(&self) {
-        unimplemented!();
+    fn connect(&self) -> Result<(), Box<dyn Error>> {
+        // Each request opens its own connection.
+        Ok(())
     }
     fn send(
         &self,
         req: KuzzleRequest,
-        _query_options: QueryOptions,
-    ) -> Result<KuzzleResponse, Box<Error>> {
-        let kuzzle_route = self._get_route(req.controller(), req.action());
+        query_options: QueryOptions,
+    ) -> Result<KuzzleResponse, Box<dyn Error>> {
+        let kuzzle_route = self._get_route(req.controller(), req.action())?;
+        let mut query_strings = req.query_strings().clone();
+
+        // A document id which has no placeholder in the route
+        // (e.g. `document/create`) is passed as the `_id` argument.
+        if let Some(id) = req.id() {
+            if !kuzzle_route.url.contains(":_id") {
+                query_strings.insert("_id".to_string(), Value::from(id.clone()));
+            }
+        }
+
+        // Fill route placeholders: `:index`, `:collection` and `:_id` come from
+        // the request itself, any other one is taken out of the query strings.
         let route = kuzzle_route
             .url
-            .replace(":index", &req.index().clone().unwrap_or(String::new()))
-            .replace(
-                ":collection",
-                &req.collection().clone().unwrap_or(String::new()),
-            );
+            .split('/')
+            .map(|segment| match segment {
+                ":index" => req.index().clone().unwrap_or_default(),
+                ":collection" => req.collection().clone().unwrap_or_default(),
+                ":_id" => req.id().clone().unwrap_or_default(),
+                _ if segment.starts_with(':') => query_strings
+                    .remove(&segment[1..])
+                    .map(to_query_value)
+                    .unwrap_or_default(),
+                _ => segment.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join("/");
 
         #[cfg(not(test))]
         let host = &format!("http://{}:{}", self._options.host(), self._options.port(),);
@@ -120,18 +245,65 @@ impl Protocol for Http {
             request = request.json(&req.body());
         }
 
-        if !req.query_strings().is_empty() {
-            request = request.query(&req.query_strings());
+        if !query_strings.is_empty() {
+            // Values are rendered as text beforehand: arbitrary precision
+            // numbers can't go through the url encoder as they are.
+            let query_strings: Vec<(String, String)> = query_strings
+                .into_iter()
+                .map(|(key, value)| (key, to_query_value(value)))
+                .collect();
+            request = request.query(&query_strings);
+        }
+
+        if let Some(jwt) = req.jwt() {
+            request = request.bearer_auth(jwt);
+        }
+
+        let mut volatile = req.volatile().clone();
+        if let Some(metadata) = client_metadata(&self._options) {
+            volatile.entry("clientMetadata").or_insert(metadata);
         }
+        if !volatile.is_empty() {
+            request = request.header("x-kuzzle-volatile", serde_json::to_string(&volatile)?);
+        }
+
+        let mut http_response = request.send()?;
+        let body = read_body(&mut http_response, self._options.max_response_bytes())?;
 
-        let response: KuzzleResponse = request.send()?.json()?;
+        // A version conflict on a conditional write is a failed precondition,
+        // whatever the status and body the server answered with.
+        let status = http_response.status().as_u16();
+        if query_options.version().is_some() && (status == 409 || status == 412) {
+            let message = match serde_json::from_slice::<KuzzleResponse>(&body) {
+                Ok(response) => match response.error() {
+                    Some(k_err) => k_err.message().clone(),
+                    None => String::new(),
+                },
+                Err(_) => String::new(),
+            };
+            return Err(Box::new(KuzzleError::new(
+                Some(412),
+                &format!(
+                    "Document version does not match {}. {}",
+                    query_options.version().unwrap(),
+                    message
+                ),
+            )));
+        }
+
+        let response: KuzzleResponse = serde_json::from_slice(&body)?;
         Ok(response)
     }
+    fn cancel_request(&self, _request_id: &str) -> bool {
+        // HTTP requests are synchronous: nothing is ever left pending.
+        false
+    }
     fn close(&self) {
-        unimplemented!();
+        // Each request closes its own connection.
     }
-    fn state(&self) {
-        unimplemented!();
+    fn state(&self) -> ConnectionState {
+        // HTTP has no persistent connection, it is never known to be offline.
+        ConnectionState::Connected
     }
     fn request_history(&self) {
         unimplemented!();
@@ -145,4 +317,205 @@ impl Protocol for Http {
     fn clear_queue(&self) {
         unimplemented!();
     }
+    fn queue_stats(&self) -> QueueStats {
+        // HTTP requests are never queued.
+        QueueStats::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::KuzzleOptions;
+    use mockito::Matcher;
+
+    #[test]
+    fn send_fail_response_too_large() {
+        let _m = mockito::mock("GET", "/_now")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "now": 1928374619383
+                    }
+                }"#,
+            )
+            .create();
+
+        let http = Http::new(KuzzleOptions::new("localhost", 7512).set_max_response_bytes(64));
+        let res = http.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+
+        assert!(res.is_err());
+        let err = res.unwrap_err();
+        let size_err = err.downcast_ref::<SizeLimitError>().unwrap();
+        assert_eq!(size_err.max_bytes(), 64);
+    }
+
+    #[test]
+    fn read_body_fail_without_content_length() {
+        // A chunked response: the size is only known once read.
+        let body = vec![b'x'; 65];
+        let err = read_body(&body[..], &Some(64)).unwrap_err();
+        let size_err = err.downcast_ref::<SizeLimitError>().unwrap();
+
+        assert_eq!(size_err.max_bytes(), 64);
+        assert_eq!(read_body(&body[..], &Some(65)).unwrap(), body);
+        assert_eq!(read_body(&body[..], &None).unwrap(), body);
+    }
+
+    #[test]
+    fn send_ok_response_within_limit() {
+        let _m = mockito::mock("GET", "/_now")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "now": 1928374619383
+                    }
+                }"#,
+            )
+            .create();
+
+        let http = Http::new(KuzzleOptions::new("localhost", 7512).set_max_response_bytes(4096));
+        let res = http.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn build_client_ok_connect_timeout() {
+        use std::time::{Duration, Instant};
+
+        let options =
+            KuzzleOptions::new("localhost", 7512).set_connect_timeout(Duration::from_millis(200));
+        let client = build_client(&options);
+
+        // Non-routable address: without a connect timeout, the connection
+        // attempt would only be given up by the OS, minutes later.
+        let start = Instant::now();
+        let res = client.get("http://10.255.255.1:7512/_now").send();
+
+        assert!(res.is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn send_ok_client_metadata_volatile() {
+        let mut metadata = serde_json::Map::new();
+        metadata.insert("service".to_string(), Value::from("crab-tracker"));
+        let options = KuzzleOptions::new("localhost", 7512).set_client_metadata(metadata);
+        let expected = client_metadata(&options).unwrap();
+        assert_eq!(expected["pid"], Value::from(std::process::id()));
+
+        let _m = mockito::mock("GET", "/_now")
+            .match_header(
+                "x-kuzzle-volatile",
+                Matcher::Json(serde_json::json!({
+                    "origin": "migration",
+                    "clientMetadata": expected
+                })),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "now": 1928374619383 }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let http = Http::new(options);
+        let req = KuzzleRequest::new("server", "now")
+            .add_to_volatile("origin".to_string(), Value::from("migration"));
+        let res = http.send(req, QueryOptions::new());
+
+        _m.assert();
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn to_camel_case_ok() {
+        assert_eq!(to_camel_case("get_auto_refresh"), "getAutoRefresh");
+        assert_eq!(to_camel_case("m_delete"), "mDelete");
+        assert_eq!(to_camel_case("getAutoRefresh"), "getAutoRefresh");
+        assert_eq!(to_camel_case("_create"), "_create");
+    }
+
+    #[test]
+    fn get_route_ok() {
+        let http = Http::new(KuzzleOptions::new("localhost", 7512));
+        let route = http._get_route("index", "getAutoRefresh").unwrap();
+
+        assert_eq!(route.url, "/:index/_autoRefresh");
+        assert_eq!(route.verb, "GET");
+    }
+
+    #[test]
+    fn get_route_ok_snake_case() {
+        let http = Http::new(KuzzleOptions::new("localhost", 7512));
+        let route = http._get_route("index", "get_auto_refresh").unwrap();
+
+        assert_eq!(route.url, "/:index/_autoRefresh");
+    }
+
+    #[test]
+    fn get_route_fail_lists_valid_actions() {
+        let http = Http::new(KuzzleOptions::new("localhost", 7512));
+        let err = http._get_route("index", "autoRefresh").unwrap_err();
+        let sdk_err = err.downcast_ref::<SdkError>().unwrap();
+
+        assert!(sdk_err
+            .message()
+            .starts_with("index/autoRefresh is not available over HTTP, valid index actions are: create, delete, exists, getAutoRefresh,"));
+    }
+
+    #[test]
+    fn get_route_fail_unknown_controller() {
+        let http = Http::new(KuzzleOptions::new("localhost", 7512));
+        let err = http._get_route("indexes", "create").unwrap_err();
+        let sdk_err = err.downcast_ref::<SdkError>().unwrap();
+
+        assert!(sdk_err
+            .message()
+            .starts_with("unknown controller indexes, valid controllers are: admin, auth, bulk,"));
+    }
+
+    #[test]
+    fn send_fail_route_not_available() {
+        let http = Http::new(KuzzleOptions::new("localhost", 7512));
+        let res = http.send(
+            KuzzleRequest::new("realtime", "subscribe"),
+            QueryOptions::new(),
+        );
+
+        let err = res.unwrap_err();
+        assert!(err.downcast_ref::<SdkError>().is_some());
+    }
 }
@@ -1,6 +1,9 @@
+mod client_metadata;
 mod http;
+mod offline_queue;
 mod protocol;
 mod websocket;
+mod websocket_connection;
 
 pub use self::http::Http;
 pub use self::protocol::Protocol;
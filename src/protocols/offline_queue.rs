@@ -0,0 +1,192 @@
+use crate::types::{KuzzleRequest, QueueStats};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Requests buffered while the SDK is offline, oldest first.
+///
+/// Entries older than `ttl` are expired and the oldest entries are dropped
+/// when `max_size` is reached. Both are counted in `dropped_total`.
+pub(crate) struct OfflineQueue {
+    _entries: VecDeque<(Instant, KuzzleRequest)>,
+    _max_size: usize,
+    _ttl: Duration,
+    _dropped_total: u64,
+}
+
+impl OfflineQueue {
+    pub(crate) fn new(max_size: usize, ttl: Duration) -> OfflineQueue {
+        OfflineQueue {
+            _entries: VecDeque::new(),
+            _max_size: max_size,
+            _ttl: ttl,
+            _dropped_total: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, req: KuzzleRequest) {
+        self.push_at(req, Instant::now());
+    }
+
+    pub(crate) fn push_at(&mut self, req: KuzzleRequest, now: Instant) {
+        self.expire_at(now);
+        self._entries.push_back((now, req));
+
+        while self._entries.len() > self._max_size {
+            self._entries.pop_front();
+            self._dropped_total += 1;
+        }
+    }
+
+    /// Drops the entries which outlived the queue ttl.
+    pub(crate) fn expire_at(&mut self, now: Instant) {
+        while let Some((queued_at, _)) = self._entries.front() {
+            if now.duration_since(*queued_at) <= self._ttl {
+                break;
+            }
+            self._entries.pop_front();
+            self._dropped_total += 1;
+        }
+    }
+
+    /// Takes the oldest unexpired request out of the queue, along with the
+    /// time it was queued at.
+    pub(crate) fn pop_front(&mut self) -> Option<(Instant, KuzzleRequest)> {
+        self.expire_at(Instant::now());
+        self._entries.pop_front()
+    }
+
+    /// Puts back a request taken with `pop_front`, e.g. after a failed
+    /// replay, keeping its place and queuing time.
+    pub(crate) fn requeue(&mut self, queued_at: Instant, req: KuzzleRequest) {
+        self._entries.push_front((queued_at, req));
+    }
+
+    /// Drops the queued request with the given id, returning whether there
+    /// was one. Removed requests are not counted as dropped.
+    pub(crate) fn remove(&mut self, request_id: &str) -> bool {
+        let len = self._entries.len();
+        self._entries
+            .retain(|(_, req)| req.request_id().as_ref().map(String::as_str) != Some(request_id));
+        self._entries.len() != len
+    }
+
+    /// Empties the queue. Cleared requests are not counted as dropped.
+    pub(crate) fn clear(&mut self) {
+        self._entries.clear();
+    }
+
+    pub(crate) fn stats(&mut self) -> QueueStats {
+        self.stats_at(Instant::now())
+    }
+
+    pub(crate) fn stats_at(&mut self, now: Instant) -> QueueStats {
+        self.expire_at(now);
+        QueueStats::new(
+            self._entries.len(),
+            self._entries
+                .front()
+                .map(|(queued_at, _)| now.duration_since(*queued_at)),
+            self._dropped_total,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_empty_queue() {
+        let mut queue = OfflineQueue::new(10, Duration::from_secs(120));
+        assert_eq!(queue.stats(), QueueStats::default());
+    }
+
+    #[test]
+    fn stats_after_enqueuing() {
+        let start = Instant::now();
+        let mut queue = OfflineQueue::new(10, Duration::from_secs(120));
+        queue.push_at(KuzzleRequest::new("server", "now"), start);
+        queue.push_at(
+            KuzzleRequest::new("server", "info"),
+            start + Duration::from_secs(5),
+        );
+
+        let stats = queue.stats_at(start + Duration::from_secs(10));
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats.oldest_age(), Some(Duration::from_secs(10)));
+        assert_eq!(stats.dropped_total(), 0);
+    }
+
+    #[test]
+    fn stats_after_expiring() {
+        let start = Instant::now();
+        let mut queue = OfflineQueue::new(10, Duration::from_secs(60));
+        queue.push_at(KuzzleRequest::new("server", "now"), start);
+        queue.push_at(
+            KuzzleRequest::new("server", "info"),
+            start + Duration::from_secs(30),
+        );
+
+        let stats = queue.stats_at(start + Duration::from_secs(75));
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats.oldest_age(), Some(Duration::from_secs(45)));
+        assert_eq!(stats.dropped_total(), 1);
+    }
+
+    #[test]
+    fn stats_after_overflow() {
+        let start = Instant::now();
+        let mut queue = OfflineQueue::new(2, Duration::from_secs(60));
+        for i in 0..5 {
+            queue.push_at(
+                KuzzleRequest::new("server", "now"),
+                start + Duration::from_secs(i),
+            );
+        }
+
+        let stats = queue.stats_at(start + Duration::from_secs(5));
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats.oldest_age(), Some(Duration::from_secs(2)));
+        assert_eq!(stats.dropped_total(), 3);
+    }
+
+    #[test]
+    fn requeue_keeps_order_and_age() {
+        let mut queue = OfflineQueue::new(10, Duration::from_secs(60));
+        queue.push(KuzzleRequest::new("server", "now"));
+        queue.push(KuzzleRequest::new("server", "info"));
+
+        let (queued_at, req) = queue.pop_front().unwrap();
+        assert_eq!(req.action(), "now");
+        queue.requeue(queued_at, req);
+
+        let (requeued_at, req) = queue.pop_front().unwrap();
+        assert_eq!(req.action(), "now");
+        assert_eq!(requeued_at, queued_at);
+        assert_eq!(queue.stats().len(), 1);
+    }
+
+    #[test]
+    fn remove_by_request_id() {
+        let mut queue = OfflineQueue::new(10, Duration::from_secs(60));
+        queue.push(KuzzleRequest::new("server", "now").set_request_id("ferris"));
+        queue.push(KuzzleRequest::new("server", "info").set_request_id("crab"));
+
+        assert!(queue.remove("ferris"));
+        assert!(!queue.remove("lobster"));
+        let (_, req) = queue.pop_front().unwrap();
+        assert_eq!(req.action(), "info");
+        assert_eq!(queue.stats().dropped_total(), 0);
+    }
+
+    #[test]
+    fn clear_does_not_count_as_dropped() {
+        let mut queue = OfflineQueue::new(10, Duration::from_secs(60));
+        queue.push(KuzzleRequest::new("server", "now"));
+        queue.clear();
+
+        let stats = queue.stats();
+        assert!(stats.is_empty());
+        assert_eq!(stats.dropped_total(), 0);
+    }
+}
@@ -1,15 +1,93 @@
-use crate::types::{KuzzleRequest, KuzzleResponse, QueryOptions};
+use crate::types::{
+    ConnectionState, KuzzleRequest, KuzzleResponse, Notification, QueryOptions, QueueStats,
+};
 use std::error::Error;
+use std::time::Duration;
 
 pub trait Protocol {
     fn once(&self);
     fn listener_count(&self);
-    fn connect(&self);
-    fn send(&self, req: KuzzleRequest, options: QueryOptions) -> Result<KuzzleResponse, Box<Error>>;
+    /// Opens the connection to Kuzzle. Protocols without persistent
+    /// connection, such as `Http`, have nothing to open.
+    fn connect(&self) -> Result<(), Box<dyn Error>>;
+    fn send(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Box<dyn Error>>;
+
+    /// Sends several requests, returning their responses in the same order.
+    /// Protocols able to pack them in a single round trip override this,
+    /// the default sends them one after the other.
+    fn send_batch(
+        &self,
+        reqs: Vec<KuzzleRequest>,
+        options: QueryOptions,
+    ) -> Result<Vec<KuzzleResponse>, Box<dyn Error>> {
+        reqs.into_iter()
+            .map(|req| self.send(req, options.clone()))
+            .collect()
+    }
+
+    /// Whether the protocol keeps a connection open, on which realtime
+    /// notifications are received. Request/response protocols such as
+    /// `Http` cannot deliver them.
+    fn supports_notifications(&self) -> bool {
+        false
+    }
+
+    /// Registers the callback invoked with each notification received on
+    /// the given subscription channel.
+    fn listen(&self, _channel: &str, _callback: Box<dyn Fn(Notification)>) {}
+
+    /// Unregisters the callback of the given subscription channel.
+    fn unlisten(&self, _channel: &str) {}
+
+    /// Reads the messages received within `timeout`, handing the
+    /// notifications to the callbacks of their channel, and returns how
+    /// many were dispatched. Protocols without notifications have none.
+    fn receive_notifications(&self, _timeout: Duration) -> Result<usize, Box<dyn Error>> {
+        Ok(0)
+    }
+
+    /// Whether subscriptions are to be renewed once the connection is
+    /// re-established, see `KuzzleOptions::set_auto_resubscribe`.
+    fn auto_resubscribe(&self) -> bool {
+        false
+    }
+
+    /// Whether the offline queue is to be replayed once the connection is
+    /// re-established, see `KuzzleOptions::set_auto_replay`.
+    fn auto_replay(&self) -> bool {
+        false
+    }
+
+    /// Re-establishes a lost connection, if set to auto-reconnect, no more
+    /// than once per reconnection delay. Protocols without persistent
+    /// connection have nothing to re-establish.
+    fn reconnect(&self) {}
+
+    /// Returns whether the connection was re-established since the last
+    /// call, the subscriptions made before being lost by the server.
+    fn take_reconnection(&self) -> bool {
+        false
+    }
+
+    /// Sends the requests of the offline queue, oldest first, returning how
+    /// many were replayed. Protocols without offline queue replay nothing.
+    fn replay_queue(&self) -> Result<usize, Box<dyn Error>> {
+        Ok(0)
+    }
+
+    /// Stops the running `replay_queue` before its next entry, leaving the
+    /// requests not replayed yet in the queue.
+    fn stop_replay(&self) {}
+    fn cancel_request(&self, request_id: &str) -> bool;
     fn close(&self);
-    fn state(&self);
+    fn state(&self) -> ConnectionState;
     fn request_history(&self);
     fn start_queuing(&self);
     fn stop_queuing(&self);
     fn clear_queue(&self);
+    fn queue_stats(&self) -> QueueStats;
 }
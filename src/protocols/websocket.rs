@@ -1,17 +1,283 @@
+use crate::protocols::client_metadata::client_metadata;
+use crate::protocols::offline_queue::OfflineQueue;
+use crate::protocols::websocket_connection::Connection;
 use crate::protocols::Protocol;
-use crate::types::{KuzzleOptions, KuzzleRequest, KuzzleResponse, QueryOptions};
+use crate::types::{
+    ConnectionError, ConnectionState, KuzzleOptions, KuzzleRequest, KuzzleResponse, Notification,
+    QueryOptions, QueueStats, SdkError, SizeLimitError, WebsocketFraming,
+};
+use serde_json::{Map, Value};
+use std::cell::{Cell, RefCell, RefMut};
+use std::collections::HashMap;
 use std::error::Error;
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Notification callbacks, by subscription channel.
+type Listeners = HashMap<String, Rc<dyn Fn(Notification)>>;
+
+/// How often Socket.IO connections are pinged, as engine.io v3 servers
+/// drop the clients they have not heard from for a while.
+const SOCKET_IO_PING_INTERVAL: Duration = Duration::from_secs(25);
 
 pub struct Websocket {
     _options: KuzzleOptions,
+    _connection: RefCell<Option<Connection>>,
+    _pending_requests: RefCell<HashMap<String, Sender<KuzzleResponse>>>,
+    _listeners: RefCell<Listeners>,
+    _offline_queue: RefCell<OfflineQueue>,
+    _state: Cell<ConnectionState>,
+    _last_ping: Cell<Instant>,
+    _connected_once: Cell<bool>,
+    _lost_at: Cell<Option<Instant>>,
+    _reconnected: Cell<bool>,
+    _replay_stopped: Cell<bool>,
 }
 
 impl Websocket {
     pub fn new(options: KuzzleOptions) -> Websocket {
-        Websocket { _options: options }
+        let offline_queue =
+            OfflineQueue::new(*options.queue_max_size() as usize, *options.queue_ttl());
+        Websocket {
+            _options: options,
+            _connection: RefCell::new(None),
+            _pending_requests: RefCell::new(HashMap::new()),
+            _listeners: RefCell::new(HashMap::new()),
+            _offline_queue: RefCell::new(offline_queue),
+            _state: Cell::new(ConnectionState::Offline),
+            _last_ping: Cell::new(Instant::now()),
+            _connected_once: Cell::new(false),
+            _lost_at: Cell::new(None),
+            _reconnected: Cell::new(false),
+            _replay_stopped: Cell::new(false),
+        }
+    }
+
+    /// Number of requests still waiting for their response.
+    pub fn pending_requests_count(&self) -> usize {
+        self._pending_requests.borrow().len()
+    }
+
+    /// Registers a request waiting for its response, correlated by its
+    /// request id. The response is delivered through the returned receiver.
+    pub(crate) fn add_pending_request(&self, request_id: &str) -> Receiver<KuzzleResponse> {
+        let (sender, receiver) = channel();
+        self._pending_requests
+            .borrow_mut()
+            .insert(request_id.to_string(), sender);
+        receiver
+    }
+
+    /// Gives a request its id, unless it has one, for it to be told apart
+    /// once queued.
+    fn identify(&self, req: KuzzleRequest) -> KuzzleRequest {
+        match req.request_id() {
+            Some(_) => req,
+            None => {
+                let request_id = self._options.request_id_generator().next_id();
+                req.set_request_id(&request_id)
+            }
+        }
+    }
+
+    /// Hands the queued requests to `send`, oldest first and
+    /// `replay_interval` apart, not to overwhelm a freshly recovered server.
+    /// The replay ends before the next entry once `stop_replay` is called,
+    /// or on the first failure, the failed request being put back in the
+    /// queue along with the ones not replayed yet.
+    fn replay_with<F>(&self, mut send: F) -> Result<usize, Box<dyn Error>>
+    where
+        F: FnMut(KuzzleRequest) -> Result<(), Box<dyn Error>>,
+    {
+        self._replay_stopped.set(false);
+        let mut replayed = 0;
+
+        while !self._replay_stopped.get() {
+            let (queued_at, req) = match self._offline_queue.borrow_mut().pop_front() {
+                Some(entry) => entry,
+                None => break,
+            };
+            if replayed > 0 {
+                thread::sleep(*self._options.replay_interval());
+            }
+
+            if let Err(err) = send(req.clone()) {
+                self._offline_queue.borrow_mut().requeue(queued_at, req);
+                return Err(err);
+            }
+            replayed += 1;
+        }
+
+        Ok(replayed)
+    }
+
+    /// Headers of the connection handshake, holding the client metadata
+    /// if enabled with `KuzzleOptions::set_client_metadata`.
+    pub(crate) fn handshake_headers(&self) -> Vec<(&'static str, String)> {
+        match client_metadata(&self._options) {
+            Some(metadata) => vec![("x-kuzzle-client-metadata", metadata.to_string())],
+            None => Vec::new(),
+        }
+    }
+
+    /// Records that the connection was re-established, for `Kuzzle` to
+    /// renew its subscriptions.
+    pub(crate) fn set_reconnected(&self) {
+        self._reconnected.set(true);
+    }
+
+    /// Hands a received notification to the callback listening on its
+    /// channel, returning whether there was one.
+    pub(crate) fn dispatch(&self, notification: Notification) -> bool {
+        // The listeners are released first, for the callback to be able
+        // to subscribe or unsubscribe.
+        let callback = self._listeners.borrow().get(notification.room()).cloned();
+        match callback {
+            Some(callback) => {
+                callback(notification);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The open connection, if any.
+    fn connection(&self) -> RefMut<'_, Option<Connection>> {
+        self._connection.borrow_mut()
+    }
+
+    /// Frames and writes a message on the connection.
+    fn write(&self, message: &str) -> Result<(), Box<dyn Error>> {
+        let written = match self.connection().as_mut() {
+            Some(connection) => connection.send_text(message),
+            None => return Err(Box::new(not_connected())),
+        };
+        written.map_err(|err| self.lost(err.into()))
+    }
+
+    /// Pings Socket.IO connections every `SOCKET_IO_PING_INTERVAL`.
+    fn keep_alive(&self) -> Result<(), Box<dyn Error>> {
+        if *self._options.websocket_framing() == WebsocketFraming::SocketIo
+            && self._last_ping.get().elapsed() >= SOCKET_IO_PING_INTERVAL
+        {
+            self._last_ping.set(Instant::now());
+            // Engine.io "ping" packet.
+            self.write("2")?;
+        }
+        Ok(())
+    }
+
+    /// Reads the next message, waiting up to `timeout` for it if given,
+    /// and hands a notification to the callback of its channel or a
+    /// response to the request waiting for it. Returns the number of
+    /// notifications dispatched.
+    fn receive(&self, timeout: Option<Duration>) -> Result<usize, Box<dyn Error>> {
+        self.keep_alive()?;
+        let read = match self.connection().as_mut() {
+            Some(connection) => {
+                connection.read_message(timeout, self._options.max_response_bytes())
+            }
+            None => return Err(Box::new(not_connected())),
+        };
+        let message = match read {
+            Ok(Some(message)) => message,
+            Ok(None) => return Ok(0),
+            Err(err) => return Err(self.lost(err)),
+        };
+
+        // The connection borrow is released beforehand: handling a message
+        // may lead to sending new requests.
+        if let Some(payload) = self._options.websocket_framing().decode(&message) {
+            let payload: Value = serde_json::from_str(&payload)?;
+
+            // Notifications are told apart by their room, a listened channel.
+            let channel = payload["room"].as_str().unwrap_or_default();
+            if self._listeners.borrow().contains_key(channel) {
+                let notification: Notification = serde_json::from_value(payload)?;
+                return Ok(self.dispatch(notification) as usize);
+            }
+
+            let request_id = payload["requestId"].as_str().unwrap_or_default();
+            let sender = self._pending_requests.borrow_mut().remove(request_id);
+            if let Some(sender) = sender {
+                let response: KuzzleResponse = serde_json::from_value(payload)?;
+                // The waiter may be gone, its request having been cancelled.
+                let _ = sender.send(response);
+            }
+        }
+        Ok(0)
+    }
+
+    /// Drops a connection which failed, failing the pending requests.
+    /// Returns the error to report, a `ConnectionError` unless the failure
+    /// is a response exceeding `KuzzleOptions::set_max_response_bytes`.
+    fn lost(&self, err: Box<dyn Error>) -> Box<dyn Error> {
+        if let Some(mut connection) = self.connection().take() {
+            connection.close();
+        }
+        self._state.set(ConnectionState::Offline);
+        self._lost_at.set(Some(Instant::now()));
+        self._pending_requests.borrow_mut().clear();
+
+        if err.is::<SizeLimitError>() || err.is::<ConnectionError>() {
+            err
+        } else {
+            Box::new(ConnectionError::new(&format!(
+                "Connection to Kuzzle lost: {}.",
+                err
+            )))
+        }
     }
 }
 
+/// The error of a request sent while offline.
+fn not_connected() -> SdkError {
+    SdkError::new("Websocket::send", "not connected to Kuzzle.")
+}
+
+/// The JSON message of a request, as expected by Kuzzle on its WebSocket
+/// entry point: the request arguments sit next to its routing properties.
+fn request_payload(req: &KuzzleRequest) -> Value {
+    let mut payload = Map::new();
+    for (key, value) in req.query_strings() {
+        payload.insert(key.clone(), value.clone());
+    }
+    payload.insert(
+        "controller".to_string(),
+        Value::from(req.controller().clone()),
+    );
+    payload.insert("action".to_string(), Value::from(req.action().clone()));
+    if let Some(index) = req.index() {
+        payload.insert("index".to_string(), Value::from(index.clone()));
+    }
+    if let Some(collection) = req.collection() {
+        payload.insert("collection".to_string(), Value::from(collection.clone()));
+    }
+    if let Some(id) = req.id() {
+        payload.insert("_id".to_string(), Value::from(id.clone()));
+    }
+    if !req.body().is_empty() {
+        let body: Map<String, Value> = req
+            .body()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        payload.insert("body".to_string(), Value::from(body));
+    }
+    if !req.volatile().is_empty() {
+        payload.insert("volatile".to_string(), Value::from(req.volatile().clone()));
+    }
+    if let Some(jwt) = req.jwt() {
+        payload.insert("jwt".to_string(), Value::from(jwt.clone()));
+    }
+    if let Some(request_id) = req.request_id() {
+        payload.insert("requestId".to_string(), Value::from(request_id.clone()));
+    }
+    Value::from(payload)
+}
+
 impl Protocol for Websocket {
     fn once(&self) {
         unimplemented!();
@@ -21,24 +287,178 @@ impl Protocol for Websocket {
         unimplemented!();
     }
 
-    fn connect(&self) {
-        unimplemented!();
+    fn connect(&self) -> Result<(), Box<dyn Error>> {
+        if self._connection.borrow().is_some() {
+            return Ok(());
+        }
+
+        self._state.set(ConnectionState::Connecting);
+        let connection = Connection::open(
+            self._options.host(),
+            *self._options.port(),
+            *self._options.ssl_connection(),
+            self._options.websocket_framing().path(),
+            &self.handshake_headers(),
+            self._options.connect_timeout(),
+        );
+        match connection {
+            Ok(connection) => {
+                *self.connection() = Some(connection);
+                self._last_ping.set(Instant::now());
+                self._state.set(ConnectionState::Connected);
+                self._lost_at.set(None);
+                // The server forgot the subscriptions of the previous one.
+                if self._connected_once.replace(true) {
+                    self.set_reconnected();
+                }
+                Ok(())
+            }
+            Err(err) => {
+                self._state.set(ConnectionState::Offline);
+                Err(err)
+            }
+        }
     }
 
     fn send(
         &self,
-        _req: KuzzleRequest,
-        _options: QueryOptions,
-    ) -> Result<KuzzleResponse, Box<Error>> {
-        unimplemented!();
+        req: KuzzleRequest,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Box<dyn Error>> {
+        if self.state() != ConnectionState::Connected {
+            // While offline, queuable requests are buffered until the
+            // connection is back, see `KuzzleOptions::set_auto_queue`.
+            if *self._options.auto_queue() && options.queuable() {
+                self._offline_queue.borrow_mut().push(self.identify(req));
+                return Err(Box::new(SdkError::new(
+                    "Websocket::send",
+                    "not connected to Kuzzle, the request is queued.",
+                )));
+            }
+            return Err(Box::new(not_connected()));
+        }
+
+        let req = self.identify(req);
+        let request_id = req.request_id().clone().unwrap_or_default();
+        let response = self.add_pending_request(&request_id);
+        let message = self
+            ._options
+            .websocket_framing()
+            .encode(&request_payload(&req).to_string());
+        if let Err(err) = self.keep_alive().and_then(|_| self.write(&message)) {
+            self._pending_requests.borrow_mut().remove(&request_id);
+            return Err(err);
+        }
+
+        // Responses may come in any order: the messages are read until
+        // this request's own arrives.
+        loop {
+            match response.try_recv() {
+                Ok(response) => return Ok(response),
+                Err(TryRecvError::Empty) => {
+                    self.receive(None)?;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    return Err(Box::new(SdkError::new(
+                        "Websocket::send",
+                        &format!("request {} was cancelled.", request_id),
+                    )))
+                }
+            }
+        }
+    }
+
+    fn supports_notifications(&self) -> bool {
+        true
+    }
+
+    fn listen(&self, channel: &str, callback: Box<dyn Fn(Notification)>) {
+        self._listeners
+            .borrow_mut()
+            .insert(channel.to_string(), Rc::from(callback));
+    }
+
+    fn receive_notifications(&self, timeout: Duration) -> Result<usize, Box<dyn Error>> {
+        let deadline = Instant::now() + timeout;
+        let mut dispatched = 0;
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(dispatched);
+            }
+            dispatched += self.receive(Some(deadline - now))?;
+        }
+    }
+
+    fn unlisten(&self, channel: &str) {
+        self._listeners.borrow_mut().remove(channel);
+    }
+
+    fn auto_resubscribe(&self) -> bool {
+        *self._options.auto_resubscribe()
+    }
+
+    fn auto_replay(&self) -> bool {
+        *self._options.auto_replay()
+    }
+
+    fn reconnect(&self) {
+        // Only a lost connection is re-established, not a closed one.
+        let lost_at = match self._lost_at.get() {
+            Some(lost_at) => lost_at,
+            None => return,
+        };
+        if !*self._options.auto_reconnect()
+            || lost_at.elapsed() < *self._options.reconnection_delay()
+        {
+            return;
+        }
+
+        if self.connect().is_err() {
+            self._lost_at.set(Some(Instant::now()));
+        }
+    }
+
+    fn take_reconnection(&self) -> bool {
+        self._reconnected.replace(false)
+    }
+
+    fn replay_queue(&self) -> Result<usize, Box<dyn Error>> {
+        // Replaying while offline would only fail on the first entry.
+        if self.state() != ConnectionState::Connected {
+            return Err(Box::new(SdkError::new(
+                "Websocket::replay_queue",
+                "the offline queue can only be replayed once connected.",
+            )));
+        }
+        self.replay_with(|req| self.send(req, QueryOptions::new()).map(|_| ()))
+    }
+
+    fn stop_replay(&self) {
+        self._replay_stopped.set(true);
+    }
+
+    fn cancel_request(&self, request_id: &str) -> bool {
+        // Dropping the sender makes `send` give up waiting for the response.
+        let pending = self
+            ._pending_requests
+            .borrow_mut()
+            .remove(request_id)
+            .is_some();
+        pending || self._offline_queue.borrow_mut().remove(request_id)
     }
 
     fn close(&self) {
-        unimplemented!();
+        if let Some(mut connection) = self.connection().take() {
+            connection.close();
+        }
+        self._state.set(ConnectionState::Offline);
+        self._lost_at.set(None);
+        self._pending_requests.borrow_mut().clear();
     }
 
-    fn state(&self) {
-        unimplemented!();
+    fn state(&self) -> ConnectionState {
+        self._state.get()
     }
 
     fn request_history(&self) {
@@ -54,6 +474,790 @@ impl Protocol for Websocket {
     }
 
     fn clear_queue(&self) {
-        unimplemented!();
+        self._offline_queue.borrow_mut().clear();
+    }
+
+    fn queue_stats(&self) -> QueueStats {
+        self._offline_queue.borrow_mut().stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuzzle::Kuzzle;
+    use crate::protocols::websocket_connection::test_server::{serve, Client};
+    use crate::types::{SequentialGenerator, SubscribeOptions};
+    use serde_json::{from_value, json};
+    use std::rc::Rc;
+    use std::sync::{Arc, Mutex};
+    use std::thread::JoinHandle;
+
+    fn queue(ws: &Websocket, req: KuzzleRequest) {
+        let err = ws.send(req, QueryOptions::new()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Websocket::send] not connected to Kuzzle, the request is queued."
+        );
+    }
+
+    #[test]
+    fn queue_stats_after_enqueuing() {
+        let ws = Websocket::new(
+            KuzzleOptions::new("localhost", 7512)
+                .set_auto_queue(true)
+                .set_queue_max_size(1),
+        );
+        queue(&ws, KuzzleRequest::new("server", "now"));
+        queue(&ws, KuzzleRequest::new("server", "info"));
+
+        let stats = ws.queue_stats();
+        assert_eq!(stats.len(), 1);
+        assert!(stats.oldest_age().is_some());
+        assert_eq!(stats.dropped_total(), 1);
+
+        ws.clear_queue();
+        assert!(ws.queue_stats().is_empty());
+    }
+
+    #[test]
+    fn send_fail_offline_without_queuing() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+        let err = ws
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Websocket::send] not connected to Kuzzle."
+        );
+
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512).set_auto_queue(true));
+        let options = QueryOptions::new().set_queuable(false);
+        assert!(ws
+            .send(KuzzleRequest::new("server", "now"), options)
+            .is_err());
+        assert!(ws.queue_stats().is_empty());
+    }
+
+    #[test]
+    fn replay_stopped_after_first_entry() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512).set_auto_queue(true));
+        queue(&ws, KuzzleRequest::new("server", "now"));
+        queue(&ws, KuzzleRequest::new("server", "info"));
+        queue(&ws, KuzzleRequest::new("server", "getStats"));
+
+        let mut sent = Vec::new();
+        let replayed = ws
+            .replay_with(|req| {
+                sent.push(req.action().to_string());
+                ws.stop_replay();
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(replayed, 1);
+        assert_eq!(sent, vec!["now"]);
+        assert_eq!(ws.queue_stats().len(), 2);
+
+        let replayed = ws.replay_with(|_| Ok(())).unwrap();
+        assert_eq!(replayed, 2);
+        assert!(ws.queue_stats().is_empty());
+    }
+
+    #[test]
+    fn replay_failure_keeps_entry_queued() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512).set_auto_queue(true));
+        queue(&ws, KuzzleRequest::new("server", "now"));
+        queue(&ws, KuzzleRequest::new("server", "info"));
+
+        let err = ws
+            .replay_with(|req| match req.action().as_str() {
+                "info" => Err(From::from("server is overloaded")),
+                _ => Ok(()),
+            })
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "server is overloaded");
+        assert_eq!(ws.queue_stats().len(), 1);
+    }
+
+    #[test]
+    fn replay_queue_fail_offline() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512).set_auto_queue(true));
+        queue(&ws, KuzzleRequest::new("server", "now"));
+
+        let err = ws.replay_queue().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Websocket::replay_queue] the offline queue can only be replayed once connected."
+        );
+        assert_eq!(ws.queue_stats().len(), 1);
+    }
+
+    #[test]
+    fn cancel_request_removes_pending_entry() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+        let waiter = ws.add_pending_request("ferris-request-1");
+        let _other = ws.add_pending_request("ferris-request-2");
+        assert_eq!(ws.pending_requests_count(), 2);
+
+        assert!(ws.cancel_request("ferris-request-1"));
+        assert_eq!(ws.pending_requests_count(), 1);
+        assert!(waiter.recv().is_err());
+    }
+
+    #[test]
+    fn send_queues_sequential_request_ids() {
+        let ws = Websocket::new(
+            KuzzleOptions::new("localhost", 7512)
+                .set_auto_queue(true)
+                .set_request_id_generator(SequentialGenerator::new()),
+        );
+        for _ in 0..3 {
+            queue(&ws, KuzzleRequest::new("server", "now"));
+        }
+        queue(
+            &ws,
+            KuzzleRequest::new("server", "now").set_request_id("ferris"),
+        );
+
+        let mut ids = Vec::new();
+        ws.replay_with(|req| {
+            ids.push(req.request_id().clone().unwrap());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(ids, vec!["1", "2", "3", "ferris"]);
+    }
+
+    #[test]
+    fn cancel_request_unknown_id() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+        let _waiter = ws.add_pending_request("ferris-request-1");
+
+        assert!(!ws.cancel_request("unknown-request"));
+        assert_eq!(ws.pending_requests_count(), 1);
+    }
+
+    fn notification(room: &str) -> Notification {
+        from_value(json!({
+            "type": "document",
+            "room": room,
+            "scope": "in",
+            "result": { "_id": "ferris" }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn dispatch_to_listening_channel() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let sink = received.clone();
+        ws.listen(
+            "channel-1",
+            Box::new(move |notification| sink.borrow_mut().push(notification)),
+        );
+
+        assert!(ws.dispatch(notification("channel-1")));
+        assert!(!ws.dispatch(notification("channel-2")));
+        assert_eq!(received.borrow().len(), 1);
+        assert_eq!(received.borrow()[0].room(), "channel-1");
+
+        ws.unlisten("channel-1");
+        assert!(!ws.dispatch(notification("channel-1")));
+        assert_eq!(received.borrow().len(), 1);
+    }
+
+    #[test]
+    fn handshake_headers_client_metadata() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+        assert!(ws.handshake_headers().is_empty());
+
+        let mut metadata = serde_json::Map::new();
+        metadata.insert("service".to_string(), json!("crab-tracker"));
+        let ws =
+            Websocket::new(KuzzleOptions::new("localhost", 7512).set_client_metadata(metadata));
+        let headers = ws.handshake_headers();
+
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].0, "x-kuzzle-client-metadata");
+        let sent: serde_json::Value = serde_json::from_str(&headers[0].1).unwrap();
+        assert_eq!(sent["service"], json!("crab-tracker"));
+        assert_eq!(sent["pid"], json!(std::process::id()));
+        assert!(sent["sdkVersion"].is_string());
+    }
+
+    #[test]
+    fn take_reconnection_once() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+        assert!(ws.auto_resubscribe());
+        assert!(!ws.take_reconnection());
+
+        ws.set_reconnected();
+        assert!(ws.take_reconnection());
+        assert!(!ws.take_reconnection());
+    }
+
+    #[test]
+    fn raw_framing() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+        let framing = *ws._options.websocket_framing();
+        let request = r#"{"controller":"server","action":"now","requestId":"ferris"}"#;
+        let response = r#"{"requestId":"ferris","status":200,"result":{"now":1}}"#;
+
+        assert_eq!(framing, WebsocketFraming::Raw);
+        assert_eq!(framing.path(), "/");
+        assert_eq!(framing.encode(request), request);
+        assert_eq!(framing.decode(response), Some(response.to_string()));
+    }
+
+    #[test]
+    fn socket_io_framing() {
+        let ws = Websocket::new(
+            KuzzleOptions::new("localhost", 7512).set_websocket_framing(WebsocketFraming::SocketIo),
+        );
+        let framing = *ws._options.websocket_framing();
+        let request = r#"{"controller":"server","action":"now","requestId":"ferris"}"#;
+
+        assert_eq!(framing.path(), "/socket.io/?EIO=3&transport=websocket");
+        assert_eq!(
+            framing.encode(request),
+            r#"42["kuzzle",{"controller":"server","action":"now","requestId":"ferris"}]"#
+        );
+
+        let payload = framing
+            .decode(r#"42["ferris",{"requestId":"ferris","status":200,"result":{"now":1}}]"#)
+            .unwrap();
+        let response: KuzzleResponse = serde_json::from_str(&payload).unwrap();
+        assert_eq!(response.request_id(), "ferris");
+        assert_eq!(*response.status(), 200);
+    }
+
+    #[test]
+    fn socket_io_framing_ignores_control_messages() {
+        let framing = WebsocketFraming::SocketIo;
+
+        assert_eq!(
+            framing.decode(r#"0{"sid":"abc","pingInterval":25000}"#),
+            None
+        );
+        assert_eq!(framing.decode("40"), None);
+        assert_eq!(framing.decode("2"), None);
+        assert_eq!(framing.decode("42not json"), None);
+    }
+
+    /// Answers each request with its own action, echoing its payload.
+    fn echo(mut client: Client) {
+        while let Some(message) = client.receive() {
+            let request: Value = serde_json::from_str(&message).unwrap();
+            client.send(
+                &json!({
+                    "requestId": request["requestId"],
+                    "status": 200,
+                    "action": request["action"],
+                    "result": request
+                })
+                .to_string(),
+            );
+        }
+    }
+
+    fn connected(port: u32) -> Websocket {
+        let ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port));
+        ws.connect().unwrap();
+        ws
+    }
+
+    #[test]
+    fn connect_ok_then_send() {
+        let (port, server) = serve(1, echo);
+        let ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port));
+        assert_eq!(ws.state(), ConnectionState::Offline);
+
+        ws.connect().unwrap();
+        assert_eq!(ws.state(), ConnectionState::Connected);
+
+        let req = KuzzleRequest::new("document", "create")
+            .set_index("nyc-open-data")
+            .set_collection("yellow-taxi")
+            .set_id("ferris")
+            .set_jwt("jwt-token")
+            .add_to_body("name".to_string(), json!("Ferris"))
+            .add_to_query_strings("refresh".to_string(), json!("wait_for"));
+        let res = ws.send(req, QueryOptions::new()).unwrap();
+        assert_eq!(*res.status(), 200);
+        assert_eq!(ws.pending_requests_count(), 0);
+
+        let sent = res.result();
+        assert_eq!(sent["controller"], json!("document"));
+        assert_eq!(sent["index"], json!("nyc-open-data"));
+        assert_eq!(sent["collection"], json!("yellow-taxi"));
+        assert_eq!(sent["_id"], json!("ferris"));
+        assert_eq!(sent["jwt"], json!("jwt-token"));
+        assert_eq!(sent["body"], json!({ "name": "Ferris" }));
+        assert_eq!(sent["refresh"], json!("wait_for"));
+        assert_eq!(sent["requestId"], json!(res.request_id()));
+
+        ws.close();
+        assert_eq!(ws.state(), ConnectionState::Offline);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn send_ok_responses_out_of_order() {
+        let (port, server) = serve(1, |mut client| {
+            let first = client.receive().unwrap();
+            // An unrelated response comes first.
+            client.send(r#"{"requestId":"someone-else","status":200,"result":null}"#);
+            let first: Value = serde_json::from_str(&first).unwrap();
+            client.send(
+                &json!({ "requestId": first["requestId"], "status": 200, "result": "now" })
+                    .to_string(),
+            );
+        });
+        let ws = connected(port);
+
+        let res = ws
+            .send(
+                KuzzleRequest::new("server", "now").set_request_id("ferris"),
+                QueryOptions::new(),
+            )
+            .unwrap();
+        assert_eq!(res.request_id(), "ferris");
+        assert_eq!(res.result(), &json!("now"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn send_ok_socket_io_framing() {
+        let (port, server) = serve(1, |mut client| {
+            assert!(client
+                .headers()
+                .starts_with("GET /socket.io/?EIO=3&transport=websocket HTTP/1.1"));
+            client.send(r#"0{"sid":"abc","pingInterval":25000}"#);
+            client.send("40");
+
+            let message = client.receive().unwrap();
+            assert!(message.starts_with(r#"42["kuzzle","#));
+            let request: Value = serde_json::from_str(&message[2..]).unwrap();
+            let request_id = request[1]["requestId"].clone();
+            client.send(&format!(
+                r#"42[{},{}]"#,
+                request_id,
+                json!({ "requestId": request_id, "status": 200, "result": "now" })
+            ));
+        });
+        let ws = Websocket::new(
+            KuzzleOptions::new("127.0.0.1", port).set_websocket_framing(WebsocketFraming::SocketIo),
+        );
+        ws.connect().unwrap();
+
+        let res = ws
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+        assert_eq!(res.result(), &json!("now"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn send_fail_connection_lost() {
+        // The server leaves without answering.
+        let (port, server) = serve(1, |mut client| {
+            client.receive();
+        });
+        let ws = connected(port);
+
+        let err = ws
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap_err();
+        assert!(err.downcast_ref::<ConnectionError>().is_some());
+        assert_eq!(ws.state(), ConnectionState::Offline);
+        assert_eq!(ws.pending_requests_count(), 0);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn send_fail_response_too_large() {
+        let (port, server) = serve(1, |mut client| {
+            client.receive();
+            client.send(&format!(
+                r#"{{"requestId":"ferris","status":200,"result":"{}"}}"#,
+                "x".repeat(100)
+            ));
+        });
+        let ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port).set_max_response_bytes(64));
+        ws.connect().unwrap();
+
+        let err = ws
+            .send(
+                KuzzleRequest::new("server", "now").set_request_id("ferris"),
+                QueryOptions::new(),
+            )
+            .unwrap_err();
+        assert!(err.downcast_ref::<SizeLimitError>().is_some());
+        assert_eq!(ws.state(), ConnectionState::Offline);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn connect_fail_server_unreachable() {
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            u32::from(listener.local_addr().unwrap().port())
+        };
+        let ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port));
+
+        assert!(ws.connect().is_err());
+        assert_eq!(ws.state(), ConnectionState::Offline);
+    }
+
+    #[test]
+    fn kuzzle_query_once_connected() {
+        let (port, server) = serve(1, echo);
+        let kuzzle = Kuzzle::new(Websocket::new(KuzzleOptions::new("127.0.0.1", port)));
+        assert!(kuzzle.server().now().is_err());
+
+        kuzzle.connect().unwrap();
+        let res = kuzzle
+            .query(KuzzleRequest::new("server", "info"), QueryOptions::new())
+            .unwrap();
+        assert_eq!(res.result()["action"], json!("info"));
+        assert!(res.result()["volatile"]["sdkInstanceId"].is_string());
+
+        kuzzle.disconnect();
+        server.join().unwrap();
+    }
+
+    /// Answers subscriptions with the `ferris-channel` channel, and writes
+    /// with a notification on that channel before their response. Other
+    /// requests are answered, then followed by a notification on each
+    /// channel, the listened one and another.
+    fn notifying(mut client: Client) {
+        while let Some(message) = client.receive() {
+            let request: Value = serde_json::from_str(&message).unwrap();
+            let request_id = request["requestId"].clone();
+            let result = match request["action"].as_str().unwrap() {
+                "subscribe" => json!({ "roomId": "ferris-room", "channel": "ferris-channel" }),
+                "create" => {
+                    // Notifications carry the id of the request behind them.
+                    client.send(
+                        &json!({
+                            "type": "document",
+                            "room": "ferris-channel",
+                            "requestId": request_id,
+                            "scope": "in",
+                            "result": { "_id": "ferris" }
+                        })
+                        .to_string(),
+                    );
+                    json!({ "_id": "ferris" })
+                }
+                _ => json!(null),
+            };
+            client.send(
+                &json!({ "requestId": request_id, "status": 200, "result": result }).to_string(),
+            );
+            if request["controller"] == json!("server") {
+                for room in &["ferris-channel", "lobster-channel"] {
+                    client.send(
+                        &json!({ "type": "document", "room": room, "scope": "out" }).to_string(),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn receive_notifications_dispatches_to_callbacks() {
+        let (port, server) = serve(1, notifying);
+        let kuzzle = Kuzzle::new(Websocket::new(KuzzleOptions::new("127.0.0.1", port)));
+        kuzzle.connect().unwrap();
+
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let sink = received.clone();
+        let options = SubscribeOptions::new().set_subscribe_to_self(true);
+        kuzzle
+            .realtime()
+            .subscribe(
+                "nyc-open-data",
+                "yellow-taxi",
+                json!({}),
+                Box::new(move |notification| sink.borrow_mut().push(notification)),
+                options,
+            )
+            .unwrap();
+
+        // Dispatched while waiting for the response of the write.
+        let res = kuzzle
+            .query(
+                KuzzleRequest::new("document", "create")
+                    .set_index("nyc-open-data")
+                    .set_collection("yellow-taxi"),
+                QueryOptions::new(),
+            )
+            .unwrap();
+        assert_eq!(res.result()["_id"], json!("ferris"));
+        assert_eq!(received.borrow().len(), 1);
+
+        // Received after the response, once waiting for notifications.
+        kuzzle
+            .query(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+        assert_eq!(received.borrow().len(), 1);
+        assert_eq!(
+            kuzzle
+                .receive_notifications(Duration::from_millis(100))
+                .unwrap(),
+            1
+        );
+        assert_eq!(received.borrow().len(), 2);
+        assert!(received
+            .borrow()
+            .iter()
+            .all(|notification| notification.room() == "ferris-channel"));
+
+        kuzzle.disconnect();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn subscribe_channel_receives_notifications_in_order() {
+        let (port, server) = serve(1, |mut client| {
+            let subscribe: Value = serde_json::from_str(&client.receive().unwrap()).unwrap();
+            client.send(
+                &json!({
+                    "requestId": subscribe["requestId"],
+                    "status": 200,
+                    "result": { "roomId": "ferris-room", "channel": "ferris-channel" }
+                })
+                .to_string(),
+            );
+            for id in &["ferris-1", "ferris-2", "ferris-3"] {
+                client.send(
+                    &json!({
+                        "type": "document",
+                        "room": "ferris-channel",
+                        "scope": "in",
+                        "result": { "_id": id }
+                    })
+                    .to_string(),
+                );
+            }
+            client.receive();
+        });
+        let kuzzle = Kuzzle::new(Websocket::new(KuzzleOptions::new("127.0.0.1", port)));
+        kuzzle.connect().unwrap();
+
+        let (room_id, notifications) = kuzzle
+            .realtime()
+            .subscribe_channel(
+                "nyc-open-data",
+                "yellow-taxi",
+                json!({}),
+                SubscribeOptions::new(),
+            )
+            .unwrap();
+        assert_eq!(room_id, "ferris-room");
+        assert!(notifications.try_recv().is_err());
+
+        kuzzle
+            .receive_notifications(Duration::from_millis(100))
+            .unwrap();
+        let ids: Vec<Value> = notifications
+            .try_iter()
+            .map(|notification| notification.result()["_id"].clone())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![json!("ferris-1"), json!("ferris-2"), json!("ferris-3")]
+        );
+
+        kuzzle.disconnect();
+        server.join().unwrap();
+    }
+
+    /// Actions received by a server, by connection.
+    type Actions = Arc<Mutex<Vec<Vec<String>>>>;
+
+    /// Serves two connections, recording the actions received on each.
+    /// Subscriptions are answered with the `ferris-channel` channel, the
+    /// first connection being dropped right after.
+    fn dropping_after_subscribe() -> (u32, JoinHandle<()>, Actions) {
+        let actions = Arc::new(Mutex::new(Vec::new()));
+        let recorded = actions.clone();
+        let (port, server) = serve(2, move |mut client| {
+            recorded.lock().unwrap().push(Vec::new());
+            let first = recorded.lock().unwrap().len() == 1;
+            while let Some(message) = client.receive() {
+                let request: Value = serde_json::from_str(&message).unwrap();
+                let action = request["action"].as_str().unwrap().to_string();
+                let result = match action.as_str() {
+                    "subscribe" => {
+                        json!({ "roomId": "ferris-room", "channel": "ferris-channel" })
+                    }
+                    _ => json!(null),
+                };
+                recorded.lock().unwrap().last_mut().unwrap().push(action);
+                client.send(
+                    &json!({ "requestId": request["requestId"], "status": 200, "result": result })
+                        .to_string(),
+                );
+                if first && request["controller"] == json!("realtime") {
+                    return;
+                }
+            }
+        });
+        (port, server, actions)
+    }
+
+    fn subscribe(kuzzle: &Kuzzle) {
+        kuzzle
+            .realtime()
+            .subscribe(
+                "nyc-open-data",
+                "yellow-taxi",
+                json!({}),
+                Box::new(|_| {}),
+                SubscribeOptions::new(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn reconnect_then_resubscribe() {
+        let (port, server, actions) = dropping_after_subscribe();
+        let kuzzle = Kuzzle::new(Websocket::new(
+            KuzzleOptions::new("127.0.0.1", port).set_reconnection_delay(0),
+        ));
+        kuzzle.connect().unwrap();
+        subscribe(&kuzzle);
+
+        let err = kuzzle
+            .receive_notifications(Duration::from_secs(5))
+            .unwrap_err();
+        assert!(err.downcast_ref::<ConnectionError>().is_some());
+
+        kuzzle
+            .query(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+        assert_eq!(kuzzle.subscriptions().len(), 1);
+
+        kuzzle.disconnect();
+        server.join().unwrap();
+        assert_eq!(
+            *actions.lock().unwrap(),
+            vec![vec!["subscribe"], vec!["subscribe", "now"]]
+        );
+    }
+
+    #[test]
+    fn no_reconnection_once_closed() {
+        let (port, server, actions) = dropping_after_subscribe();
+        let kuzzle = Kuzzle::new(Websocket::new(
+            KuzzleOptions::new("127.0.0.1", port)
+                .set_reconnection_delay(0)
+                .set_auto_queue(true)
+                .set_auto_replay(true),
+        ));
+        kuzzle.connect().unwrap();
+        subscribe(&kuzzle);
+        kuzzle.disconnect();
+
+        let err = kuzzle
+            .query(KuzzleRequest::new("server", "info"), QueryOptions::new())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Websocket::send] not connected to Kuzzle, the request is queued."
+        );
+
+        // Connecting again resubscribes, then replays the queue.
+        kuzzle.connect().unwrap();
+        kuzzle
+            .query(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+        assert!(kuzzle.queue_stats().is_empty());
+
+        kuzzle.disconnect();
+        server.join().unwrap();
+        assert_eq!(
+            *actions.lock().unwrap(),
+            vec![vec!["subscribe"], vec!["subscribe", "info", "now"]]
+        );
+    }
+
+    #[test]
+    fn connect_sends_client_metadata() {
+        let (port, server) = serve(1, |client| {
+            let metadata = client
+                .headers()
+                .lines()
+                .find(|line| line.starts_with("x-kuzzle-client-metadata: "))
+                .map(|line| line["x-kuzzle-client-metadata: ".len()..].to_string())
+                .unwrap();
+            let metadata: Value = serde_json::from_str(&metadata).unwrap();
+            assert_eq!(metadata["service"], json!("crab-tracker"));
+            assert_eq!(metadata["pid"], json!(std::process::id()));
+        });
+
+        let mut metadata = serde_json::Map::new();
+        metadata.insert("service".to_string(), json!("crab-tracker"));
+        let ws =
+            Websocket::new(KuzzleOptions::new("127.0.0.1", port).set_client_metadata(metadata));
+        ws.connect().unwrap();
+
+        ws.close();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn cancel_request_in_flight() {
+        let (port, server) = serve(1, |mut client| {
+            client.receive();
+            client.send(r#"{"type":"document","room":"ferris-channel","scope":"in"}"#);
+            client.send(r#"{"requestId":"ferris","status":200,"result":null}"#);
+            client.receive();
+        });
+        let ws = Rc::new(connected(port));
+        let canceller = Rc::downgrade(&ws);
+        ws.listen(
+            "ferris-channel",
+            Box::new(move |_| {
+                assert!(canceller.upgrade().unwrap().cancel_request("ferris"));
+            }),
+        );
+
+        let err = ws
+            .send(
+                KuzzleRequest::new("document", "search").set_request_id("ferris"),
+                QueryOptions::new(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Websocket::send] request ferris was cancelled."
+        );
+        assert_eq!(ws.pending_requests_count(), 0);
+
+        // The late response is ignored.
+        assert_eq!(ws.receive(Some(Duration::from_millis(100))).unwrap(), 0);
+        ws.close();
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn cancel_request_queued() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512).set_auto_queue(true));
+        queue(
+            &ws,
+            KuzzleRequest::new("server", "now").set_request_id("ferris"),
+        );
+        queue(&ws, KuzzleRequest::new("server", "info"));
+
+        assert!(ws.cancel_request("ferris"));
+        assert!(!ws.cancel_request("ferris"));
+        assert_eq!(ws.queue_stats().len(), 1);
+        assert_eq!(ws.queue_stats().dropped_total(), 0);
     }
 }
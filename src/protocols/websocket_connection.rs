@@ -0,0 +1,495 @@
+use crate::types::{ConnectionError, SdkError, SizeLimitError};
+use native_tls::TlsConnector;
+use std::error::Error;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// Appended to the handshake key to compute the `Sec-WebSocket-Accept`
+/// header the server answers with, see RFC 6455 section 1.3.
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Upper bound of the handshake response, which is read a byte at a time.
+const MAX_HANDSHAKE_BYTES: usize = 8192;
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+trait Stream: Read + Write {}
+
+impl<T: Read + Write> Stream for T {}
+
+/// A client WebSocket connection (RFC 6455), over TCP or TLS.
+pub(crate) struct Connection {
+    _stream: Box<dyn Stream>,
+    // Same socket as `_stream`, kept to set read timeouts.
+    _socket: TcpStream,
+}
+
+impl Connection {
+    /// Opens a TCP connection, wrapped in TLS if `ssl` is set, and upgrades
+    /// it to a WebSocket connection on `path` with the given extra headers.
+    pub(crate) fn open(
+        host: &str,
+        port: u32,
+        ssl: bool,
+        path: &str,
+        headers: &[(&str, String)],
+        connect_timeout: &Option<Duration>,
+    ) -> Result<Connection, Box<dyn Error>> {
+        let socket = match connect_timeout {
+            Some(timeout) => {
+                let address = (host, port as u16)
+                    .to_socket_addrs()?
+                    .next()
+                    .ok_or_else(|| {
+                        SdkError::new("Websocket::connect", &format!("unknown host {}.", host))
+                    })?;
+                TcpStream::connect_timeout(&address, *timeout)?
+            }
+            None => TcpStream::connect((host, port as u16))?,
+        };
+        socket.set_nodelay(true)?;
+
+        let stream: Box<dyn Stream> = if ssl {
+            Box::new(TlsConnector::new()?.connect(host, socket.try_clone()?)?)
+        } else {
+            Box::new(socket.try_clone()?)
+        };
+        let mut connection = Connection {
+            _stream: stream,
+            _socket: socket,
+        };
+        connection.handshake(host, port, path, headers)?;
+        Ok(connection)
+    }
+
+    /// Sends the HTTP upgrade request and checks the server accepted it.
+    fn handshake(
+        &mut self,
+        host: &str,
+        port: u32,
+        path: &str,
+        headers: &[(&str, String)],
+    ) -> Result<(), Box<dyn Error>> {
+        let key = base64::encode(&rand::random::<[u8; 16]>());
+        let mut request = format!(
+            "GET {} HTTP/1.1\r\n\
+             Host: {}:{}\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Key: {}\r\n\
+             Sec-WebSocket-Version: 13\r\n",
+            path, host, port, key
+        );
+        for (name, value) in headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        request.push_str("\r\n");
+        self._stream.write_all(request.as_bytes())?;
+        self._stream.flush()?;
+
+        // Read byte per byte: the first frames may follow the headers.
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        while !response.ends_with(b"\r\n\r\n") {
+            if response.len() >= MAX_HANDSHAKE_BYTES {
+                return Err(Box::new(SdkError::new(
+                    "Websocket::connect",
+                    "handshake response too large.",
+                )));
+            }
+            self._stream.read_exact(&mut byte)?;
+            response.push(byte[0]);
+        }
+        let response = String::from_utf8_lossy(&response);
+
+        let mut lines = response.split("\r\n");
+        let status_line = lines.next().unwrap_or_default();
+        if status_line.split(' ').nth(1) != Some("101") {
+            return Err(Box::new(SdkError::new(
+                "Websocket::connect",
+                &format!("handshake refused: {}.", status_line),
+            )));
+        }
+        let accept = lines
+            .filter_map(|line| {
+                let mut header = line.splitn(2, ':');
+                match (header.next(), header.next()) {
+                    (Some(name), Some(value)) => Some((name.trim(), value.trim())),
+                    _ => None,
+                }
+            })
+            .find(|(name, _)| name.eq_ignore_ascii_case("sec-websocket-accept"))
+            .map(|(_, value)| value);
+        if accept != Some(accept_key(&key).as_str()) {
+            return Err(Box::new(SdkError::new(
+                "Websocket::connect",
+                "handshake failed: invalid Sec-WebSocket-Accept header.",
+            )));
+        }
+        Ok(())
+    }
+
+    /// Sends a text message, in a single masked frame.
+    pub(crate) fn send_text(&mut self, text: &str) -> io::Result<()> {
+        write_frame(
+            &mut self._stream,
+            OPCODE_TEXT,
+            text.as_bytes(),
+            Some(rand::random()),
+        )
+    }
+
+    /// Reads the next data message, answering pings on the way. Returns
+    /// `None` if no message started within `timeout`, when given.
+    /// Messages larger than `max_bytes` fail with a `SizeLimitError`.
+    pub(crate) fn read_message(
+        &mut self,
+        timeout: Option<Duration>,
+        max_bytes: &Option<u64>,
+    ) -> Result<Option<String>, Box<dyn Error>> {
+        let mut message = Vec::new();
+        let mut started = false;
+        loop {
+            // Only the first byte of a message is awaited with a timeout:
+            // giving up in the middle of a message would lose its end.
+            self._socket
+                .set_read_timeout(if started { None } else { timeout })?;
+            let mut head = [0u8; 1];
+            match self._stream.read(&mut head) {
+                Ok(0) => {
+                    return Err(Box::new(ConnectionError::new(
+                        "Connection closed by Kuzzle.",
+                    )))
+                }
+                Ok(_) => {}
+                Err(ref err)
+                    if !started
+                        && (err.kind() == io::ErrorKind::WouldBlock
+                            || err.kind() == io::ErrorKind::TimedOut) =>
+                {
+                    return Ok(None)
+                }
+                Err(err) => return Err(Box::new(err)),
+            }
+            self._socket.set_read_timeout(None)?;
+
+            let (fin, opcode, payload) = read_frame(&mut self._stream, head[0], max_bytes)?;
+            match opcode {
+                OPCODE_TEXT | OPCODE_BINARY | OPCODE_CONTINUATION => {
+                    started = true;
+                    message.extend_from_slice(&payload);
+                    if let Some(max_bytes) = max_bytes {
+                        if message.len() as u64 > *max_bytes {
+                            return Err(Box::new(SizeLimitError::new(*max_bytes)));
+                        }
+                    }
+                    if fin {
+                        return Ok(Some(String::from_utf8(message)?));
+                    }
+                }
+                OPCODE_PING => {
+                    write_frame(
+                        &mut self._stream,
+                        OPCODE_PONG,
+                        &payload,
+                        Some(rand::random()),
+                    )?;
+                }
+                OPCODE_CLOSE => {
+                    self.close();
+                    return Err(Box::new(ConnectionError::new(
+                        "Connection closed by Kuzzle.",
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Sends a normal closure frame, then shuts the socket down.
+    pub(crate) fn close(&mut self) {
+        let _ = write_frame(
+            &mut self._stream,
+            OPCODE_CLOSE,
+            &1000u16.to_be_bytes(),
+            Some(rand::random()),
+        );
+        let _ = self._socket.shutdown(std::net::Shutdown::Both);
+    }
+}
+
+/// Writes a final frame, its payload masked with `mask` if given, as
+/// clients must do.
+pub(crate) fn write_frame<W: Write>(
+    writer: &mut W,
+    opcode: u8,
+    payload: &[u8],
+    mask: Option<[u8; 4]>,
+) -> io::Result<()> {
+    let mut frame = vec![0x80 | opcode];
+    let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+    match payload.len() {
+        len if len < 126 => frame.push(mask_bit | len as u8),
+        len if len <= 0xFFFF => {
+            frame.push(mask_bit | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(mask_bit | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    match mask {
+        Some(mask) => {
+            frame.extend_from_slice(&mask);
+            frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        }
+        None => frame.extend_from_slice(payload),
+    }
+    writer.write_all(&frame)?;
+    writer.flush()
+}
+
+/// Reads the rest of a frame whose first byte is `head`, returning whether
+/// it is final, its opcode and its unmasked payload.
+pub(crate) fn read_frame<R: Read>(
+    reader: &mut R,
+    head: u8,
+    max_bytes: &Option<u64>,
+) -> Result<(bool, u8, Vec<u8>), Box<dyn Error>> {
+    let mut byte = [0u8; 1];
+    reader.read_exact(&mut byte)?;
+    let masked = byte[0] & 0x80 != 0;
+    let len = match byte[0] & 0x7F {
+        126 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len)?;
+            u64::from(u16::from_be_bytes(len))
+        }
+        127 => {
+            let mut len = [0u8; 8];
+            reader.read_exact(&mut len)?;
+            u64::from_be_bytes(len)
+        }
+        len => u64::from(len),
+    };
+    if let Some(max_bytes) = max_bytes {
+        if len > *max_bytes {
+            return Err(Box::new(SizeLimitError::new(*max_bytes)));
+        }
+    }
+
+    let mut mask = [0u8; 4];
+    if masked {
+        reader.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload)?;
+    if masked {
+        for (i, b) in payload.iter_mut().enumerate() {
+            *b ^= mask[i % 4];
+        }
+    }
+    Ok((head & 0x80 != 0, head & 0x0F, payload))
+}
+
+/// The `Sec-WebSocket-Accept` value matching a `Sec-WebSocket-Key`.
+pub(crate) fn accept_key(key: &str) -> String {
+    base64::encode(&sha1(format!("{}{}", key, ACCEPT_GUID).as_bytes()))
+}
+
+/// SHA-1 digest, only used to check the handshake (RFC 3174).
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [
+        0x6745_2301,
+        0xEFCD_AB89,
+        0x98BA_DCFE,
+        0x1032_5476,
+        0xC3D2_E1F0,
+    ];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ED9_EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+                _ => (b ^ c ^ d, 0xCA62_C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (state, value) in h.iter_mut().zip(&[a, b, c, d, e]) {
+            *state = state.wrapping_add(*value);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (bytes, word) in digest.chunks_mut(4).zip(&h) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// A single-client WebSocket server, standing for Kuzzle in tests.
+#[cfg(test)]
+pub(crate) mod test_server {
+    use super::{accept_key, read_frame, write_frame, OPCODE_TEXT};
+    use std::io::Read;
+    use std::net::{TcpListener, TcpStream};
+    use std::thread::{self, JoinHandle};
+
+    /// The connection of the client, server side.
+    pub(crate) struct Client {
+        stream: TcpStream,
+        headers: String,
+    }
+
+    impl Client {
+        /// The raw headers of the handshake request.
+        pub(crate) fn headers(&self) -> &str {
+            &self.headers
+        }
+
+        /// Reads the next text message, `None` once the client is gone.
+        pub(crate) fn receive(&mut self) -> Option<String> {
+            loop {
+                let mut head = [0u8; 1];
+                if self.stream.read(&mut head).ok()? == 0 {
+                    return None;
+                }
+                let (_, opcode, payload) = read_frame(&mut self.stream, head[0], &None).ok()?;
+                if opcode == OPCODE_TEXT {
+                    return String::from_utf8(payload).ok();
+                }
+            }
+        }
+
+        /// Sends a text message.
+        pub(crate) fn send(&mut self, message: &str) {
+            write_frame(&mut self.stream, OPCODE_TEXT, message.as_bytes(), None).unwrap();
+        }
+    }
+
+    /// Listens on a random local port, returned along with the thread
+    /// running `handler` on each accepted connection, `connections` times.
+    pub(crate) fn serve<F>(connections: usize, handler: F) -> (u32, JoinHandle<()>)
+    where
+        F: Fn(Client) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = u32::from(listener.local_addr().unwrap().port());
+        let server = thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let mut stream = stream.unwrap();
+                let mut headers = Vec::new();
+                let mut byte = [0u8; 1];
+                while !headers.ends_with(b"\r\n\r\n") {
+                    stream.read_exact(&mut byte).unwrap();
+                    headers.push(byte[0]);
+                }
+                let headers = String::from_utf8(headers).unwrap();
+                let key = headers
+                    .lines()
+                    .find(|line| line.starts_with("Sec-WebSocket-Key:"))
+                    .map(|line| line["Sec-WebSocket-Key:".len()..].trim())
+                    .unwrap();
+                let response = format!(
+                    "HTTP/1.1 101 Switching Protocols\r\n\
+                     Upgrade: websocket\r\n\
+                     Connection: Upgrade\r\n\
+                     Sec-WebSocket-Accept: {}\r\n\r\n",
+                    accept_key(key)
+                );
+                std::io::Write::write_all(&mut stream, response.as_bytes()).unwrap();
+                handler(Client { stream, headers });
+            }
+        });
+        (port, server)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_key_rfc_example() {
+        // RFC 6455 section 1.3.
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn sha1_known_digests() {
+        let hex = |digest: [u8; 20]| {
+            digest
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<String>()
+        };
+        assert_eq!(hex(sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(
+            hex(sha1(b"The quick brown fox jumps over the lazy dog")),
+            "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12"
+        );
+    }
+
+    #[test]
+    fn frame_round_trip() {
+        for len in &[5, 300, 70_000] {
+            let payload = vec![b'x'; *len];
+            let mut frame = Vec::new();
+            write_frame(&mut frame, OPCODE_TEXT, &payload, Some([1, 2, 3, 4])).unwrap();
+
+            let mut reader = &frame[1..];
+            let (fin, opcode, read) = read_frame(&mut reader, frame[0], &None).unwrap();
+            assert!(fin);
+            assert_eq!(opcode, OPCODE_TEXT);
+            assert_eq!(read, payload);
+        }
+    }
+
+    #[test]
+    fn read_frame_fail_above_max_bytes() {
+        let mut frame = Vec::new();
+        write_frame(&mut frame, OPCODE_TEXT, &[b'x'; 65], None).unwrap();
+
+        let mut reader = &frame[1..];
+        let err = read_frame(&mut reader, frame[0], &Some(64)).unwrap_err();
+        assert!(err.downcast_ref::<SizeLimitError>().is_some());
+    }
+}
@@ -0,0 +1,9 @@
+/// The state of the connection between the SDK and Kuzzle, as reported
+/// by `Protocol::state`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ConnectionState {
+    #[default]
+    Offline,
+    Connecting,
+    Connected,
+}
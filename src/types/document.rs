@@ -0,0 +1,47 @@
+use crate::types::path_deserializer::source_as;
+use crate::types::{Hit, SdkError};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// A Kuzzle document: its id, content and version.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Document {
+    _id: String,
+    _source: Map<String, Value>,
+    _version: u64,
+}
+
+impl Document {
+    /// Builds a `Document` out of a search hit, whose version is 0 if
+    /// the search did not return it.
+    pub(crate) fn from_hit(hit: &Hit) -> Document {
+        Document {
+            _id: hit.id().clone(),
+            _source: hit.source().clone(),
+            _version: hit.version().unwrap_or(0),
+        }
+    }
+
+    /// Document id getter.
+    pub fn id(&self) -> &String {
+        &self._id
+    }
+
+    /// Document source getter.
+    pub fn source(&self) -> &Map<String, Value> {
+        &self._source
+    }
+
+    /// Deserializes the document source into a `T`.
+    pub fn source_as<T>(&self) -> Result<T, SdkError>
+    where
+        T: DeserializeOwned,
+    {
+        source_as("Document::source_as", &self._id, &self._source)
+    }
+
+    /// Document version getter.
+    pub fn version(&self) -> u64 {
+        self._version
+    }
+}
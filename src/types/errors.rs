@@ -8,15 +8,55 @@ pub struct KuzzleError {
     status: Option<u16>,
     message: String,
     stack: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
 }
 
+/// User-friendly English messages of common Kuzzle errors, keyed by error id,
+/// for `KuzzleError::localized_message`.
+pub const ENGLISH_ERROR_MESSAGES: &[(&str, &str)] = &[
+    (
+        "api.process.overloaded",
+        "The server is busy, please try again in a moment.",
+    ),
+    (
+        "security.rights.forbidden",
+        "You are not allowed to do this.",
+    ),
+    (
+        "security.token.expired",
+        "Your session has expired, please log in again.",
+    ),
+    (
+        "security.token.invalid",
+        "Your session is invalid, please log in again.",
+    ),
+    (
+        "services.storage.document_already_exists",
+        "This item already exists.",
+    ),
+    (
+        "services.storage.not_found",
+        "This item could not be found.",
+    ),
+    (
+        "services.storage.unknown_collection",
+        "This collection does not exist.",
+    ),
+    (
+        "services.storage.unknown_index",
+        "This index does not exist.",
+    ),
+];
+
 impl KuzzleError {
     /// Returns a custom KuzzleError with the given status and message
     ///
     /// # Arguments
     ///
     /// * `status` - An `Option<u16>` that can be `Some(value)` or `None`.
-    /// Depends you want to specify a custom status code.
+    ///   Depends you want to specify a custom status code.
     /// * `message` - A string slice that holds your custom error message.
     ///
     /// # Example
@@ -32,6 +72,7 @@ impl KuzzleError {
             status,
             message: message.to_string(),
             stack: None,
+            id: None,
         }
     }
 
@@ -49,6 +90,40 @@ impl KuzzleError {
     pub fn stack(&self) -> &Option<String> {
         &self.stack
     }
+
+    /// KuzzleError id getter: a stable error identifier such as
+    /// `security.token.expired`, sent by Kuzzle 2 and later.
+    pub fn id(&self) -> &Option<String> {
+        &self.id
+    }
+
+    /// Returns the message `messages` maps to the error id, to show end
+    /// users, or the server message if the error has no id or no entry.
+    /// `ENGLISH_ERROR_MESSAGES` covers common errors, other languages are
+    /// given as tables of the same form.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::types::{KuzzleError, ENGLISH_ERROR_MESSAGES};
+    ///
+    /// const FRENCH: &[(&str, &str)] = &[
+    ///     ("security.token.expired", "Votre session a expiré, veuillez vous reconnecter."),
+    /// ];
+    ///
+    /// let err = KuzzleError::new(Some(500), "Unexpected error");
+    /// assert_eq!(err.localized_message(FRENCH), "Unexpected error");
+    /// assert_eq!(err.localized_message(ENGLISH_ERROR_MESSAGES), "Unexpected error");
+    /// ```
+    pub fn localized_message(&self, messages: &[(&str, &str)]) -> String {
+        let localized = self.id.as_ref().and_then(|id| {
+            messages
+                .iter()
+                .find(|(message_id, _)| message_id == id)
+                .map(|(_, message)| message.to_string())
+        });
+        localized.unwrap_or_else(|| self.message.clone())
+    }
 }
 
 impl error::Error for KuzzleError {}
@@ -103,7 +178,7 @@ impl SdkError {
     /// # Arguments
     ///
     /// * `cause` - A `&str` containing name of the function, method or controller
-    /// that triggered the error.
+    ///   that triggered the error.
     /// * `message` - A `&str` slice that holds your custom error message.
     ///
     /// # Example
@@ -139,3 +214,141 @@ impl fmt::Display for SdkError {
         write!(f, "[{}] {}", self.cause, self.message)
     }
 }
+
+/// Connection relative error. Triggered when a request cannot be sent
+/// because the SDK is not connected to Kuzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionError {
+    message: String,
+}
+
+impl ConnectionError {
+    /// Returns a ConnectionError with the given message
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::types::ConnectionError;
+    /// let offline = ConnectionError::new("Unable to execute request: not connected to Kuzzle.");
+    /// assert_eq!(
+    ///     format!("{}", offline),
+    ///     format!("[ConnectionError] {}", offline.message())
+    /// );
+    /// ```
+    pub fn new(message: &str) -> ConnectionError {
+        ConnectionError {
+            message: message.to_string(),
+        }
+    }
+
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+}
+
+impl error::Error for ConnectionError {}
+
+impl fmt::Display for ConnectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[ConnectionError] {}", self.message)
+    }
+}
+
+/// Response size relative error. Triggered when a response body exceeds
+/// the maximum set by `KuzzleOptions::set_max_response_bytes`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeLimitError {
+    max_bytes: u64,
+}
+
+impl SizeLimitError {
+    /// Returns a SizeLimitError for the given maximum size, in bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::types::SizeLimitError;
+    /// let too_large = SizeLimitError::new(1024);
+    /// assert_eq!(
+    ///     format!("{}", too_large),
+    ///     "[SizeLimitError] Response size exceeds the configured maximum (1024 bytes)."
+    /// );
+    /// ```
+    pub fn new(max_bytes: u64) -> SizeLimitError {
+        SizeLimitError { max_bytes }
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+}
+
+impl error::Error for SizeLimitError {}
+
+impl fmt::Display for SizeLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[SizeLimitError] Response size exceeds the configured maximum ({} bytes).",
+            self.max_bytes
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_value, json};
+
+    #[test]
+    fn localized_message_ok_english() {
+        let err: KuzzleError = from_value(json!({
+            "status": 401,
+            "message": "Token expired",
+            "id": "security.token.expired"
+        }))
+        .unwrap();
+
+        assert_eq!(err.id(), &Some("security.token.expired".to_string()));
+        assert_eq!(
+            err.localized_message(ENGLISH_ERROR_MESSAGES),
+            "Your session has expired, please log in again."
+        );
+    }
+
+    #[test]
+    fn localized_message_ok_custom_table() {
+        let err: KuzzleError = from_value(json!({
+            "status": 404,
+            "message": "Document \"nemo\" not found.",
+            "id": "services.storage.not_found"
+        }))
+        .unwrap();
+        let french = [("services.storage.not_found", "Cet élément est introuvable.")];
+
+        assert_eq!(
+            err.localized_message(&french),
+            "Cet élément est introuvable."
+        );
+    }
+
+    #[test]
+    fn localized_message_ok_fallback_to_server_message() {
+        let unmapped: KuzzleError = from_value(json!({
+            "status": 400,
+            "message": "Crabs cannot fly.",
+            "id": "plugin.crabs.cannot_fly"
+        }))
+        .unwrap();
+        let without_id = KuzzleError::new(Some(400), "Crabs cannot swim backwards.");
+
+        assert_eq!(
+            unmapped.localized_message(ENGLISH_ERROR_MESSAGES),
+            "Crabs cannot fly."
+        );
+        assert_eq!(
+            without_id.localized_message(ENGLISH_ERROR_MESSAGES),
+            "Crabs cannot swim backwards."
+        );
+    }
+}
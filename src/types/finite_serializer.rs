@@ -0,0 +1,394 @@
+//! A `Serializer` walking a value before it is turned into a
+//! `serde_json::Value`, as `serde_json` silently serializes NaN and infinite
+//! floats as `null`. The path of the offending float is reported instead
+//! (e.g. `positions[1].lat`).
+
+use crate::types::SdkError;
+use serde::ser::{
+    self, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+use serde_json::{to_value, Value};
+use std::error;
+use std::fmt;
+
+/// Turns `value` into a `serde_json::Value`, failing with an `SdkError`
+/// naming the offending path if it holds a NaN or infinite float.
+/// `path` is the name of the request argument `value` is given for.
+pub(crate) fn to_finite_value<T>(path: &str, value: &T) -> Result<Value, SdkError>
+where
+    T: ?Sized + Serialize,
+{
+    let check = FiniteCheck {
+        path: path.to_string(),
+    };
+    if let Err(CheckError::NonFinite(path)) = value.serialize(check) {
+        return Err(SdkError::new(
+            "KuzzleRequest",
+            &format!("`{}` must be a finite number.", path),
+        ));
+    }
+
+    to_value(value).map_err(|err| {
+        SdkError::new(
+            "KuzzleRequest",
+            &format!("`{}` cannot be serialized: {}", path, err),
+        )
+    })
+}
+
+#[derive(Debug)]
+enum CheckError {
+    NonFinite(String),
+    // Raised by `Serialize` implementations: `to_value` reports them.
+    Custom(String),
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckError::NonFinite(path) => write!(f, "`{}` must be a finite number.", path),
+            CheckError::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl error::Error for CheckError {}
+
+impl ser::Error for CheckError {
+    fn custom<T: fmt::Display>(msg: T) -> CheckError {
+        CheckError::Custom(msg.to_string())
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+struct FiniteCheck {
+    path: String,
+}
+
+impl FiniteCheck {
+    fn check(self, finite: bool) -> Result<(), CheckError> {
+        if finite {
+            Ok(())
+        } else {
+            Err(CheckError::NonFinite(self.path))
+        }
+    }
+}
+
+macro_rules! accept {
+    ($($method:ident: $type:ty),*) => {
+        $(
+            fn $method(self, _value: $type) -> Result<(), CheckError> {
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl Serializer for FiniteCheck {
+    type Ok = ();
+    type Error = CheckError;
+    type SerializeSeq = SeqCheck;
+    type SerializeTuple = SeqCheck;
+    type SerializeTupleStruct = SeqCheck;
+    type SerializeTupleVariant = SeqCheck;
+    type SerializeMap = MapCheck;
+    type SerializeStruct = MapCheck;
+    type SerializeStructVariant = MapCheck;
+
+    accept!(
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_char: char,
+        serialize_str: &str,
+        serialize_bytes: &[u8],
+        serialize_unit_struct: &'static str
+    );
+
+    fn serialize_f32(self, value: f32) -> Result<(), CheckError> {
+        self.check(value.is_finite())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), CheckError> {
+        self.check(value.is_finite())
+    }
+
+    fn serialize_none(self) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), CheckError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), CheckError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), CheckError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(FiniteCheck {
+            path: join(&self.path, variant),
+        })
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqCheck, CheckError> {
+        Ok(SeqCheck {
+            path: self.path,
+            index: 0,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqCheck, CheckError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqCheck, CheckError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SeqCheck, CheckError> {
+        Ok(SeqCheck {
+            path: join(&self.path, variant),
+            index: 0,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapCheck, CheckError> {
+        Ok(MapCheck {
+            path: self.path,
+            key: String::new(),
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<MapCheck, CheckError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<MapCheck, CheckError> {
+        Ok(MapCheck {
+            path: join(&self.path, variant),
+            key: String::new(),
+        })
+    }
+}
+
+struct SeqCheck {
+    path: String,
+    index: usize,
+}
+
+impl SeqCheck {
+    fn check_item<T>(&mut self, value: &T) -> Result<(), CheckError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let path = format!("{}[{}]", self.path, self.index);
+        self.index += 1;
+        value.serialize(FiniteCheck { path })
+    }
+}
+
+macro_rules! seq_check {
+    ($($trait:ident: $method:ident),*) => {
+        $(
+            impl $trait for SeqCheck {
+                type Ok = ();
+                type Error = CheckError;
+
+                fn $method<T>(&mut self, value: &T) -> Result<(), CheckError>
+                where
+                    T: ?Sized + Serialize,
+                {
+                    self.check_item(value)
+                }
+
+                fn end(self) -> Result<(), CheckError> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+seq_check!(
+    SerializeSeq: serialize_element,
+    SerializeTuple: serialize_element,
+    SerializeTupleStruct: serialize_field,
+    SerializeTupleVariant: serialize_field
+);
+
+struct MapCheck {
+    path: String,
+    key: String,
+}
+
+impl SerializeMap for MapCheck {
+    type Ok = ();
+    type Error = CheckError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), CheckError>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.key = match to_value(key) {
+            Ok(Value::String(key)) => key,
+            Ok(key) => key.to_string(),
+            Err(err) => return Err(CheckError::Custom(err.to_string())),
+        };
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), CheckError>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(FiniteCheck {
+            path: join(&self.path, &self.key),
+        })
+    }
+
+    fn end(self) -> Result<(), CheckError> {
+        Ok(())
+    }
+}
+
+macro_rules! struct_check {
+    ($($trait:ident),*) => {
+        $(
+            impl $trait for MapCheck {
+                type Ok = ();
+                type Error = CheckError;
+
+                fn serialize_field<T>(
+                    &mut self,
+                    key: &'static str,
+                    value: &T,
+                ) -> Result<(), CheckError>
+                where
+                    T: ?Sized + Serialize,
+                {
+                    value.serialize(FiniteCheck {
+                        path: join(&self.path, key),
+                    })
+                }
+
+                fn end(self) -> Result<(), CheckError> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+struct_check!(SerializeStruct, SerializeStructVariant);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Position {
+        lat: f64,
+        lon: f64,
+    }
+
+    #[test]
+    fn to_finite_value_ok() {
+        let value = to_finite_value(
+            "positions",
+            &vec![Position {
+                lat: 43.6,
+                lon: 3.9,
+            }],
+        )
+        .unwrap();
+
+        assert_eq!(value, serde_json::json!([{ "lat": 43.6, "lon": 3.9 }]));
+    }
+
+    #[test]
+    fn to_finite_value_fail_nan_names_path() {
+        let positions = vec![
+            Position {
+                lat: 43.6,
+                lon: 3.9,
+            },
+            Position {
+                lat: f64::NAN,
+                lon: 3.9,
+            },
+        ];
+        let err = to_finite_value("positions", &positions).unwrap_err();
+
+        assert_eq!(err.message(), "`positions[1].lat` must be a finite number.");
+    }
+
+    #[test]
+    fn to_finite_value_fail_infinite_map_value() {
+        let mut scores = BTreeMap::new();
+        scores.insert("ferris", f32::INFINITY);
+        let err = to_finite_value("scores", &scores).unwrap_err();
+
+        assert_eq!(err.message(), "`scores.ferris` must be a finite number.");
+    }
+}
@@ -0,0 +1,96 @@
+use crate::types::path_deserializer::source_as;
+use crate::types::SdkError;
+use serde::de::DeserializeOwned;
+use serde_json::{from_value, Map, Value};
+use std::convert::TryFrom;
+
+/// A search hit: a document along with its relevance score.
+/// The raw hit is kept for fields which have no dedicated getter,
+/// such as `sort` or `highlight`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "Value", into = "Value")]
+pub struct Hit {
+    id: String,
+    source: Map<String, Value>,
+    score: Option<f64>,
+    version: Option<u64>,
+    raw: Value,
+}
+
+#[derive(Deserialize)]
+struct HitFields {
+    _id: String,
+    #[serde(default)]
+    _source: Map<String, Value>,
+    #[serde(default)]
+    _score: Option<f64>,
+    #[serde(default)]
+    _version: Option<u64>,
+}
+
+impl Hit {
+    /// Hit id getter.
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Hit source getter.
+    pub fn source(&self) -> &Map<String, Value> {
+        &self.source
+    }
+
+    /// Deserializes the hit source into a `T`.
+    pub fn source_as<T>(&self) -> Result<T, SdkError>
+    where
+        T: DeserializeOwned,
+    {
+        source_as("Hit::source_as", &self.id, &self.source)
+    }
+
+    /// Hit score getter.
+    /// Returns `None` when Kuzzle did not compute it, e.g. on sorted searches.
+    pub fn score(&self) -> Option<f64> {
+        self.score
+    }
+
+    /// Hit version getter.
+    pub fn version(&self) -> Option<u64> {
+        self.version
+    }
+
+    /// Index of the hit, for hits of a multi-target search.
+    pub fn index(&self) -> Option<&str> {
+        self.raw["index"].as_str()
+    }
+
+    /// Collection of the hit, for hits of a multi-target search.
+    pub fn collection(&self) -> Option<&str> {
+        self.raw["collection"].as_str()
+    }
+
+    /// Returns the hit as sent by Kuzzle.
+    pub fn raw(&self) -> &Value {
+        &self.raw
+    }
+}
+
+impl TryFrom<Value> for Hit {
+    type Error = serde_json::Error;
+
+    fn try_from(raw: Value) -> Result<Hit, serde_json::Error> {
+        let fields: HitFields = from_value(raw.clone())?;
+        Ok(Hit {
+            id: fields._id,
+            source: fields._source,
+            score: fields._score,
+            version: fields._version,
+            raw,
+        })
+    }
+}
+
+impl From<Hit> for Value {
+    fn from(hit: Hit) -> Value {
+        hit.raw
+    }
+}
@@ -1,9 +1,45 @@
+mod connection_state;
+mod document;
 mod errors;
+mod finite_serializer;
+mod hit;
+mod notification;
 mod options;
+mod path_deserializer;
+mod profile;
+mod queue_stats;
 mod request;
+mod request_id;
 mod response;
+mod retry_policy;
+mod right;
+mod role;
+mod search_result;
+mod server_limits;
+mod stats_snapshot;
+mod subscription;
+mod user;
 
-pub use self::errors::{KuzzleError, SdkError};
-pub use self::options::{KuzzleOptions, OfflineMode, QueryOptions};
+pub use self::connection_state::ConnectionState;
+pub use self::document::Document;
+pub use self::errors::{
+    ConnectionError, KuzzleError, SdkError, SizeLimitError, ENGLISH_ERROR_MESSAGES,
+};
+pub use self::hit::Hit;
+pub use self::notification::{Notification, NotificationKind};
+pub use self::options::{
+    KuzzleOptions, MsSetOptions, OfflineMode, QueryOptions, RefreshMode, WebsocketFraming,
+};
+pub use self::profile::{Policy, Profile};
+pub use self::queue_stats::QueueStats;
 pub use self::request::KuzzleRequest;
+pub use self::request_id::{RequestIdGenerator, SequentialGenerator, UuidGenerator};
 pub use self::response::KuzzleResponse;
+pub use self::retry_policy::RetryPolicy;
+pub use self::right::{Right, RightValue};
+pub use self::role::Role;
+pub use self::search_result::SearchResult;
+pub use self::server_limits::ServerLimits;
+pub use self::stats_snapshot::StatsSnapshot;
+pub use self::subscription::{Scope, State, SubscribeOptions, SubscriptionInfo, Users};
+pub use self::user::User;
@@ -0,0 +1,332 @@
+use crate::types::{Scope, Users};
+use serde_json::{Map, Value};
+
+/// A realtime notification, received on the channel of a subscription.
+///
+/// * `document` notifications describe a document entering or leaving the
+///   subscription scope (`scope`), `result` holding the document.
+/// * `user` notifications describe a user joining or leaving the room
+///   (`user`), `result` holding the number of subscribers.
+/// * `TokenExpired` notifications tell that the authentication token of
+///   the connection expired, ending its subscriptions.
+///
+/// `kind` gives the content of each type as a `NotificationKind`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Notification {
+    #[serde(rename = "type")]
+    notification_type: String,
+    #[serde(default)]
+    room: String,
+    #[serde(rename = "requestId", default)]
+    request_id: String,
+    #[serde(default)]
+    status: u16,
+    #[serde(default)]
+    timestamp: u64,
+    #[serde(default)]
+    index: String,
+    #[serde(default)]
+    collection: String,
+    #[serde(default)]
+    controller: String,
+    #[serde(default)]
+    action: String,
+    #[serde(default)]
+    protocol: String,
+    #[serde(default)]
+    scope: Option<Scope>,
+    #[serde(default)]
+    user: Option<Users>,
+    #[serde(default)]
+    volatile: Map<String, Value>,
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    message: String,
+}
+
+/// The content of a `Notification`, according to its type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotificationKind {
+    /// A document entered (`Scope::In`) or left (`Scope::Out`) the
+    /// subscription scope.
+    Document {
+        scope: Scope,
+        id: String,
+        source: Value,
+    },
+    /// A user joined (`Users::In`) or left (`Users::Out`) the room, which
+    /// now has `count` subscribers.
+    User { user: Users, count: u64 },
+    /// The authentication token of the connection expired.
+    TokenExpired { message: String },
+    /// A notification type unknown to this SDK version.
+    Unknown(String),
+}
+
+impl Notification {
+    /// Notification type getter: `document` or `user`.
+    pub fn notification_type(&self) -> &String {
+        &self.notification_type
+    }
+
+    /// Notification room getter: the channel of the subscription.
+    pub fn room(&self) -> &String {
+        &self.room
+    }
+
+    /// Notification request_id getter: the request which triggered it.
+    pub fn request_id(&self) -> &String {
+        &self.request_id
+    }
+
+    /// Notification status getter.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Notification timestamp getter (epoch-millis).
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Notification index getter.
+    pub fn index(&self) -> &String {
+        &self.index
+    }
+
+    /// Notification collection getter.
+    pub fn collection(&self) -> &String {
+        &self.collection
+    }
+
+    /// Notification controller getter.
+    pub fn controller(&self) -> &String {
+        &self.controller
+    }
+
+    /// Notification action getter.
+    pub fn action(&self) -> &String {
+        &self.action
+    }
+
+    /// Notification protocol getter.
+    pub fn protocol(&self) -> &String {
+        &self.protocol
+    }
+
+    /// Notification scope getter, for document notifications.
+    pub fn scope(&self) -> Option<Scope> {
+        self.scope
+    }
+
+    /// Notification user getter, for user notifications.
+    pub fn user(&self) -> Option<Users> {
+        self.user
+    }
+
+    /// Notification volatile getter.
+    pub fn volatile(&self) -> &Map<String, Value> {
+        &self.volatile
+    }
+
+    /// Notification result getter.
+    pub fn result(&self) -> &Value {
+        &self.result
+    }
+
+    /// Notification message getter, for `TokenExpired` notifications.
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+
+    /// Returns the content of the notification, to `match` on its type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::types::{Notification, NotificationKind, Scope};
+    /// use serde_json::{from_value, json};
+    ///
+    /// let notification: Notification = from_value(json!({
+    ///     "type": "document",
+    ///     "room": "channel-1",
+    ///     "scope": "in",
+    ///     "result": { "_id": "ferris", "_source": { "species": "crab" } }
+    /// })).unwrap();
+    ///
+    /// match notification.kind() {
+    ///     NotificationKind::Document { scope: Scope::In, id, .. } => assert_eq!(id, "ferris"),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn kind(&self) -> NotificationKind {
+        match self.notification_type.as_str() {
+            "document" => NotificationKind::Document {
+                scope: self.scope.unwrap_or_default(),
+                id: self.result["_id"].as_str().unwrap_or_default().to_string(),
+                source: self.result["_source"].clone(),
+            },
+            "user" => NotificationKind::User {
+                user: self.user.unwrap_or_default(),
+                count: self.result["count"].as_u64().unwrap_or_default(),
+            },
+            "TokenExpired" => NotificationKind::TokenExpired {
+                message: self.message.clone(),
+            },
+            other => NotificationKind::Unknown(other.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_str, from_value, json, to_string};
+
+    /// Deserializes a notification, checking it survives a round trip.
+    fn round_trip(sample: &str) -> Notification {
+        let notification: Notification = from_str(sample).unwrap();
+        let serialized = to_string(&notification).unwrap();
+        assert_eq!(from_str::<Notification>(&serialized).unwrap(), notification);
+        notification
+    }
+
+    #[test]
+    fn kind_document() {
+        let notification = round_trip(
+            r#"{
+                "status": 200,
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "timestamp": 1538052587000,
+                "volatile": { "sdkInstanceId": "ferris-sdk" },
+                "index": "ferris_index",
+                "collection": "crabs",
+                "controller": "document",
+                "action": "delete",
+                "protocol": "websocket",
+                "scope": "out",
+                "result": { "_id": "nemo", "_source": { "species": "clownfish" } },
+                "type": "document",
+                "room": "channel-1"
+            }"#,
+        );
+
+        assert_eq!(
+            notification.kind(),
+            NotificationKind::Document {
+                scope: Scope::Out,
+                id: "nemo".to_string(),
+                source: json!({ "species": "clownfish" }),
+            }
+        );
+    }
+
+    #[test]
+    fn kind_user() {
+        let notification = round_trip(
+            r#"{
+                "status": 200,
+                "timestamp": 1538052587000,
+                "volatile": {},
+                "index": "ferris_index",
+                "collection": "crabs",
+                "controller": "realtime",
+                "action": "unsubscribe",
+                "protocol": "websocket",
+                "user": "out",
+                "result": { "count": 1 },
+                "type": "user",
+                "room": "channel-1"
+            }"#,
+        );
+
+        assert_eq!(
+            notification.kind(),
+            NotificationKind::User {
+                user: Users::Out,
+                count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn kind_token_expired() {
+        let notification = round_trip(
+            r#"{
+                "status": 200,
+                "type": "TokenExpired",
+                "message": "Authentication Token Expired"
+            }"#,
+        );
+
+        assert_eq!(notification.room(), "");
+        assert_eq!(
+            notification.kind(),
+            NotificationKind::TokenExpired {
+                message: "Authentication Token Expired".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn kind_unknown() {
+        let notification = round_trip(r#"{ "type": "lobster", "room": "channel-1" }"#);
+
+        assert_eq!(
+            notification.kind(),
+            NotificationKind::Unknown("lobster".to_string())
+        );
+    }
+
+    #[test]
+    fn deserialize_document_notification() {
+        let notification: Notification = from_value(json!({
+            "status": 200,
+            "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+            "timestamp": 1_538_052_587_000u64,
+            "volatile": { "sdkName": "rust" },
+            "index": "ferris_index",
+            "collection": "crabs",
+            "controller": "document",
+            "action": "create",
+            "protocol": "websocket",
+            "scope": "in",
+            "result": { "_id": "ferris", "_source": { "species": "crab" } },
+            "type": "document",
+            "room": "channel-1"
+        }))
+        .unwrap();
+
+        assert_eq!(notification.notification_type(), "document");
+        assert_eq!(notification.room(), "channel-1");
+        assert_eq!(notification.action(), "create");
+        assert_eq!(notification.scope(), Some(Scope::In));
+        assert_eq!(notification.user(), None);
+        assert_eq!(notification.volatile()["sdkName"], json!("rust"));
+        assert_eq!(notification.result()["_id"], json!("ferris"));
+    }
+
+    #[test]
+    fn deserialize_user_notification() {
+        let notification: Notification = from_value(json!({
+            "status": 200,
+            "index": "ferris_index",
+            "collection": "crabs",
+            "controller": "realtime",
+            "action": "subscribe",
+            "protocol": "websocket",
+            "user": "in",
+            "result": { "count": 2 },
+            "type": "user",
+            "room": "channel-1"
+        }))
+        .unwrap();
+
+        assert_eq!(notification.notification_type(), "user");
+        assert_eq!(notification.user(), Some(Users::In));
+        assert_eq!(notification.scope(), None);
+        assert_eq!(notification.result()["count"], json!(2));
+        assert_eq!(notification.request_id(), "");
+    }
+}
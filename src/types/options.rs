@@ -5,11 +5,84 @@ pub enum OfflineMode {
     Auto,
 }
 
+/// Used to choose how messages are framed on the WebSocket connection.
+///
+/// * `Raw` - plain JSON text frames, supported by every Kuzzle version (default).
+/// * `SocketIo` - Socket.IO (engine.io v3) framing, for Kuzzle 1.x servers
+///   only exposing their Socket.IO entry point. Kuzzle 2 dropped Socket.IO.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WebsocketFraming {
+    Raw,
+    SocketIo,
+}
+
+impl WebsocketFraming {
+    /// Returns the path to open the WebSocket connection on.
+    pub fn path(self) -> &'static str {
+        match self {
+            WebsocketFraming::Raw => "/",
+            WebsocketFraming::SocketIo => "/socket.io/?EIO=3&transport=websocket",
+        }
+    }
+
+    /// Frames a JSON request payload into the message to send.
+    pub fn encode(self, payload: &str) -> String {
+        match self {
+            WebsocketFraming::Raw => payload.to_string(),
+            // Engine.io "message" packet (4) holding a Socket.IO "event" packet (2).
+            WebsocketFraming::SocketIo => format!("42[\"kuzzle\",{}]", payload),
+        }
+    }
+
+    /// Extracts the JSON payload of a received message.
+    /// Returns `None` for control messages (handshake, ping, ...).
+    pub fn decode(self, message: &str) -> Option<String> {
+        match self {
+            WebsocketFraming::Raw => Some(message.to_string()),
+            WebsocketFraming::SocketIo => {
+                if !message.starts_with("42") {
+                    return None;
+                }
+
+                match serde_json::from_str::<Value>(&message[2..]) {
+                    Ok(Value::Array(event)) => event.get(1).map(|payload| payload.to_string()),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// Used to choose how a write request waits for its changes to be indexed.
+///
+/// This is a per-write setting: with `WaitFor`, Kuzzle answers once the
+/// written documents are searchable. It must not be confused with the
+/// heavyweight `IndexController::refresh` action, which forces a refresh
+/// of a whole index, nor with the index `autoRefresh` flag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RefreshMode {
+    False,
+    WaitFor,
+}
+
+impl RefreshMode {
+    /// Returns the `refresh` query string value expected by Kuzzle, if any.
+    pub fn as_query_string(self) -> Option<&'static str> {
+        match self {
+            RefreshMode::False => None,
+            RefreshMode::WaitFor => Some("wait_for"),
+        }
+    }
+}
+
+use crate::types::request::redact;
+use crate::types::{RequestIdGenerator, UuidGenerator};
+use serde_json::{Map, Value};
+use std::fmt;
 use std::time;
 
 /// Options are used to configure Kuzzle SDK behavior.
 /// Use them when instanciate `Kuzzle` structure to pass it a set of options.
-#[derive(Debug)]
 pub struct KuzzleOptions {
     _auto_queue: bool,
     _auto_reconnect: bool,
@@ -17,6 +90,11 @@ pub struct KuzzleOptions {
     _auto_resubscribe: bool,
     _host: String,
     _port: u32,
+    _connect_timeout: Option<time::Duration>,
+    _client_metadata: Option<Map<String, Value>>,
+    _request_id_generator: Box<dyn RequestIdGenerator>,
+    _max_response_bytes: Option<u64>,
+    _websocket_framing: WebsocketFraming,
     _offline_mode: OfflineMode,
     _queue_max_size: u32,
     _queue_ttl: time::Duration,
@@ -34,6 +112,11 @@ impl Default for KuzzleOptions {
             _auto_resubscribe: true,
             _host: String::from("localhost"),
             _port: 7512,
+            _connect_timeout: None,
+            _client_metadata: None,
+            _request_id_generator: Box::new(UuidGenerator),
+            _max_response_bytes: None,
+            _websocket_framing: WebsocketFraming::Raw,
             _offline_mode: OfflineMode::Manual,
             _queue_max_size: 500,
             _queue_ttl: time::Duration::from_millis(120000),
@@ -44,6 +127,39 @@ impl Default for KuzzleOptions {
     }
 }
 
+impl fmt::Debug for KuzzleOptions {
+    // The client metadata is user-provided: its secrets (e.g. an api key)
+    // are redacted so options can be logged safely.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let client_metadata = self._client_metadata.as_ref().map(|metadata| {
+            metadata
+                .iter()
+                .map(|(key, value)| (key.clone(), redact(key, value)))
+                .collect::<Map<String, Value>>()
+        });
+
+        f.debug_struct("KuzzleOptions")
+            .field("auto_queue", &self._auto_queue)
+            .field("auto_reconnect", &self._auto_reconnect)
+            .field("auto_replay", &self._auto_replay)
+            .field("auto_resubscribe", &self._auto_resubscribe)
+            .field("host", &self._host)
+            .field("port", &self._port)
+            .field("connect_timeout", &self._connect_timeout)
+            .field("client_metadata", &client_metadata)
+            .field("request_id_generator", &self._request_id_generator)
+            .field("max_response_bytes", &self._max_response_bytes)
+            .field("websocket_framing", &self._websocket_framing)
+            .field("offline_mode", &self._offline_mode)
+            .field("queue_max_size", &self._queue_max_size)
+            .field("queue_ttl", &self._queue_ttl)
+            .field("reconnection_delay", &self._reconnection_delay)
+            .field("replay_interval", &self._replay_interval)
+            .field("ssl_connection", &self._ssl_connection)
+            .finish()
+    }
+}
+
 impl KuzzleOptions {
     /// Returns a KuzzleOption struct with the given host and port.
     /// KuzzleOptions is used to pass options to Kuzzle struct.
@@ -102,6 +218,26 @@ impl KuzzleOptions {
         &self._port
     }
 
+    pub fn connect_timeout(&self) -> &Option<time::Duration> {
+        &self._connect_timeout
+    }
+
+    pub fn client_metadata(&self) -> &Option<Map<String, Value>> {
+        &self._client_metadata
+    }
+
+    pub fn request_id_generator(&self) -> &dyn RequestIdGenerator {
+        &*self._request_id_generator
+    }
+
+    pub fn max_response_bytes(&self) -> &Option<u64> {
+        &self._max_response_bytes
+    }
+
+    pub fn websocket_framing(&self) -> &WebsocketFraming {
+        &self._websocket_framing
+    }
+
     pub fn queue_max_size(&self) -> &u32 {
         &self._queue_max_size
     }
@@ -157,6 +293,46 @@ impl KuzzleOptions {
         self
     }
 
+    /// Gives up connecting to the server after `timeout`, to fail fast on
+    /// unreachable hosts. Unlike the request timeout, it does not cut
+    /// slow responses once the connection is established.
+    pub fn set_connect_timeout(mut self, timeout: time::Duration) -> Self {
+        self._connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Identifies this client to Kuzzle, e.g. for server logs to be
+    /// correlated with client processes: the given entries, along with the
+    /// `hostname`, `pid` and `sdkVersion`, are sent in the WebSocket
+    /// handshake and as `clientMetadata` volatile data over HTTP.
+    pub fn set_client_metadata(mut self, metadata: Map<String, Value>) -> Self {
+        self._client_metadata = Some(metadata);
+        self
+    }
+
+    /// Replaces the UUID v4 request ids, e.g. with a `SequentialGenerator`
+    /// for tests to expect exact request ids.
+    pub fn set_request_id_generator<G>(mut self, generator: G) -> Self
+    where
+        G: 'static + RequestIdGenerator,
+    {
+        self._request_id_generator = Box::new(generator);
+        self
+    }
+
+    /// Responses larger than this limit are rejected with a
+    /// `SizeLimitError`, without buffering more than `max_bytes + 1` bytes
+    /// of their body.
+    pub fn set_max_response_bytes(mut self, max_bytes: u64) -> Self {
+        self._max_response_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn set_websocket_framing(mut self, framing: WebsocketFraming) -> Self {
+        self._websocket_framing = framing;
+        self
+    }
+
     pub fn set_queue_max_size(mut self, max_size: u32) -> Self {
         self._queue_max_size = max_size;
         self
@@ -183,16 +359,267 @@ impl KuzzleOptions {
     }
 }
 
+/// Options are used to tune a single query, e.g. its queuing behavior or
+/// the `refresh` policy applied to write actions.
+#[derive(Clone)]
 pub struct QueryOptions {
     queuable: bool,
+    refresh: RefreshMode,
+    skip_validation: bool,
+    node: Option<String>,
+    from: Option<u64>,
+    size: Option<u64>,
+    scroll: Option<String>,
+    lang: Option<String>,
+    search_after: Option<Vec<Value>>,
+    version: Option<u64>,
+    volatile: Map<String, Value>,
+    notify: bool,
+    silent: bool,
+}
+
+impl Default for QueryOptions {
+    fn default() -> QueryOptions {
+        QueryOptions {
+            queuable: true,
+            refresh: RefreshMode::False,
+            skip_validation: false,
+            node: None,
+            from: None,
+            size: None,
+            scroll: None,
+            lang: None,
+            search_after: None,
+            version: None,
+            volatile: Map::new(),
+            notify: true,
+            silent: false,
+        }
+    }
 }
 
 impl QueryOptions {
     pub fn new() -> QueryOptions {
-        QueryOptions { queuable: true }
+        Self::default()
     }
 
     pub fn queuable(&self) -> bool {
         self.queuable
     }
+
+    pub fn refresh(&self) -> RefreshMode {
+        self.refresh
+    }
+
+    pub fn skip_validation(&self) -> bool {
+        self.skip_validation
+    }
+
+    pub fn node(&self) -> &Option<String> {
+        &self.node
+    }
+
+    pub fn from(&self) -> &Option<u64> {
+        &self.from
+    }
+
+    pub fn size(&self) -> &Option<u64> {
+        &self.size
+    }
+
+    pub fn scroll(&self) -> &Option<String> {
+        &self.scroll
+    }
+
+    pub fn lang(&self) -> &Option<String> {
+        &self.lang
+    }
+
+    pub fn search_after(&self) -> &Option<Vec<Value>> {
+        &self.search_after
+    }
+
+    pub fn version(&self) -> &Option<u64> {
+        &self.version
+    }
+
+    pub fn volatile(&self) -> &Map<String, Value> {
+        &self.volatile
+    }
+
+    pub fn notify(&self) -> bool {
+        self.notify
+    }
+
+    pub fn silent(&self) -> bool {
+        self.silent
+    }
+
+    pub fn set_queuable(mut self, queuable: bool) -> Self {
+        self.queuable = queuable;
+        self
+    }
+
+    /// Sets the `refresh` mode applied to write actions.
+    pub fn set_refresh(mut self, refresh: RefreshMode) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Bypasses the client-side argument checks (e.g. empty index names)
+    /// so the request reaches Kuzzle as is. Only the methods taking a
+    /// `QueryOptions` can skip them, e.g. `IndexController::create_with_options`
+    /// or `DocumentController::count`; the other ones, such as
+    /// `DocumentController::get`, always check their arguments.
+    pub fn set_skip_validation(mut self, skip_validation: bool) -> Self {
+        self.skip_validation = skip_validation;
+        self
+    }
+
+    /// Pins the request to the given Kuzzle cluster node id.
+    pub fn set_node(mut self, node: &str) -> Self {
+        self.node = Some(node.to_string());
+        self
+    }
+
+    /// Sets the offset of the first result to fetch, for paginated searches.
+    pub fn set_from(mut self, from: u64) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Sets the maximum number of results to fetch, for paginated searches.
+    pub fn set_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets the time to live of a search cursor (e.g. `30s`), for scrolled searches.
+    pub fn set_scroll(mut self, scroll: &str) -> Self {
+        self.scroll = Some(scroll.to_string());
+        self
+    }
+
+    /// Sets the language of search and count queries: `koncorde` to use
+    /// Koncorde filters rather than the default Elasticsearch Query DSL.
+    pub fn set_lang(mut self, lang: &str) -> Self {
+        self.lang = Some(lang.to_string());
+        self
+    }
+
+    /// Sets the `sort` values of the last hit of the previous page
+    /// (see `SearchResult::last_sort`), for sorted deep pagination.
+    pub fn set_search_after(mut self, search_after: Vec<Value>) -> Self {
+        self.search_after = Some(search_after);
+        self
+    }
+
+    /// Makes a write action conditional: it is applied only if the document
+    /// is still at the given `_version`, otherwise the query fails with
+    /// a `PreconditionError` (412). Used for optimistic locking.
+    pub fn set_version(mut self, version: u64) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Sets volatile data, forwarded to plugins and realtime subscribers
+    /// but not stored. Its entries take precedence over the global
+    /// volatile data of the Kuzzle client.
+    pub fn set_volatile(mut self, volatile: Map<String, Value>) -> Self {
+        self.volatile = volatile;
+        self
+    }
+
+    /// When `false`, a write action does not trigger realtime notifications
+    /// to the subscribers of the modified documents. Defaults to `true`.
+    pub fn set_notify(mut self, notify: bool) -> Self {
+        self.notify = notify;
+        self
+    }
+
+    /// When `true`, Kuzzle does not answer with the request result, for
+    /// fire-and-forget writes. It has no effect on realtime notifications,
+    /// see `set_notify`.
+    pub fn set_silent(mut self, silent: bool) -> Self {
+        self.silent = silent;
+        self
+    }
+}
+
+/// Options of `MemoryStorageController::set`, mapped to the Redis `SET`
+/// flags: an expiration delay (`EX` in seconds or `PX` in milliseconds)
+/// and a condition on the key existence (`NX` or `XX`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MsSetOptions {
+    ex: Option<u64>,
+    px: Option<u64>,
+    nx: bool,
+    xx: bool,
+}
+
+impl MsSetOptions {
+    pub fn new() -> MsSetOptions {
+        MsSetOptions::default()
+    }
+
+    pub fn ex(&self) -> &Option<u64> {
+        &self.ex
+    }
+
+    pub fn px(&self) -> &Option<u64> {
+        &self.px
+    }
+
+    pub fn nx(&self) -> bool {
+        self.nx
+    }
+
+    pub fn xx(&self) -> bool {
+        self.xx
+    }
+
+    /// Sets the key expiration delay, in seconds.
+    pub fn set_ex(mut self, seconds: u64) -> Self {
+        self.ex = Some(seconds);
+        self
+    }
+
+    /// Sets the key expiration delay, in milliseconds.
+    pub fn set_px(mut self, milliseconds: u64) -> Self {
+        self.px = Some(milliseconds);
+        self
+    }
+
+    /// When `true`, the key is set only if it does not exist yet.
+    pub fn set_nx(mut self, nx: bool) -> Self {
+        self.nx = nx;
+        self
+    }
+
+    /// When `true`, the key is set only if it already exists.
+    pub fn set_xx(mut self, xx: bool) -> Self {
+        self.xx = xx;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn debug_redacts_client_metadata_secrets() {
+        let mut metadata = Map::new();
+        metadata.insert("appName".to_string(), json!("ferris-app"));
+        metadata.insert("api_key".to_string(), json!("ferris-key"));
+        metadata.insert("proxy".to_string(), json!({ "password": "crab" }));
+        let options = KuzzleOptions::new("localhost", 7512).set_client_metadata(metadata);
+        let output = format!("{:?}", options);
+
+        assert!(output.contains("ferris-app"));
+        assert!(!output.contains("ferris-key"));
+        assert!(!output.contains("crab"));
+        assert!(output.contains(r#""api_key": String("***")"#));
+    }
 }
@@ -0,0 +1,368 @@
+//! A `serde_json::Value` deserializer keeping track of the JSON path being
+//! deserialized, so errors can name the offending field (e.g. `hits[0]._id`).
+
+use crate::types::SdkError;
+use serde::de::{DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::map::Iter as MapIter;
+use serde_json::{Error, Map, Value};
+use std::cell::RefCell;
+use std::iter::Enumerate;
+use std::slice::Iter as SliceIter;
+
+/// Deserializes `value` into a `T`. On failure, the serde error is returned
+/// along with the path of the value which failed, empty for the root value.
+pub(crate) fn from_value_with_path<T>(value: &Value) -> Result<T, (String, Error)>
+where
+    T: DeserializeOwned,
+{
+    let failed_path = RefCell::new(None);
+    let deserializer = PathDeserializer {
+        value,
+        path: String::new(),
+        failed_path: &failed_path,
+    };
+
+    T::deserialize(deserializer).map_err(|err| (failed_path.into_inner().unwrap_or_default(), err))
+}
+
+/// Deserializes the `_source` of the document `id` into a `T`, failing
+/// with an `SdkError` naming `cause` and the offending field.
+pub(crate) fn source_as<T>(
+    cause: &str,
+    id: &str,
+    source: &Map<String, Value>,
+) -> Result<T, SdkError>
+where
+    T: DeserializeOwned,
+{
+    from_value_with_path(&Value::Object(source.clone())).map_err(|(path, err)| {
+        let message = if path.is_empty() {
+            format!("cannot deserialize the source of `{}`: {}", id, err)
+        } else {
+            format!(
+                "cannot deserialize the source of `{}` at `{}`: {}",
+                id, path, err
+            )
+        };
+        SdkError::new(cause, &message)
+    })
+}
+
+struct PathDeserializer<'a> {
+    value: &'a Value,
+    path: String,
+    // Set by the deepest deserializer failing, i.e. the first one to fail.
+    failed_path: &'a RefCell<Option<String>>,
+}
+
+impl<'a> PathDeserializer<'a> {
+    fn track<T>(
+        failed_path: &RefCell<Option<String>>,
+        path: String,
+        res: Result<T, Error>,
+    ) -> Result<T, Error> {
+        if res.is_err() && failed_path.borrow().is_none() {
+            *failed_path.borrow_mut() = Some(path);
+        }
+        res
+    }
+
+    /// Walks arrays and objects through path-aware accessors, any other
+    /// value is deserialized by `serde_json` itself.
+    fn deserialize_container<'de, V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let res = match self.value {
+            Value::Array(items) => visitor.visit_seq(PathSeq {
+                items: items.iter().enumerate(),
+                path: self.path.clone(),
+                failed_path: self.failed_path,
+            }),
+            Value::Object(entries) => visitor.visit_map(PathMap {
+                entries: entries.iter(),
+                pending: None,
+                path: self.path.clone(),
+                failed_path: self.failed_path,
+            }),
+            value => value.clone().deserialize_any(visitor),
+        };
+        Self::track(self.failed_path, self.path, res)
+    }
+}
+
+macro_rules! delegate_to_value {
+    ($($method:ident),*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                let res = self.value.clone().$method(visitor);
+                Self::track(self.failed_path, self.path, res)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> Deserializer<'de> for PathDeserializer<'a> {
+    type Error = Error;
+
+    delegate_to_value!(
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_unit,
+        deserialize_identifier,
+        deserialize_ignored_any
+    );
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_container(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let res = self.value.clone().deserialize_unit_struct(name, visitor);
+        Self::track(self.failed_path, self.path, res)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_container(visitor)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_container(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_container(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_container(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_container(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let res = self.value.clone().deserialize_enum(name, variants, visitor);
+        Self::track(self.failed_path, self.path, res)
+    }
+}
+
+struct PathSeq<'a> {
+    items: Enumerate<SliceIter<'a, Value>>,
+    path: String,
+    failed_path: &'a RefCell<Option<String>>,
+}
+
+impl<'de, 'a> SeqAccess<'de> for PathSeq<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.items.next() {
+            Some((index, value)) => seed
+                .deserialize(PathDeserializer {
+                    value,
+                    path: format!("{}[{}]", self.path, index),
+                    failed_path: self.failed_path,
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct PathMap<'a> {
+    entries: MapIter<'a>,
+    pending: Option<(&'a String, &'a Value)>,
+    path: String,
+    failed_path: &'a RefCell<Option<String>>,
+}
+
+impl<'de, 'a> MapAccess<'de> for PathMap<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.pending = Some((key, value));
+                seed.deserialize(Value::String(key.clone())).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self.pending.take().unwrap();
+        let path = if self.path.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", self.path, key)
+        };
+        seed.deserialize(PathDeserializer {
+            value,
+            path,
+            failed_path: self.failed_path,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Hit {
+        #[serde(rename = "_id")]
+        id: String,
+        sort: Option<Vec<u64>>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Page {
+        total: u64,
+        hits: Vec<Hit>,
+    }
+
+    #[test]
+    fn from_value_with_path_ok() {
+        let page: Page = from_value_with_path(&json!({
+            "total": 1,
+            "hits": [{ "_id": "ferris", "sort": [2] }]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            page,
+            Page {
+                total: 1,
+                hits: vec![Hit {
+                    id: "ferris".to_string(),
+                    sort: Some(vec![2])
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn from_value_with_path_fail_nested_field() {
+        let (path, err) = from_value_with_path::<Page>(&json!({
+            "total": 2,
+            "hits": [{ "_id": "ferris" }, { "_id": 42 }]
+        }))
+        .unwrap_err();
+
+        assert_eq!(path, "hits[1]._id");
+        assert!(err.to_string().contains("invalid type"));
+    }
+
+    #[test]
+    fn from_value_with_path_fail_array_item_and_root() {
+        let (path, err) = from_value_with_path::<Page>(&json!({
+            "total": 1,
+            "hits": [{ "_id": "ferris", "sort": [1, "crab"] }, {}]
+        }))
+        .unwrap_err();
+
+        assert_eq!(path, "hits[0].sort[1]");
+        assert!(err.to_string().contains("invalid type"));
+
+        let (path, err) = from_value_with_path::<Page>(&json!({ "hits": [] })).unwrap_err();
+        assert_eq!(path, "");
+        assert!(err.to_string().contains("missing field `total`"));
+    }
+}
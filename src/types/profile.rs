@@ -0,0 +1,82 @@
+use crate::types::Hit;
+use serde_json::{from_value, Value};
+
+/// A policy of a profile: the role it grants, optionally restricted to
+/// some indexes and collections, e.g.
+/// `[{ "index": "ferris_index", "collections": ["crabs"] }]`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Policy {
+    #[serde(rename = "roleId")]
+    role_id: String,
+
+    #[serde(
+        rename = "restrictedTo",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    restricted_to: Option<Vec<Value>>,
+}
+
+impl Policy {
+    pub fn new(role_id: &str) -> Policy {
+        Policy {
+            role_id: role_id.to_string(),
+            restricted_to: None,
+        }
+    }
+
+    /// Policy role_id getter.
+    pub fn role_id(&self) -> &String {
+        &self.role_id
+    }
+
+    /// Policy restricted_to getter.
+    pub fn restricted_to(&self) -> &Option<Vec<Value>> {
+        &self.restricted_to
+    }
+
+    /// Restricts the role to the given indexes and collections.
+    pub fn set_restricted_to(mut self, restricted_to: Vec<Value>) -> Self {
+        self.restricted_to = Some(restricted_to);
+        self
+    }
+}
+
+/// A Kuzzle profile: the policies granting rights to its users.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    _id: String,
+    policies: Vec<Policy>,
+}
+
+impl Profile {
+    pub fn new(id: &str, policies: Vec<Policy>) -> Profile {
+        Profile {
+            _id: id.to_string(),
+            policies,
+        }
+    }
+
+    /// Builds a `Profile` out of a `{ _id, _source }` result.
+    pub(crate) fn from_result(result: &Value) -> Profile {
+        let policies = from_value(result["_source"]["policies"].clone()).unwrap_or_default();
+        Profile::new(result["_id"].as_str().unwrap(), policies)
+    }
+
+    /// Profile id getter.
+    pub fn id(&self) -> &String {
+        &self._id
+    }
+
+    /// Profile policies getter.
+    pub fn policies(&self) -> &Vec<Policy> {
+        &self.policies
+    }
+}
+
+impl From<&Hit> for Profile {
+    /// Builds a `Profile` out of a hit of `SecurityController::search_profiles`.
+    fn from(hit: &Hit) -> Profile {
+        Profile::from_result(hit.raw())
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+/// A snapshot of the offline queue, used to take backpressure decisions.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QueueStats {
+    len: usize,
+    oldest_age: Option<Duration>,
+    dropped_total: u64,
+}
+
+impl QueueStats {
+    pub fn new(len: usize, oldest_age: Option<Duration>, dropped_total: u64) -> QueueStats {
+        QueueStats {
+            len,
+            oldest_age,
+            dropped_total,
+        }
+    }
+
+    /// Number of requests currently queued.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Time spent in the queue by its oldest request, `None` if empty.
+    pub fn oldest_age(&self) -> Option<Duration> {
+        self.oldest_age
+    }
+
+    /// Number of requests dropped since the queue was created, either
+    /// because they outlived `queue_ttl` or because `queue_max_size` was hit.
+    pub fn dropped_total(&self) -> u64 {
+        self.dropped_total
+    }
+}
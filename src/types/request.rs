@@ -1,13 +1,22 @@
-use serde_json::Value;
-use std::collections::HashMap;
+use crate::types::finite_serializer::to_finite_value;
+use crate::types::SdkError;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 
+#[derive(Clone, PartialEq)]
 pub struct KuzzleRequest {
     _controller: String,
     _action: String,
     _index: Option<String>,
     _collection: Option<String>,
+    _id: Option<String>,
     _body: HashMap<String, Value>,
-    _query_strings: HashMap<String, Value>,
+    _query_strings: BTreeMap<String, Value>,
+    _volatile: Map<String, Value>,
+    _jwt: Option<String>,
+    _request_id: Option<String>,
 }
 
 impl KuzzleRequest {
@@ -17,8 +26,12 @@ impl KuzzleRequest {
             _action: action.to_string(),
             _index: None,
             _collection: None,
+            _id: None,
             _body: HashMap::new(),
-            _query_strings: HashMap::new(),
+            _query_strings: BTreeMap::new(),
+            _volatile: Map::new(),
+            _jwt: None,
+            _request_id: None,
         }
     }
 
@@ -38,19 +51,55 @@ impl KuzzleRequest {
         &self._collection
     }
 
+    pub fn id(&self) -> &Option<String> {
+        &self._id
+    }
+
     pub fn body(&self) -> &HashMap<String, Value> {
         &self._body
     }
 
-    pub fn query_strings(&self) -> &HashMap<String, Value> {
+    pub fn query_strings(&self) -> &BTreeMap<String, Value> {
         &self._query_strings
     }
 
+    pub fn volatile(&self) -> &Map<String, Value> {
+        &self._volatile
+    }
+
+    pub fn jwt(&self) -> &Option<String> {
+        &self._jwt
+    }
+
+    pub fn request_id(&self) -> &Option<String> {
+        &self._request_id
+    }
+
     pub fn set_index(mut self, index: &str) -> Self {
         self._index = Some(index.to_string());
         self
     }
 
+    pub fn set_collection(mut self, collection: &str) -> Self {
+        self._collection = Some(collection.to_string());
+        self
+    }
+
+    pub fn set_id(mut self, id: &str) -> Self {
+        self._id = Some(id.to_string());
+        self
+    }
+
+    pub fn set_jwt(mut self, jwt: &str) -> Self {
+        self._jwt = Some(jwt.to_string());
+        self
+    }
+
+    pub fn set_request_id(mut self, request_id: &str) -> Self {
+        self._request_id = Some(request_id.to_string());
+        self
+    }
+
     pub fn add_to_body(mut self, key: String, value: Value) -> Self {
         self._body.insert(key, value);
         self
@@ -60,4 +109,166 @@ impl KuzzleRequest {
         self._query_strings.insert(key, value);
         self
     }
+
+    /// Serializes `value` and adds it to the body, failing with an
+    /// `SdkError` naming the offending path if it holds a NaN or infinite
+    /// float, which `serde_json` would silently turn into `null`.
+    pub fn try_add_to_body<T>(self, key: String, value: &T) -> Result<Self, SdkError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = to_finite_value(&key, value)?;
+        Ok(self.add_to_body(key, value))
+    }
+
+    /// Serializes `value` and adds it to the query strings, with the same
+    /// checks as `try_add_to_body`.
+    pub fn try_add_to_query_strings<T>(self, key: String, value: &T) -> Result<Self, SdkError>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = to_finite_value(&key, value)?;
+        Ok(self.add_to_query_strings(key, value))
+    }
+
+    pub fn add_to_volatile(mut self, key: String, value: Value) -> Self {
+        self._volatile.insert(key, value);
+        self
+    }
+}
+
+impl fmt::Debug for KuzzleRequest {
+    // The JWT and secrets (e.g. `auth/login` credentials, or the token
+    // given to `auth/checkToken`) are redacted so requests can be logged
+    // safely.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let body: BTreeMap<&String, Value> = self
+            ._body
+            .iter()
+            .map(|(key, value)| (key, redact(key, value)))
+            .collect();
+        let query_strings: BTreeMap<&String, Value> = self
+            ._query_strings
+            .iter()
+            .map(|(key, value)| (key, redact(key, value)))
+            .collect();
+
+        f.debug_struct("KuzzleRequest")
+            .field("controller", &self._controller)
+            .field("action", &self._action)
+            .field("index", &self._index)
+            .field("collection", &self._collection)
+            .field("id", &self._id)
+            .field("body", &body)
+            .field("query_strings", &query_strings)
+            .field("volatile", &self._volatile)
+            .field("jwt", &self._jwt.as_ref().map(|_| "***"))
+            .field("request_id", &self._request_id)
+            .finish()
+    }
+}
+
+/// Keys whose values are never printed, at any depth.
+const SECRET_KEYS: &[&str] = &["password", "token", "jwt", "api_key", "apiKey"];
+
+/// Returns a copy of the value stored under `key`, with the secrets it
+/// holds, e.g. `credentials.local.password`, replaced by `***`.
+pub(crate) fn redact(key: &str, value: &Value) -> Value {
+    if SECRET_KEYS.contains(&key) {
+        return Value::from("***");
+    }
+
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), redact(key, value)))
+                .collect(),
+        ),
+        Value::Array(values) => {
+            Value::Array(values.iter().map(|value| redact("", value)).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_redacts_jwt_and_password() {
+        let req = KuzzleRequest::new("auth", "login")
+            .set_jwt("ferris-jwt")
+            .add_to_body("username".to_string(), Value::from("ferris"))
+            .add_to_body("password".to_string(), Value::from("crab-secret"));
+        let output = format!("{:?}", req);
+
+        assert!(!output.contains("ferris-jwt"));
+        assert!(!output.contains("crab-secret"));
+        assert!(output.contains("\"ferris\""));
+        assert!(output.contains("***"));
+    }
+
+    #[test]
+    fn debug_redacts_nested_secrets() {
+        let req = KuzzleRequest::new("security", "createUser")
+            .set_id("ferris")
+            .add_to_body(
+                "content".to_string(),
+                serde_json::json!({ "profileIds": ["default"], "name": "Ferris" }),
+            )
+            .add_to_body(
+                "credentials".to_string(),
+                serde_json::json!({
+                    "local": { "username": "ferris", "password": "crab-secret" },
+                    "oauth": [{ "token": "lobster-token" }]
+                }),
+            );
+        let output = format!("{:?}", req);
+
+        assert!(!output.contains("crab-secret"));
+        assert!(!output.contains("lobster-token"));
+        assert!(output.contains("\"Ferris\""));
+        assert!(output.contains("\"username\": String(\"ferris\")"));
+
+        let req = KuzzleRequest::new("auth", "checkToken")
+            .add_to_body("token".to_string(), Value::from("nemo-jwt"));
+        assert!(!format!("{:?}", req).contains("nemo-jwt"));
+    }
+
+    #[test]
+    fn try_add_to_body_ok() {
+        let req = KuzzleRequest::new("document", "create")
+            .try_add_to_body("legs".to_string(), &10)
+            .unwrap()
+            .try_add_to_body("position".to_string(), &vec![43.6, 3.9])
+            .unwrap();
+
+        assert_eq!(req.body()["legs"], 10);
+        assert_eq!(req.body()["position"], serde_json::json!([43.6, 3.9]));
+    }
+
+    #[test]
+    fn try_add_to_body_fail_nan() {
+        let res = KuzzleRequest::new("document", "create")
+            .try_add_to_body("position".to_string(), &vec![43.6, f64::NAN]);
+
+        let err = res.unwrap_err();
+        assert_eq!(err.message(), "`position[1]` must be a finite number.");
+    }
+
+    #[test]
+    fn body_keeps_large_numbers_exact() {
+        let snowflake: Value = serde_json::from_str("123456789012345678901234567890").unwrap();
+        let req = KuzzleRequest::new("document", "create")
+            .add_to_body("snowflake".to_string(), snowflake)
+            .add_to_body(
+                "_version".to_string(),
+                Value::from(9_007_199_254_740_993u64),
+            );
+        let output = serde_json::to_string(req.body()).unwrap();
+
+        assert!(output.contains("\"snowflake\":123456789012345678901234567890"));
+        assert!(output.contains("\"_version\":9007199254740993"));
+    }
 }
@@ -0,0 +1,87 @@
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+
+/// Generates the ids correlating requests sent over a persistent
+/// connection with their responses, see
+/// `KuzzleOptions::set_request_id_generator`.
+pub trait RequestIdGenerator: fmt::Debug {
+    fn next_id(&self) -> String;
+}
+
+/// Generates random UUID v4 request ids, the default.
+#[derive(Debug, Default)]
+pub struct UuidGenerator;
+
+impl RequestIdGenerator for UuidGenerator {
+    fn next_id(&self) -> String {
+        let uuid = (u128::from(random_u64()) << 64 | u128::from(random_u64()))
+            & !(0xf << 76)
+            & !(0x3 << 62)
+            | 0x4 << 76
+            | 0x2 << 62;
+        format!(
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            uuid >> 96,
+            (uuid >> 80) & 0xffff,
+            (uuid >> 64) & 0xffff,
+            (uuid >> 48) & 0xffff,
+            uuid & 0xffff_ffff_ffff
+        )
+    }
+}
+
+// Each `RandomState` is keyed differently: hashing nothing gives random bits.
+fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// Generates the request ids `1`, `2`, `3`... e.g. for tests to expect
+/// exact request ids.
+#[derive(Debug, Default)]
+pub struct SequentialGenerator {
+    last: Cell<u64>,
+}
+
+impl SequentialGenerator {
+    pub fn new() -> SequentialGenerator {
+        Self::default()
+    }
+}
+
+impl RequestIdGenerator for SequentialGenerator {
+    fn next_id(&self) -> String {
+        self.last.set(self.last.get() + 1);
+        self.last.get().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid_generator_v4() {
+        let generator = UuidGenerator;
+        let id = generator.next_id();
+        let groups: Vec<&str> = id.split('-').collect();
+
+        assert_eq!(
+            groups.iter().map(|group| group.len()).collect::<Vec<_>>(),
+            vec![8, 4, 4, 4, 12]
+        );
+        assert!(groups[2].starts_with('4'));
+        assert!("89ab".contains(&groups[3][..1]));
+        assert_ne!(generator.next_id(), id);
+    }
+
+    #[test]
+    fn sequential_generator() {
+        let generator = SequentialGenerator::new();
+
+        assert_eq!(generator.next_id(), "1");
+        assert_eq!(generator.next_id(), "2");
+        assert_eq!(generator.next_id(), "3");
+    }
+}
@@ -1,4 +1,6 @@
-use crate::types::KuzzleError;
+use crate::types::path_deserializer::from_value_with_path;
+use crate::types::{KuzzleError, SdkError};
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 use std::collections::HashMap;
 
@@ -6,7 +8,8 @@ use std::collections::HashMap;
 /// This format is shared by all  API routes, including routes added by controller plugins.
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct KuzzleResponse {
-    #[serde(rename = "requestId")]
+    // Custom plugin controllers may answer without a request id.
+    #[serde(rename = "requestId", default)]
     request_id: String,
     status: u16,
     error: Option<KuzzleError>,
@@ -24,6 +27,8 @@ pub struct KuzzleResponse {
 
 impl KuzzleResponse {
     /// KuzzleResponse request_id getter.
+    /// Empty if the response did not hold any, as some custom plugin
+    /// controllers do.
     pub fn request_id(&self) -> &String {
         &self.request_id
     }
@@ -63,6 +68,39 @@ impl KuzzleResponse {
         &self.result
     }
 
+    /// Deserializes the result into a `T`.
+    /// On failure, the `SdkError` message names the JSON path of the
+    /// offending field, e.g. `hits[0]._id`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::types::KuzzleResponse;
+    /// use serde_json::json;
+    ///
+    /// let res: KuzzleResponse = serde_json::from_value(json!({
+    ///     "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+    ///     "status": 200,
+    ///     "result": { "now": 1928374619383u64 }
+    /// })).unwrap();
+    ///
+    /// let err = res.result_as::<Vec<u64>>().unwrap_err();
+    /// assert!(err.message().contains("invalid type"));
+    /// ```
+    pub fn result_as<T>(&self) -> Result<T, SdkError>
+    where
+        T: DeserializeOwned,
+    {
+        from_value_with_path(&self.result).map_err(|(path, err)| {
+            let message = if path.is_empty() {
+                format!("unexpected result: {}", err)
+            } else {
+                format!("unexpected result at `{}`: {}", path, err)
+            };
+            SdkError::new("KuzzleResponse::result_as", &message)
+        })
+    }
+
     /// KuzzleResponse volatile getter.
     pub fn volatile(&self) -> &Option<HashMap<String, Value>> {
         &self.volatile
@@ -78,3 +116,76 @@ impl KuzzleResponse {
         &self.channel
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Document, SearchResult};
+    use serde_json::{from_str, from_value, json, to_string};
+
+    #[test]
+    fn result_as_fail_names_field_path() {
+        let res: KuzzleResponse = from_value(json!({
+            "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+            "status": 200,
+            "result": {
+                "total": 2,
+                "hits": [
+                    { "_id": "ferris" },
+                    { "_id": "crab" }
+                ],
+                "aggregations": null,
+                "scrollId": ["not", "a", "string"]
+            }
+        }))
+        .unwrap();
+
+        let err = res.result_as::<SearchResult>().unwrap_err();
+        assert_eq!(err.cause(), "KuzzleResponse::result_as");
+        assert!(err
+            .message()
+            .starts_with("unexpected result at `scrollId`: invalid type"));
+    }
+
+    #[test]
+    fn result_as_ok() {
+        let res: KuzzleResponse = from_value(json!({
+            "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+            "status": 200,
+            "result": { "total": 0, "hits": [] }
+        }))
+        .unwrap();
+
+        let result = res.result_as::<SearchResult>().unwrap();
+        assert_eq!(result.total(), 0);
+    }
+
+    #[test]
+    fn deserialize_ok_missing_request_id() {
+        let res: KuzzleResponse = from_value(json!({
+            "status": 200,
+            "controller": "ferris-plugin/crabs",
+            "action": "count",
+            "result": { "count": 42 }
+        }))
+        .unwrap();
+
+        assert_eq!(res.request_id(), "");
+        assert_eq!(res.result()["count"], 42);
+    }
+
+    #[test]
+    fn large_numbers_round_trip_exactly() {
+        let payload = r#"{"requestId":"da9040aa-9529-4fb9-b627-a38736321364","status":200,"error":null,"controller":"document","action":"get","collection":"ferris_collection","index":"ferris_index","volatile":null,"result":{"_id":"ferris","_source":{"snowflake":123456789012345678901234567890},"_version":9007199254740993},"room":null,"channel":null}"#;
+        let res: KuzzleResponse = from_str(payload).unwrap();
+
+        assert_eq!(to_string(&res).unwrap(), payload);
+
+        let document: Document = res.result_as().unwrap();
+        assert_eq!(document.version(), 9_007_199_254_740_993);
+        assert_eq!(
+            document.source()["snowflake"].to_string(),
+            "123456789012345678901234567890"
+        );
+    }
+}
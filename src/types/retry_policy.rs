@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+/// How many times, and how long apart, a request failing on a connection
+/// error is retried. Errors reported by Kuzzle itself are never retried.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            delay: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns a RetryPolicy retrying 3 times, 500ms apart.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::types::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new()
+    ///     .set_max_retries(5)
+    ///     .set_delay(200);
+    /// assert_eq!(policy.max_retries(), 5);
+    /// ```
+    pub fn new() -> RetryPolicy {
+        Self::default()
+    }
+
+    /// Number of retries after the first attempt.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Time waited before each retry.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    pub fn set_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn set_delay(mut self, delay: u64) -> Self {
+        self.delay = Duration::from_millis(delay);
+        self
+    }
+}
@@ -0,0 +1,46 @@
+/// Whether a right grants access to an API action.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RightValue {
+    Allowed,
+    Denied,
+    Conditional,
+}
+
+/// A right of the current user on an API action, restricted to an
+/// index and a collection (`*` meaning any).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Right {
+    controller: String,
+    action: String,
+    index: String,
+    collection: String,
+    value: RightValue,
+}
+
+impl Right {
+    /// Right controller getter.
+    pub fn controller(&self) -> &String {
+        &self.controller
+    }
+
+    /// Right action getter.
+    pub fn action(&self) -> &String {
+        &self.action
+    }
+
+    /// Right index getter.
+    pub fn index(&self) -> &String {
+        &self.index
+    }
+
+    /// Right collection getter.
+    pub fn collection(&self) -> &String {
+        &self.collection
+    }
+
+    /// Right value getter.
+    pub fn value(&self) -> RightValue {
+        self.value
+    }
+}
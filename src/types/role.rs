@@ -0,0 +1,37 @@
+use serde_json::{Map, Value};
+
+/// A Kuzzle role: the API actions it allows, by controller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Role {
+    _id: String,
+    controllers: Map<String, Value>,
+}
+
+impl Role {
+    pub fn new(id: &str, controllers: Map<String, Value>) -> Role {
+        Role {
+            _id: id.to_string(),
+            controllers,
+        }
+    }
+
+    /// Builds a `Role` out of a `{ _id, _source }` result.
+    pub(crate) fn from_result(result: &Value) -> Role {
+        let controllers = result["_source"]["controllers"]
+            .as_object()
+            .cloned()
+            .unwrap_or_default();
+        Role::new(result["_id"].as_str().unwrap(), controllers)
+    }
+
+    /// Role id getter.
+    pub fn id(&self) -> &String {
+        &self._id
+    }
+
+    /// Role controllers getter, e.g.
+    /// `{ "document": { "actions": { "get": true } } }`.
+    pub fn controllers(&self) -> &Map<String, Value> {
+        &self.controllers
+    }
+}
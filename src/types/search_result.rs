@@ -0,0 +1,132 @@
+use crate::types::{Hit, SdkError};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// A SearchResult holds a page of documents returned by a search,
+/// along with the information needed to fetch the next ones.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    total: u64,
+    hits: Vec<Hit>,
+
+    #[serde(rename = "scrollId")]
+    scroll_id: Option<String>,
+    aggregations: Option<Value>,
+}
+
+impl SearchResult {
+    /// SearchResult total getter.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// SearchResult hits getter.
+    pub fn hits(&self) -> &Vec<Hit> {
+        &self.hits
+    }
+
+    /// Deserializes the source of every hit into a `T`, failing on the
+    /// first one which does not fit.
+    pub fn hits_as<T>(&self) -> Result<Vec<T>, SdkError>
+    where
+        T: DeserializeOwned,
+    {
+        self.hits.iter().map(Hit::source_as).collect()
+    }
+
+    /// SearchResult scroll_id getter.
+    pub fn scroll_id(&self) -> &Option<String> {
+        &self.scroll_id
+    }
+
+    /// SearchResult aggregations getter.
+    pub fn aggregations(&self) -> &Option<Value> {
+        &self.aggregations
+    }
+
+    /// Returns the `sort` values of the last hit, to be passed as
+    /// `search_after` to fetch the next page of a sorted search.
+    /// Returns `None` if there is no hit or if the search was not sorted.
+    pub fn last_sort(&self) -> Option<Vec<Value>> {
+        self.hits
+            .last()
+            .and_then(|hit| hit.raw().get("sort"))
+            .and_then(|sort| sort.as_array())
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_value, json};
+
+    #[test]
+    fn hits_ok_scores() {
+        let result: SearchResult = from_value(json!({
+            "total": 2,
+            "hits": [
+                { "_id": "ferris", "_score": 1.42, "_version": 3, "_source": { "name": "Ferris" } },
+                { "_id": "crab", "_score": null, "_source": { "name": "Crab" }, "sort": ["crab"] }
+            ]
+        }))
+        .unwrap();
+
+        let hits = result.hits();
+        assert_eq!(hits[0].id(), "ferris");
+        assert_eq!(hits[0].score(), Some(1.42));
+        assert_eq!(hits[0].version(), Some(3));
+        assert_eq!(hits[0].source()["name"], "Ferris");
+        assert_eq!(hits[1].score(), None);
+        assert_eq!(hits[1].version(), None);
+        assert_eq!(hits[1].raw()["sort"], json!(["crab"]));
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Crab {
+        name: String,
+        legs: u8,
+    }
+
+    #[test]
+    fn hits_as_ok() {
+        let result: SearchResult = from_value(json!({
+            "total": 2,
+            "hits": [
+                { "_id": "ferris", "_source": { "name": "Ferris", "legs": 10 } },
+                { "_id": "crab", "_source": { "name": "Crab", "legs": 8, "color": "red" } }
+            ]
+        }))
+        .unwrap();
+
+        let crabs: Vec<Crab> = result.hits_as().unwrap();
+        assert_eq!(
+            crabs,
+            vec![
+                Crab {
+                    name: "Ferris".to_string(),
+                    legs: 10
+                },
+                Crab {
+                    name: "Crab".to_string(),
+                    legs: 8
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn hits_as_fail_names_hit_and_field() {
+        let result: SearchResult = from_value(json!({
+            "total": 1,
+            "hits": [{ "_id": "lobster", "_source": { "name": "Lobster", "legs": "ten" } }]
+        }))
+        .unwrap();
+
+        let err = result.hits_as::<Crab>().unwrap_err();
+        assert_eq!(err.cause(), "Hit::source_as");
+        assert!(err
+            .message()
+            .starts_with("cannot deserialize the source of `lobster` at `legs`: invalid type"));
+    }
+}
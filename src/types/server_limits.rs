@@ -0,0 +1,47 @@
+/// The limits a Kuzzle server enforces on requests, as advertised in the
+/// `limits` section of `server/getConfig`. Defaults are Kuzzle's own,
+/// until `Kuzzle::load_server_limits` fetches the deployment values.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ServerLimits {
+    #[serde(
+        rename = "documentsFetchCount",
+        default = "default_documents_fetch_count"
+    )]
+    documents_fetch_count: u64,
+
+    #[serde(
+        rename = "documentsWriteCount",
+        default = "default_documents_write_count"
+    )]
+    documents_write_count: u64,
+}
+
+fn default_documents_fetch_count() -> u64 {
+    10_000
+}
+
+fn default_documents_write_count() -> u64 {
+    200
+}
+
+impl Default for ServerLimits {
+    fn default() -> ServerLimits {
+        ServerLimits {
+            documents_fetch_count: default_documents_fetch_count(),
+            documents_write_count: default_documents_write_count(),
+        }
+    }
+}
+
+impl ServerLimits {
+    /// Maximum number of documents a single search can reach,
+    /// `from` and `size` included.
+    pub fn documents_fetch_count(&self) -> u64 {
+        self.documents_fetch_count
+    }
+
+    /// Maximum number of documents written by a single request.
+    pub fn documents_write_count(&self) -> u64 {
+        self.documents_write_count
+    }
+}
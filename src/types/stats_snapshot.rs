@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+/// A Kuzzle internal statistics snapshot: request and connection counters
+/// per protocol, taken at `timestamp` (epoch-millis).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsSnapshot {
+    #[serde(default)]
+    completed_requests: HashMap<String, u64>,
+    #[serde(default)]
+    failed_requests: HashMap<String, u64>,
+    #[serde(default)]
+    ongoing_requests: HashMap<String, u64>,
+    #[serde(default)]
+    connections: HashMap<String, u64>,
+    timestamp: u64,
+}
+
+impl StatsSnapshot {
+    /// StatsSnapshot completed_requests getter.
+    pub fn completed_requests(&self) -> &HashMap<String, u64> {
+        &self.completed_requests
+    }
+
+    /// StatsSnapshot failed_requests getter.
+    pub fn failed_requests(&self) -> &HashMap<String, u64> {
+        &self.failed_requests
+    }
+
+    /// StatsSnapshot ongoing_requests getter.
+    pub fn ongoing_requests(&self) -> &HashMap<String, u64> {
+        &self.ongoing_requests
+    }
+
+    /// StatsSnapshot connections getter.
+    pub fn connections(&self) -> &HashMap<String, u64> {
+        &self.connections
+    }
+
+    /// StatsSnapshot timestamp getter.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
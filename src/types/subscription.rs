@@ -0,0 +1,216 @@
+use crate::types::QueryOptions;
+use serde_json::Value;
+
+/// Filters notifications according to the document state relative to the
+/// subscription filters: entering (`In`), leaving (`Out`), both (`All`)
+/// or none of them (`None`). Kuzzle accepts `none` for rooms only used to
+/// follow the users joining and leaving them (see `Users`).
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    In,
+    Out,
+    #[default]
+    All,
+    None,
+}
+
+/// Filters user notifications: users joining (`In`) or leaving (`Out`)
+/// the room, both (`All`) or none of them (`None`).
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Users {
+    In,
+    Out,
+    All,
+    #[default]
+    None,
+}
+
+/// Filters notifications according to the document write state:
+/// `Pending` (before the write is done), `Done` (after) or `All`.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    Pending,
+    #[default]
+    Done,
+    All,
+}
+
+/// Options of `RealtimeController::subscribe`, tuning the subscription
+/// itself and the underlying `realtime/subscribe` query.
+#[derive(Clone)]
+pub struct SubscribeOptions {
+    scope: Scope,
+    users: Users,
+    state: State,
+    subscribe_to_self: bool,
+    query_options: QueryOptions,
+}
+
+impl Default for SubscribeOptions {
+    fn default() -> SubscribeOptions {
+        SubscribeOptions {
+            scope: Scope::default(),
+            users: Users::default(),
+            state: State::default(),
+            subscribe_to_self: true,
+            query_options: QueryOptions::new(),
+        }
+    }
+}
+
+impl SubscribeOptions {
+    pub fn new() -> SubscribeOptions {
+        Self::default()
+    }
+
+    pub fn scope(&self) -> Scope {
+        self.scope
+    }
+
+    pub fn users(&self) -> Users {
+        self.users
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn subscribe_to_self(&self) -> bool {
+        self.subscribe_to_self
+    }
+
+    pub fn query_options(&self) -> &QueryOptions {
+        &self.query_options
+    }
+
+    pub fn set_scope(mut self, scope: Scope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    pub fn set_users(mut self, users: Users) -> Self {
+        self.users = users;
+        self
+    }
+
+    pub fn set_state(mut self, state: State) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// With `false`, notifications triggered by the requests of this
+    /// client are not handed to the subscription callback.
+    pub fn set_subscribe_to_self(mut self, subscribe_to_self: bool) -> Self {
+        self.subscribe_to_self = subscribe_to_self;
+        self
+    }
+
+    pub fn set_query_options(mut self, query_options: QueryOptions) -> Self {
+        self.query_options = query_options;
+        self
+    }
+}
+
+/// An active realtime subscription, as registered on the Kuzzle client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubscriptionInfo {
+    room_id: String,
+    channel: String,
+    index: String,
+    collection: String,
+    filters: Value,
+}
+
+impl SubscriptionInfo {
+    pub fn new(
+        room_id: &str,
+        channel: &str,
+        index: &str,
+        collection: &str,
+        filters: Value,
+    ) -> SubscriptionInfo {
+        SubscriptionInfo {
+            room_id: room_id.to_string(),
+            channel: channel.to_string(),
+            index: index.to_string(),
+            collection: collection.to_string(),
+            filters,
+        }
+    }
+
+    /// SubscriptionInfo room_id getter.
+    pub fn room_id(&self) -> &String {
+        &self.room_id
+    }
+
+    /// SubscriptionInfo channel getter.
+    pub fn channel(&self) -> &String {
+        &self.channel
+    }
+
+    /// SubscriptionInfo index getter.
+    pub fn index(&self) -> &String {
+        &self.index
+    }
+
+    /// SubscriptionInfo collection getter.
+    pub fn collection(&self) -> &String {
+        &self.collection
+    }
+
+    /// SubscriptionInfo filters getter.
+    pub fn filters(&self) -> &Value {
+        &self.filters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{from_value, json, to_value};
+
+    #[test]
+    fn scope_serialize() {
+        assert_eq!(to_value(Scope::In).unwrap(), json!("in"));
+        assert_eq!(to_value(Scope::Out).unwrap(), json!("out"));
+        assert_eq!(to_value(Scope::All).unwrap(), json!("all"));
+        assert_eq!(to_value(Scope::None).unwrap(), json!("none"));
+    }
+
+    #[test]
+    fn users_serialize() {
+        assert_eq!(to_value(Users::In).unwrap(), json!("in"));
+        assert_eq!(to_value(Users::Out).unwrap(), json!("out"));
+        assert_eq!(to_value(Users::All).unwrap(), json!("all"));
+        assert_eq!(to_value(Users::None).unwrap(), json!("none"));
+    }
+
+    #[test]
+    fn state_serialize() {
+        assert_eq!(to_value(State::Pending).unwrap(), json!("pending"));
+        assert_eq!(to_value(State::Done).unwrap(), json!("done"));
+        assert_eq!(to_value(State::All).unwrap(), json!("all"));
+    }
+
+    #[test]
+    fn deserialize_rejects_typos() {
+        assert!(from_value::<Scope>(json!("al")).is_err());
+        assert!(from_value::<Users>(json!("non")).is_err());
+        assert!(from_value::<State>(json!("don")).is_err());
+    }
+
+    #[test]
+    fn defaults_match_kuzzle() {
+        assert_eq!(Scope::default(), Scope::All);
+        assert_eq!(Users::default(), Users::None);
+        assert_eq!(State::default(), State::Done);
+
+        let options = SubscribeOptions::default();
+        assert_eq!(options.scope(), Scope::All);
+        assert_eq!(options.users(), Users::None);
+        assert!(options.subscribe_to_self());
+    }
+}
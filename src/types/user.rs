@@ -0,0 +1,50 @@
+use serde_json::{Map, Value};
+
+/// A Kuzzle user, with its content and the ids of the profiles
+/// granting its rights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct User {
+    _id: String,
+    content: Map<String, Value>,
+    profile_ids: Vec<String>,
+}
+
+impl User {
+    pub fn new(id: &str, content: Map<String, Value>, profile_ids: Vec<String>) -> User {
+        User {
+            _id: id.to_string(),
+            content,
+            profile_ids,
+        }
+    }
+
+    /// Builds a `User` out of a `{ _id, _source }` result.
+    pub(crate) fn from_result(result: &Value) -> User {
+        let content = result["_source"].as_object().unwrap().clone();
+        let profile_ids = match content.get("profileIds") {
+            Some(ids) => ids
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|id| id.as_str().unwrap().to_string())
+                .collect(),
+            None => Vec::new(),
+        };
+        User::new(result["_id"].as_str().unwrap(), content, profile_ids)
+    }
+
+    /// User id getter.
+    pub fn id(&self) -> &String {
+        &self._id
+    }
+
+    /// User content getter.
+    pub fn content(&self) -> &Map<String, Value> {
+        &self.content
+    }
+
+    /// User profile_ids getter.
+    pub fn profile_ids(&self) -> &Vec<String> {
+        &self.profile_ids
+    }
+}
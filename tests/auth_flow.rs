@@ -0,0 +1,163 @@
+use kuzzle_sdk::kuzzle::Kuzzle;
+use kuzzle_sdk::protocols::Http;
+use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+use mockito::{self, Matcher};
+use serde_json::json;
+
+/// Builds a Kuzzle instance talking to the mockito server.
+fn kuzzle() -> Kuzzle {
+    let address = mockito::server_address();
+    let host = address.ip().to_string();
+
+    Kuzzle::new(Http::new(KuzzleOptions::new(&host, u32::from(address.port()))))
+}
+
+/// Builds a successful KuzzleResponse body.
+fn response(controller: &str, action: &str, result: &str) -> String {
+    format!(
+        r#"{{
+            "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+            "status": 200,
+            "error": null,
+            "controller": "{}",
+            "action": "{}",
+            "collection": null,
+            "index": null,
+            "volatile": null,
+            "result": {}
+        }}"#,
+        controller, action, result
+    )
+}
+
+#[test]
+fn authorization_header_follows_the_session() {
+    let login = mockito::mock("POST", "/_login/local")
+        .match_header("authorization", Matcher::Missing)
+        .match_body(Matcher::Json(json!({
+            "username": "ferris",
+            "password": "crab-secret"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response(
+            "auth",
+            "login",
+            r#"{ "_id": "ferris", "jwt": "ferris-jwt", "expiresAt": 1321085955000, "ttl": 360000 }"#,
+        ))
+        .expect(1)
+        .create();
+    let now_logged = mockito::mock("GET", "/_now")
+        .match_header("authorization", "Bearer ferris-jwt")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response("server", "now", r#"{ "now": 1447151167622 }"#))
+        .expect(1)
+        .create();
+    let refresh = mockito::mock("POST", "/_refreshToken")
+        .match_header("authorization", "Bearer ferris-jwt")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response(
+            "auth",
+            "refreshToken",
+            r#"{ "_id": "ferris", "jwt": "ferris-jwt-renewed", "expiresAt": 1321085955000, "ttl": 360000 }"#,
+        ))
+        .expect(1)
+        .create();
+    let now_refreshed = mockito::mock("GET", "/_now")
+        .match_header("authorization", "Bearer ferris-jwt-renewed")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response("server", "now", r#"{ "now": 1447151167623 }"#))
+        .expect(1)
+        .create();
+    let logout = mockito::mock("POST", "/_logout")
+        .match_header("authorization", "Bearer ferris-jwt-renewed")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response("auth", "logout", "{}"))
+        .expect(1)
+        .create();
+    let now_anonymous = mockito::mock("GET", "/_now")
+        .match_header("authorization", Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(response("server", "now", r#"{ "now": 1447151167624 }"#))
+        .expect(1)
+        .create();
+
+    let k = kuzzle();
+
+    let jwt = k
+        .auth()
+        .login(
+            "local",
+            json!({ "username": "ferris", "password": "crab-secret" }),
+            QueryOptions::new(),
+        )
+        .unwrap();
+    assert_eq!(jwt, "ferris-jwt");
+    assert_eq!(k.server().now().unwrap(), 1_447_151_167_622);
+
+    assert_eq!(k.auth().refresh_token().unwrap(), "ferris-jwt-renewed");
+    assert_eq!(k.server().now().unwrap(), 1_447_151_167_623);
+
+    k.auth().logout().unwrap();
+    assert!(k.jwt().is_empty());
+    assert_eq!(k.server().now().unwrap(), 1_447_151_167_624);
+
+    login.assert();
+    now_logged.assert();
+    refresh.assert();
+    now_refreshed.assert();
+    logout.assert();
+    now_anonymous.assert();
+}
+
+#[test]
+fn failed_login_keeps_the_session_anonymous() {
+    let login = mockito::mock("POST", "/_login/local")
+        .match_body(Matcher::Json(json!({
+            "username": "nemo",
+            "password": "wrong"
+        })))
+        .with_status(401)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 401,
+                "error": {
+                    "message": "wrong username or password",
+                    "status": 401
+                },
+                "controller": "auth",
+                "action": "login",
+                "collection": null,
+                "index": null,
+                "volatile": null,
+                "result": null
+            }"#,
+        )
+        .expect(1)
+        .create();
+    let refresh = mockito::mock("POST", "/_refreshToken")
+        .match_header("authorization", Matcher::Missing)
+        .expect(0)
+        .create();
+
+    let k = kuzzle();
+
+    let res = k.auth().login(
+        "local",
+        json!({ "username": "nemo", "password": "wrong" }),
+        QueryOptions::new(),
+    );
+    assert!(res.is_err());
+    assert!(k.jwt().is_empty());
+    assert!(k.auth().refresh_token().is_err());
+
+    login.assert();
+    refresh.assert();
+}